@@ -1,7 +1,8 @@
 use crate::errors::AccessControlError;
 use crate::management::{MultipleAddressesManagementTrait, SingleAddressManagementTrait};
 use crate::role::Role;
-use soroban_sdk::{panic_with_error, Address, Env};
+use crate::threshold::ThresholdManagementTrait;
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
 #[derive(Clone)]
 pub struct AccessControl(pub(crate) Env);
@@ -15,11 +16,18 @@ impl AccessControl {
 pub trait AccessControlTrait {
     fn address_has_role(&self, address: &Address, role: &Role) -> bool;
     fn assert_address_has_role(&self, address: &Address, role: &Role);
+    fn assert_addresses_have_role(&self, addresses: &Vec<Address>, role: &Role);
 }
 
 impl AccessControlTrait for AccessControl {
+    // Signer of the role backed by the signers set is considered a role holder.
+    // Threshold is enforced separately by `require_role_signers`
     fn address_has_role(&self, address: &Address, role: &Role) -> bool {
-        if role.has_many_users() {
+        if let Some(signer_set) = self.get_role_signers(role) {
+            return signer_set.signers.contains(address);
+        }
+
+        if role.has_many_users(&self.0) {
             self.get_role_addresses(role).contains(address)
         } else {
             match self.get_role_safe(role) {
@@ -29,9 +37,39 @@ impl AccessControlTrait for AccessControl {
         }
     }
 
+    // Entrypoint check: address holds the role and the signers threshold is reached
     fn assert_address_has_role(&self, address: &Address, role: &Role) {
         if !self.address_has_role(address, role) {
             panic_with_error!(&self.0, AccessControlError::Unauthorized);
         }
+        self.require_role_signers(address, role);
+    }
+
+    // Require authorization from every address and check enough of them hold the role.
+    // If the role is not backed by the signers set, a single holder is enough.
+    fn assert_addresses_have_role(&self, addresses: &Vec<Address>, role: &Role) {
+        let signer_set = self.get_role_signers(role);
+        let threshold = match &signer_set {
+            Some(v) => v.threshold,
+            None => 1,
+        };
+
+        let mut approved: Vec<Address> = Vec::new(&self.0);
+        for address in addresses.iter() {
+            let is_signer = match &signer_set {
+                Some(v) => v.signers.contains(&address),
+                None => self.address_has_role(&address, role),
+            };
+            if !is_signer || approved.contains(&address) {
+                panic_with_error!(&self.0, AccessControlError::Unauthorized);
+            }
+
+            address.require_auth();
+            approved.push_back(address);
+        }
+
+        if approved.len() < threshold {
+            panic_with_error!(&self.0, AccessControlError::Unauthorized);
+        }
     }
 }
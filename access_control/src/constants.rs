@@ -1 +1,6 @@
 pub const ADMIN_ACTIONS_DELAY: u64 = 3 * 86400; // allow changes once per 3 days
+pub const UPGRADE_DELAY: u64 = 3 * 86400; // 3 days
+pub const WASM_ALLOWLIST_DELAY: u64 = 7 * 86400; // 7 days
+pub const MIN_DELAY: u64 = 3600; // configurable delays cannot be less than 1 hour
+pub const EMERGENCY_MODE_DURATION: u64 = 7 * 86400; // emergency mode is disabled automatically after 7 days
+pub const MAX_EMERGENCY_MODE_DURATION: u64 = 30 * 86400; // 30 days
@@ -0,0 +1,210 @@
+use crate::access::{AccessControl, AccessControlTrait};
+use crate::constants::{ADMIN_ACTIONS_DELAY, MIN_DELAY, UPGRADE_DELAY, WASM_ALLOWLIST_DELAY};
+use crate::errors::AccessControlError;
+use crate::events::Events;
+use crate::role::{Role, SymbolRepresentation};
+use crate::storage::DataKey;
+use soroban_sdk::{panic_with_error, Address, Env, Symbol};
+use utils::bump::bump_instance;
+
+// Delays between commit and apply of the admin actions which can be configured per contract
+#[derive(Clone)]
+pub enum Delay {
+    AdminActions,
+    Upgrade,
+    WasmAllowlist,
+    // delay of the role transfer or members change
+    RoleTransfer(Role),
+}
+
+impl Delay {
+    fn default_value(&self, e: &Env) -> u64 {
+        match self {
+            Delay::AdminActions => ADMIN_ACTIONS_DELAY,
+            Delay::Upgrade => UPGRADE_DELAY,
+            Delay::WasmAllowlist => WASM_ALLOWLIST_DELAY,
+            Delay::RoleTransfer(role) => match role.is_transfer_delayed(e) {
+                true => AccessControl::new(e).get_delay(&Delay::AdminActions),
+                false => 0,
+            },
+        }
+    }
+
+    // roles with mandatory delay like Admin and EmergencyAdmin cannot be transferred instantly,
+    // the rest of the roles may be changed without delay
+    fn min_value(&self, e: &Env) -> u64 {
+        match self {
+            Delay::RoleTransfer(role) if !role.is_transfer_delayed(e) => 0,
+            _ => MIN_DELAY,
+        }
+    }
+
+    fn key(&self, e: &Env) -> DataKey {
+        match self {
+            Delay::RoleTransfer(role) => DataKey::RoleTransferDelay(role.as_symbol(e)),
+            _ => DataKey::Delay(self.as_symbol(e)),
+        }
+    }
+
+    fn future_key(&self, e: &Env) -> DataKey {
+        match self {
+            Delay::RoleTransfer(role) => DataKey::FutureRoleTransferDelay(role.as_symbol(e)),
+            _ => DataKey::FutureDelay(self.as_symbol(e)),
+        }
+    }
+
+    fn deadline_key(&self, e: &Env) -> DataKey {
+        match self {
+            Delay::RoleTransfer(role) => DataKey::RoleTransferDelayDeadline(role.as_symbol(e)),
+            _ => DataKey::DelayDeadline(self.as_symbol(e)),
+        }
+    }
+}
+
+impl SymbolRepresentation for Delay {
+    fn as_symbol(&self, e: &Env) -> Symbol {
+        match self {
+            Delay::AdminActions => Symbol::new(e, "AdminActions"),
+            Delay::Upgrade => Symbol::new(e, "Upgrade"),
+            Delay::WasmAllowlist => Symbol::new(e, "WasmAllowlist"),
+            Delay::RoleTransfer(role) => role.as_symbol(e),
+        }
+    }
+
+    // role transfer delays are addressed by the role name through the dedicated methods
+    fn from_symbol(e: &Env, value: Symbol) -> Self {
+        if value == Symbol::new(e, "AdminActions") {
+            return Delay::AdminActions;
+        }
+        if value == Symbol::new(e, "Upgrade") {
+            return Delay::Upgrade;
+        }
+        if value == Symbol::new(e, "WasmAllowlist") {
+            return Delay::WasmAllowlist;
+        }
+        panic_with_error!(e, AccessControlError::UnknownDelay)
+    }
+}
+
+// Delay value change is an admin action itself, so it's applied after the current delay passes
+pub trait DelaysTrait {
+    fn get_delay(&self, delay: &Delay) -> u64;
+    fn get_future_delay(&self, delay: &Delay) -> u64;
+    fn get_delay_deadline(&self, delay: &Delay) -> u64;
+    fn commit_delay(&self, delay: &Delay, value: u64);
+    fn apply_delay(&self, delay: &Delay) -> u64;
+    fn revert_delay(&self, delay: &Delay);
+}
+
+impl DelaysTrait for AccessControl {
+    fn get_delay(&self, delay: &Delay) -> u64 {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .get(&delay.key(&self.0))
+            .unwrap_or(delay.default_value(&self.0))
+    }
+
+    fn get_future_delay(&self, delay: &Delay) -> u64 {
+        match self.get_delay_deadline(delay) {
+            0 => self.get_delay(delay),
+            _ => self
+                .0
+                .storage()
+                .instance()
+                .get(&delay.future_key(&self.0))
+                .unwrap_or(self.get_delay(delay)),
+        }
+    }
+
+    fn get_delay_deadline(&self, delay: &Delay) -> u64 {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .get(&delay.deadline_key(&self.0))
+            .unwrap_or(0)
+    }
+
+    fn commit_delay(&self, delay: &Delay, value: u64) {
+        if value < delay.min_value(&self.0) {
+            panic_with_error!(&self.0, AccessControlError::DelayTooShort);
+        }
+        if self.get_delay_deadline(delay) != 0 {
+            panic_with_error!(&self.0, AccessControlError::AnotherActionActive);
+        }
+
+        let deadline = self.0.ledger().timestamp() + self.get_delay(delay);
+        bump_instance(&self.0);
+        let storage = self.0.storage().instance();
+        storage.set(&delay.deadline_key(&self.0), &deadline);
+        storage.set(&delay.future_key(&self.0), &value);
+    }
+
+    fn apply_delay(&self, delay: &Delay) -> u64 {
+        let deadline = self.get_delay_deadline(delay);
+        if self.0.ledger().timestamp() < deadline {
+            panic_with_error!(&self.0, AccessControlError::ActionNotReadyYet);
+        }
+        if deadline == 0 {
+            panic_with_error!(&self.0, AccessControlError::NoActionActive);
+        }
+
+        let value = self.get_future_delay(delay);
+        bump_instance(&self.0);
+        let storage = self.0.storage().instance();
+        storage.set(&delay.deadline_key(&self.0), &0_u64);
+        storage.set(&delay.key(&self.0), &value);
+        value
+    }
+
+    fn revert_delay(&self, delay: &Delay) {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .set(&delay.deadline_key(&self.0), &0_u64);
+    }
+}
+
+// Commit new value for the delay by the admin
+pub fn commit_delay(e: &Env, admin: &Address, delay_name: Symbol, value: u64) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let delay = Delay::from_symbol(e, delay_name);
+    access_control.commit_delay(&delay, value);
+    Events::new(e).commit_delay(delay, value);
+}
+
+// Apply the committed delay value by the admin
+pub fn apply_delay(e: &Env, admin: &Address, delay_name: Symbol) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let delay = Delay::from_symbol(e, delay_name);
+    let value = access_control.apply_delay(&delay);
+    Events::new(e).apply_delay(delay, value);
+}
+
+// Revert the committed delay value by the admin
+pub fn revert_delay(e: &Env, admin: &Address, delay_name: Symbol) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let delay = Delay::from_symbol(e, delay_name);
+    access_control.revert_delay(&delay);
+    Events::new(e).revert_delay(delay);
+}
+
+pub fn get_delay(e: &Env, delay_name: Symbol) -> u64 {
+    AccessControl::new(e).get_delay(&Delay::from_symbol(e, delay_name))
+}
+
+pub fn get_future_delay(e: &Env, delay_name: Symbol) -> u64 {
+    AccessControl::new(e).get_future_delay(&Delay::from_symbol(e, delay_name))
+}
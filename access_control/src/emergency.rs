@@ -1,35 +1,157 @@
+use crate::access::{AccessControl, AccessControlTrait};
+use crate::constants::{EMERGENCY_MODE_DURATION, MAX_EMERGENCY_MODE_DURATION};
+use crate::errors::AccessControlError;
+use crate::events::Events;
+use crate::role::Role;
 use crate::storage::DataKey;
-use soroban_sdk::Env;
+use soroban_sdk::{contracttype, panic_with_error, Address, Env};
 use utils::bump::bump_instance;
 
 #[cfg(feature = "certora")]
 use ghost_state::GhostState;
 
+// Powers unlocked by the emergency mode.
+// Emergency mode does not grant any new roles, pausing stays with the pause admins
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EmergencyScope {
+    All = 0,
+    Upgrade = 1, // instant upgrade
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyModeState {
+    pub enabled: bool,
+    pub scope: EmergencyScope,
+    pub expires_at: u64, // u64::MAX if enabled before the expiration was introduced
+    pub remaining: u64,
+}
+
+// Emergency mode enabled by the previous contract version has no expiry stored.
+// It stays enabled until turned off explicitly, same as before the upgrade
+fn get_emergency_mode_expiry(e: &Env) -> Option<u64> {
+    e.storage().instance().get(&DataKey::EmergencyModeExpiry)
+}
+
+fn get_emergency_mode_scope(e: &Env) -> EmergencyScope {
+    e.storage()
+        .instance()
+        .get(&DataKey::EmergencyModeScope)
+        .unwrap_or(EmergencyScope::All)
+}
+
+// Emergency mode is disabled automatically once expired
 pub fn get_emergency_mode(e: &Env) -> bool {
     bump_instance(e);
-    let value = e.storage()
+    let flag = e.storage()
         .instance()
         .get(&DataKey::EmergencyMode)
         .unwrap_or(false);
-    
+    let value = flag
+        && match get_emergency_mode_expiry(e) {
+            Some(expiry) => e.ledger().timestamp() < expiry,
+            None => true,
+        };
+
     #[cfg(feature = "certora")]
     {
         GhostState::update(|state| {
             state.emergency_mode = value;
         });
     }
-    
+
     value
 }
 
+// Enabled with all powers for the default duration
 pub fn set_emergency_mode(e: &Env, value: &bool) {
+    match value {
+        true => enable_emergency_mode(e, &EmergencyScope::All, EMERGENCY_MODE_DURATION),
+        false => {
+            bump_instance(e);
+            e.storage().instance().set(&DataKey::EmergencyMode, value);
+            e.storage().instance().remove(&DataKey::EmergencyModeExpiry);
+
+            #[cfg(feature = "certora")]
+            {
+                GhostState::update(|state| {
+                    state.emergency_mode = false;
+                });
+            }
+        }
+    }
+}
+
+pub fn enable_emergency_mode(e: &Env, scope: &EmergencyScope, duration: u64) {
+    if duration == 0 || duration > MAX_EMERGENCY_MODE_DURATION {
+        panic_with_error!(e, AccessControlError::BadEmergencyModeDuration);
+    }
+
     bump_instance(e);
-    e.storage().instance().set(&DataKey::EmergencyMode, value);
-    
+    let storage = e.storage().instance();
+    storage.set(&DataKey::EmergencyMode, &true);
+    storage.set(&DataKey::EmergencyModeScope, scope);
+    storage.set(
+        &DataKey::EmergencyModeExpiry,
+        &(e.ledger().timestamp() + duration),
+    );
+
     #[cfg(feature = "certora")]
     {
         GhostState::update(|state| {
-            state.emergency_mode = *value;
+            state.emergency_mode = true;
         });
     }
-}
\ No newline at end of file
+}
+
+// Check emergency mode is enabled and unlocks given power
+pub fn is_emergency_mode_active(e: &Env, scope: &EmergencyScope) -> bool {
+    if !get_emergency_mode(e) {
+        return false;
+    }
+    let current_scope = get_emergency_mode_scope(e);
+    current_scope == EmergencyScope::All || current_scope == *scope
+}
+
+pub fn get_emergency_mode_state(e: &Env) -> EmergencyModeState {
+    let enabled = get_emergency_mode(e);
+    let expires_at = match enabled {
+        true => get_emergency_mode_expiry(e).unwrap_or(u64::MAX),
+        false => 0,
+    };
+    EmergencyModeState {
+        enabled,
+        scope: get_emergency_mode_scope(e),
+        expires_at,
+        remaining: expires_at.saturating_sub(e.ledger().timestamp()),
+    }
+}
+
+// Set emergency mode on behalf of the emergency admin. Enabled with all powers for the default duration
+pub fn toggle_emergency_mode(e: &Env, emergency_admin: &Address, value: bool) {
+    emergency_admin.require_auth();
+    AccessControl::new(e).assert_address_has_role(emergency_admin, &Role::EmergencyAdmin);
+
+    set_emergency_mode(e, &value);
+    let events = Events::new(e);
+    events.set_emergency_mode_state(get_emergency_mode_state(e));
+    events.set_emergency_mode(value);
+}
+
+// Enable emergency mode with the given scope and duration on behalf of the emergency admin
+pub fn enable_scoped_emergency_mode(
+    e: &Env,
+    emergency_admin: &Address,
+    scope: &EmergencyScope,
+    duration: u64,
+) {
+    emergency_admin.require_auth();
+    AccessControl::new(e).assert_address_has_role(emergency_admin, &Role::EmergencyAdmin);
+
+    enable_emergency_mode(e, scope, duration);
+    let events = Events::new(e);
+    events.set_emergency_mode_state(get_emergency_mode_state(e));
+    events.set_emergency_mode(true);
+}
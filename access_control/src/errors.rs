@@ -8,6 +8,11 @@ pub enum AccessControlError {
     Unauthorized = 102,
     AdminAlreadySet = 103,
     BadRoleUsage = 104,
+    RoleAlreadyExists = 105,
+    InvalidSignerSet = 106,
+    DelayTooShort = 107,
+    UnknownDelay = 108,
+    BadEmergencyModeDuration = 109,
 
     // transfer ownership errors
     AnotherActionActive = 2906,
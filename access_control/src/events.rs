@@ -1,4 +1,6 @@
-use crate::role::{Role, SymbolRepresentation};
+use crate::delays::Delay;
+use crate::emergency::EmergencyModeState;
+use crate::role::{Role, RoleConfig, SymbolRepresentation};
 use soroban_sdk::{Address, Env, Symbol, Vec};
 
 #[derive(Clone)]
@@ -63,6 +65,143 @@ impl Events {
         )
     }
 
+    pub fn register_role(&self, name: Symbol, config: RoleConfig) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "register_role"), name),
+            (config.has_many_users, config.is_transfer_delayed),
+        )
+    }
+
+    pub fn commit_role_signers(&self, role: Role, signers: Vec<Address>, threshold: u32) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "commit_role_signers"),
+                role.as_symbol(self.env()),
+            ),
+            (signers, threshold),
+        )
+    }
+
+    pub fn apply_role_signers(&self, role: Role, signers: Vec<Address>, threshold: u32) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "apply_role_signers"),
+                role.as_symbol(self.env()),
+            ),
+            (signers, threshold),
+        )
+    }
+
+    pub fn revert_role_signers(&self, role: Role) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "revert_role_signers"),
+                role.as_symbol(self.env()),
+            ),
+            (),
+        )
+    }
+
+    pub fn set_role_cosigners(&self, role: Role, signer: Address, cosigners: Vec<Address>) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "set_role_cosigners"),
+                role.as_symbol(self.env()),
+            ),
+            (signer, cosigners),
+        )
+    }
+
+    pub fn commit_transfer_delay(&self, role: Role, delay: u64) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "commit_transfer_delay"),
+                role.as_symbol(self.env()),
+            ),
+            (delay,),
+        )
+    }
+
+    pub fn apply_transfer_delay(&self, role: Role, delay: u64) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "apply_transfer_delay"),
+                role.as_symbol(self.env()),
+            ),
+            (delay,),
+        )
+    }
+
+    pub fn revert_transfer_delay(&self, role: Role) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "revert_transfer_delay"),
+                role.as_symbol(self.env()),
+            ),
+            (),
+        )
+    }
+
+    pub fn commit_member_change(&self, role: Role, address: Address, is_added: bool) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "commit_member_change"),
+                role.as_symbol(self.env()),
+            ),
+            (address, is_added),
+        )
+    }
+
+    pub fn apply_member_change(&self, role: Role, addresses: Vec<Address>) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "apply_member_change"),
+                role.as_symbol(self.env()),
+            ),
+            (addresses,),
+        )
+    }
+
+    pub fn revert_member_change(&self, role: Role) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "revert_member_change"),
+                role.as_symbol(self.env()),
+            ),
+            (),
+        )
+    }
+
+    pub fn commit_delay(&self, delay: Delay, value: u64) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "commit_delay"),
+                delay.as_symbol(self.env()),
+            ),
+            (value,),
+        )
+    }
+
+    pub fn apply_delay(&self, delay: Delay, value: u64) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "apply_delay"),
+                delay.as_symbol(self.env()),
+            ),
+            (value,),
+        )
+    }
+
+    pub fn revert_delay(&self, delay: Delay) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "revert_delay"),
+                delay.as_symbol(self.env()),
+            ),
+            (),
+        )
+    }
+
     pub fn set_emergency_mode(&self, emergency_mode: bool) {
         self.env().events().publish(
             match emergency_mode {
@@ -72,4 +211,11 @@ impl Events {
             (),
         )
     }
+
+    pub fn set_emergency_mode_state(&self, state: EmergencyModeState) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "emergency_mode_state"),),
+            (state.enabled, state.scope, state.expires_at),
+        )
+    }
 }
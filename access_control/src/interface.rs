@@ -1,4 +1,7 @@
-use soroban_sdk::{Address, Env, Symbol};
+use crate::emergency::{EmergencyModeState, EmergencyScope};
+use crate::role::RoleConfig;
+use crate::view::AccessControlState;
+use soroban_sdk::{Address, Env, Symbol, Vec};
 
 pub trait TransferableContract {
     // Commit ownership transfer
@@ -13,3 +16,106 @@ pub trait TransferableContract {
     // Get future address for transfer ownership process
     fn get_future_address(e: Env, role_name: Symbol) -> Address;
 }
+
+pub trait RoleTimelockContract {
+    // Commit delay between commit and apply of the role transfer or members change.
+    // Applied after the current delay of the role passes
+    fn commit_role_transfer_delay(e: Env, admin: Address, role_name: Symbol, delay: u64);
+
+    // Apply committed role transfer delay
+    fn apply_role_transfer_delay(e: Env, admin: Address, role_name: Symbol);
+
+    // Revert committed role transfer delay
+    fn revert_role_transfer_delay(e: Env, admin: Address, role_name: Symbol);
+
+    // Get delay between commit and apply of the role transfer or members change
+    fn get_role_transfer_delay(e: Env, role_name: Symbol) -> u64;
+
+    // Get future role transfer delay. Defaults to current value if change is not committed
+    fn get_future_role_transfer_delay(e: Env, role_name: Symbol) -> u64;
+
+    // Commit address addition to the multiple addresses role
+    fn commit_add_role_member(e: Env, admin: Address, role_name: Symbol, address: Address);
+
+    // Commit address removal from the multiple addresses role
+    fn commit_remove_role_member(e: Env, admin: Address, role_name: Symbol, address: Address);
+
+    // Apply committed members change. Returns new members list
+    fn apply_role_members_change(e: Env, admin: Address, role_name: Symbol) -> Vec<Address>;
+
+    // Revert committed members change
+    fn revert_role_members_change(e: Env, admin: Address, role_name: Symbol);
+
+    // Get committed members change as (address, is_added) pair
+    fn get_pending_role_members_change(e: Env, role_name: Symbol) -> Option<(Address, bool)>;
+}
+
+pub trait RoleRegistryContract {
+    // Declare custom role. Members are granted through the regular transfer or members change
+    fn register_role(e: Env, admin: Address, role_name: Symbol, config: RoleConfig);
+
+    // Get names of the declared custom roles
+    fn get_custom_roles(e: Env) -> Vec<Symbol>;
+
+    // Check address holds the role
+    fn has_role(e: Env, role_name: Symbol, address: Address) -> bool;
+}
+
+pub trait ConfigurableDelaysContract {
+    // Commit new value for the delay. Applied after the current delay passes
+    fn commit_delay(e: Env, admin: Address, delay_name: Symbol, value: u64);
+
+    // Apply committed delay value
+    fn apply_delay(e: Env, admin: Address, delay_name: Symbol);
+
+    // Revert committed delay value
+    fn revert_delay(e: Env, admin: Address, delay_name: Symbol);
+
+    // Get current delay value
+    fn get_delay(e: Env, delay_name: Symbol) -> u64;
+
+    // Get future delay value. Defaults to current value if change is not committed
+    fn get_future_delay(e: Env, delay_name: Symbol) -> u64;
+}
+
+pub trait AdminSignersContract {
+    // Commit M-of-N signers set backing the admin role. Applied after the admin transfer delay.
+    // Empty signers list switches back to the single admin address
+    fn commit_admin_signers(e: Env, admin: Address, signers: Vec<Address>, threshold: u32);
+
+    // Apply committed admin signers set
+    fn apply_admin_signers(e: Env, admin: Address);
+
+    // Revert committed admin signers set
+    fn revert_admin_signers(e: Env, admin: Address);
+
+    // Get admin signers set and threshold. Empty set if not configured
+    fn get_admin_signers(e: Env) -> (Vec<Address>, u32);
+
+    // Get committed admin signers set and threshold
+    fn get_future_admin_signers(e: Env) -> Option<(Vec<Address>, u32)>;
+
+    // Nominate admin signers authorizing the admin actions of the signer together with it
+    fn set_admin_cosigners(e: Env, signer: Address, cosigners: Vec<Address>);
+
+    // Get admin signers nominated by the signer
+    fn get_admin_cosigners(e: Env, signer: Address) -> Vec<Address>;
+}
+
+pub trait EmergencyModeContract {
+    // Enable emergency mode unlocking given powers until it expires
+    fn enable_emergency_mode(
+        e: Env,
+        emergency_admin: Address,
+        scope: EmergencyScope,
+        duration: u64,
+    );
+
+    // Get emergency mode scope, expiration time and remaining time
+    fn get_emergency_mode_state(e: Env) -> EmergencyModeState;
+}
+
+pub trait AccessControlStateContract {
+    // Get roles with pending changes and configurable delays in a single call
+    fn get_admin_state(e: Env) -> AccessControlState;
+}
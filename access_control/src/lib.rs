@@ -1,15 +1,19 @@
 #![no_std]
 pub mod access;
 pub mod constants;
+pub mod delays;
 pub mod emergency;
 pub mod errors;
 pub mod events;
 pub mod interface;
 pub mod management;
+pub mod registry;
 pub mod role;
 #[cfg(feature = "certora")]
 pub mod storage;
 #[cfg(not(feature = "certora"))]
 mod storage;
+pub mod threshold;
 pub mod transfer;
 pub mod utils;
+pub mod view;
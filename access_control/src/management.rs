@@ -2,6 +2,7 @@ use crate::access::AccessControl;
 use crate::errors::AccessControlError;
 use crate::role::Role;
 use crate::storage::StorageTrait;
+use crate::transfer::TransferOwnershipTrait;
 use soroban_sdk::{panic_with_error, Address, Vec};
 use utils::bump::bump_instance;
 
@@ -21,7 +22,7 @@ pub trait MultipleAddressesManagementTrait {
 
 impl SingleAddressManagementTrait for AccessControl {
     fn get_role_safe(&self, role: &Role) -> Option<Address> {
-        if role.has_many_users() {
+        if role.has_many_users(&self.0) {
             panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
         }
 
@@ -64,14 +65,15 @@ impl SingleAddressManagementTrait for AccessControl {
     }
 
     fn set_role_address(&self, role: &Role, address: &Address) {
-        if role.has_many_users() {
+        if role.has_many_users(&self.0) {
             panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
         }
 
         // require delay if address is being replaced.
-        // don't require delay if role is being set for the first time
+        // don't require delay if role is being set for the first time or address is not changed
         let addr = self.get_role_safe(role);
-        if addr.is_some() && role.is_transfer_delayed() {
+        let is_delayed = role.is_transfer_delayed(&self.0) || self.get_transfer_delay(role) > 0;
+        if is_delayed && addr.is_some() && addr != Some(address.clone()) {
             panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
         }
 
@@ -99,7 +101,7 @@ impl SingleAddressManagementTrait for AccessControl {
 
 impl MultipleAddressesManagementTrait for AccessControl {
     fn get_role_addresses(&self, role: &Role) -> Vec<Address> {
-        if !role.has_many_users() {
+        if !role.has_many_users(&self.0) {
             panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
         }
 
@@ -126,12 +128,19 @@ impl MultipleAddressesManagementTrait for AccessControl {
         value
     }
 
-    // no delay-related code as we require it only for single addresses roles
+    // if members changes are delayed, list can be changed only through the members change
     fn set_role_addresses(&self, role: &Role, addresses: &Vec<Address>) {
-        if !role.has_many_users() || role.is_transfer_delayed() {
+        if !role.has_many_users(&self.0) || role.is_transfer_delayed(&self.0) {
             panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
         }
 
+        if self.get_transfer_delay(role) > 0 {
+            let current_addresses = self.get_role_addresses(role);
+            if !current_addresses.is_empty() && &current_addresses != addresses {
+                panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
+            }
+        }
+
         let key = self.get_key(role);
         bump_instance(&self.0);
         self.0.storage().instance().set(&key, addresses);
@@ -0,0 +1,63 @@
+use crate::access::AccessControl;
+use crate::errors::AccessControlError;
+use crate::role::{Role, RoleConfig};
+use crate::storage::DataKey;
+use soroban_sdk::{panic_with_error, Symbol, Vec};
+use utils::bump::bump_instance;
+
+// Registry of the custom roles declared by the contract in addition to the built-in ones
+pub trait RoleRegistryTrait {
+    fn register_role(&self, name: &Symbol, config: &RoleConfig);
+    fn is_role_registered(&self, name: &Symbol) -> bool;
+    fn get_role_config(&self, name: &Symbol) -> RoleConfig;
+    fn get_custom_roles(&self) -> Vec<Symbol>;
+}
+
+impl RoleRegistryTrait for AccessControl {
+    fn register_role(&self, name: &Symbol, config: &RoleConfig) {
+        if Role::is_builtin(&self.0, name) {
+            panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
+        }
+        if self.is_role_registered(name) {
+            panic_with_error!(&self.0, AccessControlError::RoleAlreadyExists);
+        }
+
+        bump_instance(&self.0);
+        let storage = self.0.storage().instance();
+        storage.set(&DataKey::RoleConfig(name.clone()), config);
+
+        let mut roles = self.get_custom_roles();
+        roles.push_back(name.clone());
+        storage.set(&DataKey::CustomRoles, &roles);
+    }
+
+    fn is_role_registered(&self, name: &Symbol) -> bool {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .has(&DataKey::RoleConfig(name.clone()))
+    }
+
+    fn get_role_config(&self, name: &Symbol) -> RoleConfig {
+        bump_instance(&self.0);
+        match self
+            .0
+            .storage()
+            .instance()
+            .get(&DataKey::RoleConfig(name.clone()))
+        {
+            Some(v) => v,
+            None => panic_with_error!(&self.0, AccessControlError::RoleNotFound),
+        }
+    }
+
+    fn get_custom_roles(&self) -> Vec<Symbol> {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .get(&DataKey::CustomRoles)
+            .unwrap_or(Vec::new(&self.0))
+    }
+}
@@ -1,5 +1,7 @@
+use crate::access::AccessControl;
 use crate::errors::AccessControlError;
-use soroban_sdk::{panic_with_error, Env, Symbol};
+use crate::registry::RoleRegistryTrait;
+use soroban_sdk::{contracttype, panic_with_error, Env, Symbol};
 
 #[derive(Clone)]
 pub enum Role {
@@ -9,56 +11,91 @@ pub enum Role {
     OperationsAdmin,
     PauseAdmin,
     EmergencyPauseAdmin,
+    Custom(Symbol), // role declared by the contract in the roles registry
+}
+
+// Role properties. Built-in roles have them hardcoded, custom ones are stored in the registry
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RoleConfig {
+    pub has_many_users: bool,
+    pub is_transfer_delayed: bool,
 }
 
-// Certora: `pub(crate)` -> `pub`
-#[cfg(feature = "certora")]
 impl Role {
-    pub fn has_many_users(&self) -> bool {
+    pub fn get_config(&self, e: &Env) -> RoleConfig {
         match self {
-            Role::Admin => false,
-            Role::EmergencyAdmin => false,
-            Role::RewardsAdmin => false,
-            Role::OperationsAdmin => false,
-            Role::PauseAdmin => false,
-            Role::EmergencyPauseAdmin => true,
+            Role::Admin => RoleConfig {
+                has_many_users: false,
+                is_transfer_delayed: true,
+            },
+            Role::EmergencyAdmin => RoleConfig {
+                has_many_users: false,
+                is_transfer_delayed: true,
+            },
+            Role::RewardsAdmin => RoleConfig {
+                has_many_users: false,
+                is_transfer_delayed: false,
+            },
+            Role::OperationsAdmin => RoleConfig {
+                has_many_users: false,
+                is_transfer_delayed: false,
+            },
+            Role::PauseAdmin => RoleConfig {
+                has_many_users: false,
+                is_transfer_delayed: false,
+            },
+            Role::EmergencyPauseAdmin => RoleConfig {
+                has_many_users: true,
+                is_transfer_delayed: false,
+            },
+            Role::Custom(name) => AccessControl::new(e).get_role_config(name),
         }
     }
 
-    pub fn is_transfer_delayed(&self) -> bool {
-        match self {
-            Role::Admin => true,
-            Role::EmergencyAdmin => true,
-            Role::RewardsAdmin => false,
-            Role::OperationsAdmin => false,
-            Role::PauseAdmin => false,
-            Role::EmergencyPauseAdmin => false,
+    pub fn is_builtin(e: &Env, name: &Symbol) -> bool {
+        Role::builtin_from_symbol(e, name).is_some()
+    }
+
+    fn builtin_from_symbol(e: &Env, value: &Symbol) -> Option<Role> {
+        if value == &Symbol::new(e, "Admin") {
+            Some(Role::Admin)
+        } else if value == &Symbol::new(e, "EmergencyAdmin") {
+            Some(Role::EmergencyAdmin)
+        } else if value == &Symbol::new(e, "RewardsAdmin") {
+            Some(Role::RewardsAdmin)
+        } else if value == &Symbol::new(e, "OperationsAdmin") {
+            Some(Role::OperationsAdmin)
+        } else if value == &Symbol::new(e, "PauseAdmin") {
+            Some(Role::PauseAdmin)
+        } else if value == &Symbol::new(e, "EmergencyPauseAdmin") {
+            Some(Role::EmergencyPauseAdmin)
+        } else {
+            None
         }
     }
 }
 
+// Certora: `pub(crate)` -> `pub`
+#[cfg(feature = "certora")]
+impl Role {
+    pub fn has_many_users(&self, e: &Env) -> bool {
+        self.get_config(e).has_many_users
+    }
+
+    pub fn is_transfer_delayed(&self, e: &Env) -> bool {
+        self.get_config(e).is_transfer_delayed
+    }
+}
+
 #[cfg(not(feature = "certora"))]
 impl Role {
-    pub(crate) fn has_many_users(&self) -> bool {
-        match self {
-            Role::Admin => false,
-            Role::EmergencyAdmin => false,
-            Role::RewardsAdmin => false,
-            Role::OperationsAdmin => false,
-            Role::PauseAdmin => false,
-            Role::EmergencyPauseAdmin => true,
-        }
+    pub(crate) fn has_many_users(&self, e: &Env) -> bool {
+        self.get_config(e).has_many_users
     }
 
-    pub(crate) fn is_transfer_delayed(&self) -> bool {
-        match self {
-            Role::Admin => true,
-            Role::EmergencyAdmin => true,
-            Role::RewardsAdmin => false,
-            Role::OperationsAdmin => false,
-            Role::PauseAdmin => false,
-            Role::EmergencyPauseAdmin => false,
-        }
+    pub(crate) fn is_transfer_delayed(&self, e: &Env) -> bool {
+        self.get_config(e).is_transfer_delayed
     }
 }
 
@@ -76,22 +113,16 @@ impl SymbolRepresentation for Role {
             Role::OperationsAdmin => Symbol::new(&e, "OperationsAdmin"),
             Role::PauseAdmin => Symbol::new(&e, "PauseAdmin"),
             Role::EmergencyPauseAdmin => Symbol::new(&e, "EmergencyPauseAdmin"),
+            Role::Custom(name) => name.clone(),
         }
     }
 
     fn from_symbol(e: &Env, value: Symbol) -> Self {
-        if value == Symbol::new(e, "Admin") {
-            return Role::Admin;
-        } else if value == Symbol::new(e, "EmergencyAdmin") {
-            return Role::EmergencyAdmin;
-        } else if value == Symbol::new(e, "RewardsAdmin") {
-            return Role::RewardsAdmin;
-        } else if value == Symbol::new(e, "OperationsAdmin") {
-            return Role::OperationsAdmin;
-        } else if value == Symbol::new(e, "PauseAdmin") {
-            return Role::PauseAdmin;
-        } else if value == Symbol::new(e, "EmergencyPauseAdmin") {
-            return Role::EmergencyPauseAdmin;
+        if let Some(role) = Role::builtin_from_symbol(e, &value) {
+            return role;
+        }
+        if AccessControl::new(e).is_role_registered(&value) {
+            return Role::Custom(value);
         }
         panic_with_error!(e, AccessControlError::BadRoleUsage);
     }
@@ -1,7 +1,7 @@
 use crate::access::AccessControl;
 use crate::errors::AccessControlError;
-use crate::role::Role;
-use soroban_sdk::{contracttype, panic_with_error};
+use crate::role::{Role, SymbolRepresentation};
+use soroban_sdk::{contracttype, panic_with_error, Address, Symbol};
 
 #[derive(Clone)]
 #[contracttype]
@@ -26,6 +26,32 @@ pub enum DataKey {
 
     // emergency mode
     EmergencyMode,
+    EmergencyModeExpiry,
+    EmergencyModeScope,
+
+    // custom roles registry
+    CustomRoles,
+    RoleConfig(Symbol),
+    CustomRole(Symbol),
+
+    // transfer ownership for the rest of the roles
+    FutureRole(Symbol),
+    RoleTransferDeadline(Symbol),
+    RoleTransferDelay(Symbol),
+    FutureRoleTransferDelay(Symbol),
+    RoleTransferDelayDeadline(Symbol),
+    PendingMemberChange(Symbol), // (address, is_added) for multiple addresses roles
+
+    // threshold mode - signers set backing the role
+    RoleSigners(Symbol),
+    FutureRoleSigners(Symbol),
+    RoleSignersDeadline(Symbol),
+    RoleCosigners(Symbol, Address), // signers nominated to join the actions of the signer
+
+    // configurable delays
+    Delay(Symbol),
+    FutureDelay(Symbol),
+    DelayDeadline(Symbol),
 }
 
 // Certora: `pub(crate)` -> `pub`
@@ -55,6 +81,32 @@ pub(crate) enum DataKey {
 
     // emergency mode
     EmergencyMode,
+    EmergencyModeExpiry,
+    EmergencyModeScope,
+
+    // custom roles registry
+    CustomRoles,
+    RoleConfig(Symbol),
+    CustomRole(Symbol),
+
+    // transfer ownership for the rest of the roles
+    FutureRole(Symbol),
+    RoleTransferDeadline(Symbol),
+    RoleTransferDelay(Symbol),
+    FutureRoleTransferDelay(Symbol),
+    RoleTransferDelayDeadline(Symbol),
+    PendingMemberChange(Symbol), // (address, is_added) for multiple addresses roles
+
+    // threshold mode - signers set backing the role
+    RoleSigners(Symbol),
+    FutureRoleSigners(Symbol),
+    RoleSignersDeadline(Symbol),
+    RoleCosigners(Symbol, Address), // signers nominated to join the actions of the signer
+
+    // configurable delays
+    Delay(Symbol),
+    FutureDelay(Symbol),
+    DelayDeadline(Symbol),
 }
 
 #[cfg(not(feature = "certora"))]
@@ -73,6 +125,7 @@ impl StorageTrait for AccessControl {
             Role::OperationsAdmin => DataKey::OperationsAdmin,
             Role::PauseAdmin => DataKey::PauseAdmin,
             Role::EmergencyPauseAdmin => DataKey::EmPauseAdmins,
+            Role::Custom(name) => DataKey::CustomRole(name.clone()),
         }
     }
 
@@ -80,7 +133,10 @@ impl StorageTrait for AccessControl {
         match role {
             Role::Admin => DataKey::FutureAdmin,
             Role::EmergencyAdmin => DataKey::FutureEmergencyAdmin,
-            _ => panic_with_error!(&self.0, AccessControlError::BadRoleUsage),
+            _ if role.has_many_users(&self.0) => {
+                panic_with_error!(&self.0, AccessControlError::BadRoleUsage)
+            }
+            _ => DataKey::FutureRole(role.as_symbol(&self.0)),
         }
     }
 
@@ -88,7 +144,7 @@ impl StorageTrait for AccessControl {
         match role {
             Role::Admin => DataKey::TransferOwnershipDeadline,
             Role::EmergencyAdmin => DataKey::EmAdminTransferOwnershipDeadline,
-            _ => panic_with_error!(&self.0, AccessControlError::BadRoleUsage),
+            _ => DataKey::RoleTransferDeadline(role.as_symbol(&self.0)),
         }
     }
 }
@@ -0,0 +1,273 @@
+use crate::access::{AccessControl, AccessControlTrait};
+use crate::errors::AccessControlError;
+use crate::events::Events;
+use crate::role::{Role, SymbolRepresentation};
+use crate::storage::DataKey;
+use crate::transfer::TransferOwnershipTrait;
+use soroban_sdk::{contracttype, panic_with_error, Address, Env, Vec};
+use utils::bump::bump_instance;
+
+// Set of addresses backing the role. At least `threshold` of them should authorize the action
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SignerSet {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+// Signers set change is delayed same way as the role transfer.
+// Empty signers set switches the role back to the single address
+pub trait ThresholdManagementTrait {
+    fn get_role_signers(&self, role: &Role) -> Option<SignerSet>;
+    fn get_future_role_signers(&self, role: &Role) -> Option<SignerSet>;
+    fn get_role_signers_deadline(&self, role: &Role) -> u64;
+    fn commit_role_signers(&self, role: &Role, signers: &Vec<Address>, threshold: u32);
+    fn apply_role_signers(&self, role: &Role) -> Option<SignerSet>;
+    fn revert_role_signers(&self, role: &Role);
+
+    // signers joining the actions of the signer to reach the threshold
+    fn get_role_cosigners(&self, role: &Role, signer: &Address) -> Vec<Address>;
+    fn set_role_cosigners(&self, role: &Role, signer: &Address, cosigners: &Vec<Address>);
+
+    // require authorization of the cosigners nominated by the signer up to the threshold
+    fn require_role_signers(&self, signer: &Address, role: &Role);
+}
+
+impl ThresholdManagementTrait for AccessControl {
+    fn get_role_signers(&self, role: &Role) -> Option<SignerSet> {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .get(&DataKey::RoleSigners(role.as_symbol(&self.0)))
+    }
+
+    fn get_future_role_signers(&self, role: &Role) -> Option<SignerSet> {
+        match self.get_role_signers_deadline(role) {
+            0 => None,
+            _ => self
+                .0
+                .storage()
+                .instance()
+                .get(&DataKey::FutureRoleSigners(role.as_symbol(&self.0))),
+        }
+    }
+
+    fn get_role_signers_deadline(&self, role: &Role) -> u64 {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .get(&DataKey::RoleSignersDeadline(role.as_symbol(&self.0)))
+            .unwrap_or(0)
+    }
+
+    fn commit_role_signers(&self, role: &Role, signers: &Vec<Address>, threshold: u32) {
+        if role.has_many_users(&self.0) {
+            panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
+        }
+
+        if !signers.is_empty() && (threshold == 0 || threshold > signers.len()) {
+            panic_with_error!(&self.0, AccessControlError::InvalidSignerSet);
+        }
+        for i in 0..signers.len() {
+            if signers.first_index_of(signers.get(i).unwrap()) != Some(i) {
+                panic_with_error!(&self.0, AccessControlError::InvalidSignerSet);
+            }
+        }
+
+        if self.get_role_signers_deadline(role) != 0 {
+            panic_with_error!(&self.0, AccessControlError::AnotherActionActive);
+        }
+
+        let deadline = self.0.ledger().timestamp() + self.get_transfer_delay(role);
+        let threshold = match signers.is_empty() {
+            true => 0,
+            false => threshold,
+        };
+        bump_instance(&self.0);
+        let storage = self.0.storage().instance();
+        storage.set(
+            &DataKey::RoleSignersDeadline(role.as_symbol(&self.0)),
+            &deadline,
+        );
+        storage.set(
+            &DataKey::FutureRoleSigners(role.as_symbol(&self.0)),
+            &SignerSet {
+                signers: signers.clone(),
+                threshold,
+            },
+        );
+    }
+
+    fn apply_role_signers(&self, role: &Role) -> Option<SignerSet> {
+        let deadline = self.get_role_signers_deadline(role);
+        if self.0.ledger().timestamp() < deadline {
+            panic_with_error!(&self.0, AccessControlError::ActionNotReadyYet);
+        }
+        if deadline == 0 {
+            panic_with_error!(&self.0, AccessControlError::NoActionActive);
+        }
+
+        let future_set = match self.get_future_role_signers(role) {
+            Some(v) => v,
+            None => panic_with_error!(&self.0, AccessControlError::NoActionActive),
+        };
+
+        bump_instance(&self.0);
+        let storage = self.0.storage().instance();
+        storage.set(
+            &DataKey::RoleSignersDeadline(role.as_symbol(&self.0)),
+            &0_u64,
+        );
+
+        // nominations are made for the previous set, so they are dropped together with it
+        if let Some(signer_set) = self.get_role_signers(role) {
+            for signer in signer_set.signers.iter() {
+                storage.remove(&DataKey::RoleCosigners(role.as_symbol(&self.0), signer));
+            }
+        }
+
+        if future_set.signers.is_empty() {
+            storage.remove(&DataKey::RoleSigners(role.as_symbol(&self.0)));
+            None
+        } else {
+            storage.set(&DataKey::RoleSigners(role.as_symbol(&self.0)), &future_set);
+            Some(future_set)
+        }
+    }
+
+    fn revert_role_signers(&self, role: &Role) {
+        bump_instance(&self.0);
+        self.0.storage().instance().set(
+            &DataKey::RoleSignersDeadline(role.as_symbol(&self.0)),
+            &0_u64,
+        );
+    }
+
+    fn get_role_cosigners(&self, role: &Role, signer: &Address) -> Vec<Address> {
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .get(&DataKey::RoleCosigners(
+                role.as_symbol(&self.0),
+                signer.clone(),
+            ))
+            .unwrap_or(Vec::new(&self.0))
+    }
+
+    fn set_role_cosigners(&self, role: &Role, signer: &Address, cosigners: &Vec<Address>) {
+        let signer_set = match self.get_role_signers(role) {
+            Some(v) => v,
+            None => panic_with_error!(&self.0, AccessControlError::BadRoleUsage),
+        };
+        if !signer_set.signers.contains(signer) {
+            panic_with_error!(&self.0, AccessControlError::Unauthorized);
+        }
+
+        for i in 0..cosigners.len() {
+            let cosigner = cosigners.get(i).unwrap();
+            if &cosigner == signer
+                || !signer_set.signers.contains(&cosigner)
+                || cosigners.first_index_of(&cosigner) != Some(i)
+            {
+                panic_with_error!(&self.0, AccessControlError::InvalidSignerSet);
+            }
+        }
+
+        bump_instance(&self.0);
+        self.0.storage().instance().set(
+            &DataKey::RoleCosigners(role.as_symbol(&self.0), signer.clone()),
+            cosigners,
+        );
+    }
+
+    fn require_role_signers(&self, signer: &Address, role: &Role) {
+        let signer_set = match self.get_role_signers(role) {
+            Some(v) => v,
+            None => return,
+        };
+        if !signer_set.signers.contains(signer) {
+            panic_with_error!(&self.0, AccessControlError::Unauthorized);
+        }
+
+        // signer acts together with the nominated cosigners which are still in the set
+        let mut cosigners: Vec<Address> = Vec::new(&self.0);
+        for cosigner in self.get_role_cosigners(role, signer).iter() {
+            if cosigners.len() + 1 >= signer_set.threshold {
+                break;
+            }
+            if signer_set.signers.contains(&cosigner) {
+                cosigners.push_back(cosigner);
+            }
+        }
+        if cosigners.len() + 1 < signer_set.threshold {
+            panic_with_error!(&self.0, AccessControlError::Unauthorized);
+        }
+
+        for cosigner in cosigners.iter() {
+            cosigner.require_auth();
+        }
+    }
+}
+
+// Commit the signers set backing the admin role
+pub fn commit_admin_signers(e: &Env, admin: &Address, signers: &Vec<Address>, threshold: u32) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    access_control.commit_role_signers(&Role::Admin, signers, threshold);
+    Events::new(e).commit_role_signers(Role::Admin, signers.clone(), threshold);
+}
+
+// Apply the committed admin signers set
+pub fn apply_admin_signers(e: &Env, admin: &Address) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let (signers, threshold) = match access_control.apply_role_signers(&Role::Admin) {
+        Some(signer_set) => (signer_set.signers, signer_set.threshold),
+        None => (Vec::new(e), 0),
+    };
+    Events::new(e).apply_role_signers(Role::Admin, signers, threshold);
+}
+
+// Revert the committed admin signers set
+pub fn revert_admin_signers(e: &Env, admin: &Address) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    access_control.revert_role_signers(&Role::Admin);
+    Events::new(e).revert_role_signers(Role::Admin);
+}
+
+// Admin signers set and threshold. Empty set if not configured
+pub fn get_admin_signers(e: &Env) -> (Vec<Address>, u32) {
+    match AccessControl::new(e).get_role_signers(&Role::Admin) {
+        Some(signer_set) => (signer_set.signers, signer_set.threshold),
+        None => (Vec::new(e), 0),
+    }
+}
+
+// Committed admin signers set and threshold if the change is pending
+pub fn get_future_admin_signers(e: &Env) -> Option<(Vec<Address>, u32)> {
+    AccessControl::new(e)
+        .get_future_role_signers(&Role::Admin)
+        .map(|signer_set| (signer_set.signers, signer_set.threshold))
+}
+
+// Nominate the admin signers authorizing the admin actions of the signer together with it
+pub fn set_admin_cosigners(e: &Env, signer: &Address, cosigners: &Vec<Address>) {
+    signer.require_auth();
+    AccessControl::new(e).set_role_cosigners(&Role::Admin, signer, cosigners);
+    Events::new(e).set_role_cosigners(Role::Admin, signer.clone(), cosigners.clone());
+}
+
+// Admin signers nominated by the signer
+pub fn get_admin_cosigners(e: &Env, signer: &Address) -> Vec<Address> {
+    AccessControl::new(e).get_role_cosigners(&Role::Admin, signer)
+}
@@ -1,9 +1,11 @@
-use crate::access::AccessControl;
-use crate::constants::ADMIN_ACTIONS_DELAY;
+use crate::access::{AccessControl, AccessControlTrait};
+use crate::delays::{Delay, DelaysTrait};
 use crate::errors::AccessControlError;
-use crate::role::Role;
-use crate::storage::StorageTrait;
-use soroban_sdk::{panic_with_error, Address};
+use crate::events::Events;
+use crate::management::MultipleAddressesManagementTrait;
+use crate::role::{Role, SymbolRepresentation};
+use crate::storage::{DataKey, StorageTrait};
+use soroban_sdk::{panic_with_error, Address, Env, Symbol, Vec};
 use utils::bump::bump_instance;
 use utils::storage_errors::StorageError;
 
@@ -11,6 +13,7 @@ use utils::storage_errors::StorageError;
 use ghost_state::GhostState;
 
 pub trait TransferOwnershipTrait {
+    fn get_transfer_delay(&self, role: &Role) -> u64;
     fn get_transfer_ownership_deadline(&self, role: &Role) -> u64;
     fn put_transfer_ownership_deadline(&self, role: &Role, value: u64);
     fn get_future_address(&self, role: &Role) -> Address;
@@ -20,6 +23,11 @@ pub trait TransferOwnershipTrait {
 }
 
 impl TransferOwnershipTrait for AccessControl {
+    // delay between commit and apply. Admin and EmergencyAdmin default to the admin actions delay
+    fn get_transfer_delay(&self, role: &Role) -> u64 {
+        self.get_delay(&Delay::RoleTransfer(role.clone()))
+    }
+
     fn get_transfer_ownership_deadline(&self, role: &Role) -> u64 {
         let key = self.get_future_deadline_key(role);
         bump_instance(&self.0);
@@ -59,11 +67,11 @@ impl TransferOwnershipTrait for AccessControl {
     }
 
     fn get_future_address(&self, role: &Role) -> Address {
-        if !role.is_transfer_delayed() || role.has_many_users() {
+        if role.has_many_users(&self.0) {
             panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
         }
 
-        match self.0.storage().instance().get::<crate::storage::DataKey, Address>(&self.get_future_key(role)) {
+        match self.0.storage().instance().get::<DataKey, Address>(&self.get_future_key(role)) {
             Some(v) => { 
                 #[cfg(feature = "certora")]
                 {
@@ -84,7 +92,7 @@ impl TransferOwnershipTrait for AccessControl {
     }
 
     fn commit_transfer_ownership(&self, role: &Role, future_address: &Address) {
-        if !role.is_transfer_delayed() || role.has_many_users() {
+        if role.has_many_users(&self.0) {
             panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
         }
 
@@ -92,7 +100,7 @@ impl TransferOwnershipTrait for AccessControl {
             panic_with_error!(&self.0, AccessControlError::AnotherActionActive);
         }
 
-        let deadline = self.0.ledger().timestamp() + ADMIN_ACTIONS_DELAY;
+        let deadline = self.0.ledger().timestamp() + self.get_transfer_delay(role);
         self.put_transfer_ownership_deadline(role, deadline);
 
         bump_instance(&self.0);
@@ -160,3 +168,175 @@ impl TransferOwnershipTrait for AccessControl {
         self.put_transfer_ownership_deadline(role, 0);
     }
 }
+
+// Delayed members changes for the multiple addresses roles
+pub trait MembersChangeTrait {
+    fn get_pending_member_change(&self, role: &Role) -> Option<(Address, bool)>;
+    fn commit_member_change(&self, role: &Role, address: &Address, is_added: bool);
+    fn apply_member_change(&self, role: &Role) -> Vec<Address>;
+    fn revert_member_change(&self, role: &Role);
+}
+
+impl MembersChangeTrait for AccessControl {
+    fn get_pending_member_change(&self, role: &Role) -> Option<(Address, bool)> {
+        if !role.has_many_users(&self.0) {
+            panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
+        }
+
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .get(&DataKey::PendingMemberChange(role.as_symbol(&self.0)))
+    }
+
+    fn commit_member_change(&self, role: &Role, address: &Address, is_added: bool) {
+        if !role.has_many_users(&self.0) {
+            panic_with_error!(&self.0, AccessControlError::BadRoleUsage);
+        }
+
+        if self.get_transfer_ownership_deadline(role) != 0 {
+            panic_with_error!(&self.0, AccessControlError::AnotherActionActive);
+        }
+
+        let deadline = self.0.ledger().timestamp() + self.get_transfer_delay(role);
+        self.put_transfer_ownership_deadline(role, deadline);
+
+        bump_instance(&self.0);
+        self.0.storage().instance().set(
+            &DataKey::PendingMemberChange(role.as_symbol(&self.0)),
+            &(address.clone(), is_added),
+        );
+    }
+
+    fn apply_member_change(&self, role: &Role) -> Vec<Address> {
+        let deadline = self.get_transfer_ownership_deadline(role);
+        if self.0.ledger().timestamp() < deadline {
+            panic_with_error!(&self.0, AccessControlError::ActionNotReadyYet);
+        }
+        if deadline == 0 {
+            panic_with_error!(&self.0, AccessControlError::NoActionActive);
+        }
+
+        self.put_transfer_ownership_deadline(role, 0);
+        let (address, is_added) = match self.get_pending_member_change(role) {
+            Some(v) => v,
+            None => panic_with_error!(&self.0, StorageError::ValueNotInitialized),
+        };
+
+        let mut addresses = self.get_role_addresses(role);
+        match (is_added, addresses.first_index_of(&address)) {
+            (true, None) => addresses.push_back(address),
+            (false, Some(idx)) => {
+                addresses.remove(idx);
+            }
+            _ => panic_with_error!(&self.0, AccessControlError::BadRoleUsage),
+        }
+
+        // members list has been updated through the delay, write it directly
+        bump_instance(&self.0);
+        self.0
+            .storage()
+            .instance()
+            .set(&self.get_key(role), &addresses);
+
+        addresses
+    }
+
+    fn revert_member_change(&self, role: &Role) {
+        self.put_transfer_ownership_deadline(role, 0);
+    }
+}
+
+// Commit the transfer delay of the role on behalf of the admin
+pub fn commit_role_transfer_delay(e: &Env, admin: &Address, role_name: Symbol, delay: u64) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let role = Role::from_symbol(e, role_name);
+    access_control.commit_delay(&Delay::RoleTransfer(role.clone()), delay);
+    Events::new(e).commit_transfer_delay(role, delay);
+}
+
+// Apply the committed transfer delay of the role
+pub fn apply_role_transfer_delay(e: &Env, admin: &Address, role_name: Symbol) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let role = Role::from_symbol(e, role_name);
+    let delay = access_control.apply_delay(&Delay::RoleTransfer(role.clone()));
+    Events::new(e).apply_transfer_delay(role, delay);
+}
+
+// Revert the committed transfer delay of the role
+pub fn revert_role_transfer_delay(e: &Env, admin: &Address, role_name: Symbol) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let role = Role::from_symbol(e, role_name);
+    access_control.revert_delay(&Delay::RoleTransfer(role.clone()));
+    Events::new(e).revert_transfer_delay(role);
+}
+
+pub fn get_role_transfer_delay(e: &Env, role_name: Symbol) -> u64 {
+    AccessControl::new(e).get_transfer_delay(&Role::from_symbol(e, role_name))
+}
+
+// Committed transfer delay of the role, current value if the change is not committed
+pub fn get_future_role_transfer_delay(e: &Env, role_name: Symbol) -> u64 {
+    let role = Role::from_symbol(e, role_name);
+    AccessControl::new(e).get_future_delay(&Delay::RoleTransfer(role))
+}
+
+// Commit the members change of the multiple addresses role on behalf of the admin
+pub fn commit_role_member_change(
+    e: &Env,
+    admin: &Address,
+    role_name: Symbol,
+    address: &Address,
+    is_added: bool,
+) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let role = Role::from_symbol(e, role_name);
+    access_control.commit_member_change(&role, address, is_added);
+    Events::new(e).commit_member_change(role, address.clone(), is_added);
+}
+
+// Apply the committed members change. Returns the new members list
+pub fn apply_role_members_change(e: &Env, admin: &Address, role_name: Symbol) -> Vec<Address> {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let role = Role::from_symbol(e, role_name);
+    let addresses = access_control.apply_member_change(&role);
+    Events::new(e).apply_member_change(role, addresses.clone());
+    addresses
+}
+
+// Revert the committed members change
+pub fn revert_role_members_change(e: &Env, admin: &Address, role_name: Symbol) {
+    admin.require_auth();
+    let access_control = AccessControl::new(e);
+    access_control.assert_address_has_role(admin, &Role::Admin);
+
+    let role = Role::from_symbol(e, role_name);
+    access_control.revert_member_change(&role);
+    Events::new(e).revert_member_change(role);
+}
+
+// Committed members change as (address, is_added) pair
+pub fn get_pending_role_members_change(e: &Env, role_name: Symbol) -> Option<(Address, bool)> {
+    let access_control = AccessControl::new(e);
+    let role = Role::from_symbol(e, role_name);
+    match access_control.get_transfer_ownership_deadline(&role) {
+        0 => None,
+        _ => access_control.get_pending_member_change(&role),
+    }
+}
@@ -1,19 +1,29 @@
 use crate::access::{AccessControl, AccessControlTrait};
 use crate::errors::AccessControlError;
 use crate::role::Role;
+use crate::threshold::ThresholdManagementTrait;
 use soroban_sdk::{panic_with_error, Address, Env};
 
+// Admin signer acts together with the nominated cosigners
+fn is_owner(access_control: &AccessControl, address: &Address) -> bool {
+    if !access_control.address_has_role(address, &Role::Admin) {
+        return false;
+    }
+    access_control.require_role_signers(address, &Role::Admin);
+    true
+}
+
 pub fn require_rewards_admin_or_owner(e: &Env, address: &Address) {
     let access_control = AccessControl::new(e);
-    let _ = access_control.address_has_role(address, &Role::Admin)
-        || access_control.address_has_role(address, &Role::RewardsAdmin)
+    let _ = access_control.address_has_role(address, &Role::RewardsAdmin)
+        || is_owner(&access_control, address)
         || panic_with_error!(e, AccessControlError::Unauthorized);
 }
 
 pub fn require_operations_admin_or_owner(e: &Env, address: &Address) {
     let access_control: AccessControl = AccessControl::new(e);
     let _ = access_control.address_has_role(address, &Role::OperationsAdmin)
-        || access_control.address_has_role(address, &Role::Admin)
+        || is_owner(&access_control, address)
         || panic_with_error!(e, AccessControlError::Unauthorized);
 }
 
@@ -21,13 +31,13 @@ pub fn require_pause_or_emergency_pause_admin_or_owner(e: &Env, address: &Addres
     let access_control = AccessControl::new(e);
     let _ = access_control.address_has_role(address, &Role::PauseAdmin)
         || access_control.address_has_role(address, &Role::EmergencyPauseAdmin)
-        || access_control.address_has_role(address, &Role::Admin)
+        || is_owner(&access_control, address)
         || panic_with_error!(e, AccessControlError::Unauthorized);
 }
 
 pub fn require_pause_admin_or_owner(e: &Env, address: &Address) {
     let access_control = AccessControl::new(e);
     let _ = access_control.address_has_role(address, &Role::PauseAdmin)
-        || access_control.address_has_role(address, &Role::Admin)
+        || is_owner(&access_control, address)
         || panic_with_error!(e, AccessControlError::Unauthorized);
 }
@@ -0,0 +1,127 @@
+use crate::access::AccessControl;
+use crate::delays::{Delay, DelaysTrait};
+use crate::management::{MultipleAddressesManagementTrait, SingleAddressManagementTrait};
+use crate::registry::RoleRegistryTrait;
+use crate::role::{Role, SymbolRepresentation};
+use crate::threshold::{SignerSet, ThresholdManagementTrait};
+use crate::transfer::{MembersChangeTrait, TransferOwnershipTrait};
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+// Current delay value along with the pending change
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DelayState {
+    pub name: Symbol,
+    pub value: u64,
+    pub future_value: Option<u64>,
+    pub deadline: u64,
+}
+
+// Role holders along with the pending transfer, members change and signers set change
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RoleState {
+    pub name: Symbol,
+    pub addresses: Vec<Address>,
+    pub future_address: Option<Address>,
+    pub pending_member_change: Option<(Address, bool)>,
+    pub deadline: u64,
+    pub transfer_delay: DelayState,
+    pub signers: Option<SignerSet>,
+    pub future_signers: Option<SignerSet>,
+    pub signers_deadline: u64,
+}
+
+// Roles and configurable delays of the contract
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AccessControlState {
+    pub roles: Vec<RoleState>,
+    pub delays: Vec<DelayState>,
+}
+
+pub trait RolesViewTrait {
+    fn get_delay_state(&self, delay: &Delay) -> DelayState;
+    fn get_delays_state(&self) -> Vec<DelayState>;
+    fn get_role_state(&self, role: &Role) -> RoleState;
+    fn get_roles_state(&self) -> Vec<RoleState>;
+}
+
+impl RolesViewTrait for AccessControl {
+    fn get_delay_state(&self, delay: &Delay) -> DelayState {
+        let deadline = self.get_delay_deadline(delay);
+        DelayState {
+            name: delay.as_symbol(&self.0),
+            value: self.get_delay(delay),
+            future_value: match deadline {
+                0 => None,
+                _ => Some(self.get_future_delay(delay)),
+            },
+            deadline,
+        }
+    }
+
+    fn get_delays_state(&self) -> Vec<DelayState> {
+        let mut result = Vec::new(&self.0);
+        for delay in [Delay::AdminActions, Delay::Upgrade, Delay::WasmAllowlist] {
+            result.push_back(self.get_delay_state(&delay));
+        }
+        result
+    }
+
+    fn get_role_state(&self, role: &Role) -> RoleState {
+        let deadline = self.get_transfer_ownership_deadline(role);
+        let mut state = RoleState {
+            name: role.as_symbol(&self.0),
+            addresses: Vec::new(&self.0),
+            future_address: None,
+            pending_member_change: None,
+            deadline,
+            transfer_delay: self.get_delay_state(&Delay::RoleTransfer(role.clone())),
+            signers: self.get_role_signers(role),
+            future_signers: self.get_future_role_signers(role),
+            signers_deadline: self.get_role_signers_deadline(role),
+        };
+
+        if role.has_many_users(&self.0) {
+            state.addresses = self.get_role_addresses(role);
+            if deadline != 0 {
+                state.pending_member_change = self.get_pending_member_change(role);
+            }
+        } else {
+            if let Some(address) = self.get_role_safe(role) {
+                state.addresses.push_back(address);
+            }
+            if deadline != 0 {
+                state.future_address = Some(self.get_future_address(role));
+            }
+        }
+        state
+    }
+
+    fn get_roles_state(&self) -> Vec<RoleState> {
+        let mut result = Vec::new(&self.0);
+        for role in [
+            Role::Admin,
+            Role::EmergencyAdmin,
+            Role::RewardsAdmin,
+            Role::OperationsAdmin,
+            Role::PauseAdmin,
+            Role::EmergencyPauseAdmin,
+        ] {
+            result.push_back(self.get_role_state(&role));
+        }
+        for name in self.get_custom_roles() {
+            result.push_back(self.get_role_state(&Role::Custom(name)));
+        }
+        result
+    }
+}
+
+pub fn get_access_control_state(e: &Env) -> AccessControlState {
+    let access_control = AccessControl::new(e);
+    AccessControlState {
+        roles: access_control.get_roles_state(),
+        delays: access_control.get_delays_state(),
+    }
+}
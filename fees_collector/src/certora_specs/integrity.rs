@@ -2,7 +2,8 @@ use cvlr::{cvlr_satisfy, cvlr_assume};
 use soroban_sdk::{Env, Address, BytesN, Symbol, Vec};
 use cvlr::asserts::cvlr_assert;
 use cvlr_soroban_derive::rule;
-use access_control::constants::ADMIN_ACTIONS_DELAY;
+use access_control::access::AccessControl;
+use access_control::delays::{Delay, DelaysTrait};
 
 use crate::contract::FeesCollector;
 use crate::interface::AdminInterface;
@@ -46,7 +47,7 @@ pub fn integrity_future_address(e: Env, admin: Address, role_name: Symbol, new_a
 
 #[rule]
 pub fn integrity_commit_transfer_deadline(e: Env, admin: Address, role_name: Symbol, new_address: Address) {
-    let deadline = e.ledger().timestamp() + ADMIN_ACTIONS_DELAY;
+    let deadline = e.ledger().timestamp() + AccessControl::new(&e).get_delay(&Delay::AdminActions);
     FeesCollector::commit_transfer_ownership(e.clone(), admin, role_name.clone(), new_address.clone());
     let result = FeesCollector::h_get_transfer_ownership_dl(e, role_name);
     cvlr_assert!(result == deadline);
@@ -76,7 +77,7 @@ pub fn integrity_commit_upgrade_future_wasm(e: Env, admin: Address, new_wasm_has
 
 #[rule]
 pub fn integrity_commit_upgrade_deadline(e: Env, admin: Address, new_wasm_hash: BytesN<32>) {
-    let expected_deadline = e.ledger().timestamp() + ADMIN_ACTIONS_DELAY;
+    let expected_deadline = e.ledger().timestamp() + AccessControl::new(&e).get_delay(&Delay::Upgrade);
     FeesCollector::commit_upgrade(e.clone(), admin, new_wasm_hash);
     let deadline = FeesCollector::h_get_upgrade_deadline(e);
     cvlr_assert!(deadline == expected_deadline);
@@ -4,7 +4,8 @@ use soroban_sdk::Env;
 use cvlr::cvlr_assert;
 use crate::certora_specs::base::ParametricParams;
 use ghost_state::GhostState;
-use access_control::constants::ADMIN_ACTIONS_DELAY;
+use access_control::access::AccessControl;
+use access_control::delays::{Delay, DelaysTrait};
 
 fn check_upgrade_deadline_lifecycle(
     e: &Env,
@@ -43,7 +44,8 @@ fn check_admin_deadline_lifecycle(
 ) -> bool {
     if deadline_before == 0 && deadline_after != 0 {
         // Transition from 0 to non-zero (commit)
-        deadline_after == e.ledger().timestamp() + ADMIN_ACTIONS_DELAY && future_after.is_some()
+        deadline_after == e.ledger().timestamp() + AccessControl::new(e).get_delay(&Delay::AdminActions)
+            && future_after.is_some()
     // } else if deadline_before != 0 && deadline_after == 0 {
         // Transition from non-zero to 0 (apply or revert)
         // @note This executable path is violated due 
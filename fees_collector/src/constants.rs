@@ -0,0 +1,2 @@
+pub(crate) const FEE_SPLIT_DENOMINATOR: u32 = 10_000; // weights are set in basis points
+pub(crate) const MAX_FEE_SPLIT_RECIPIENTS: u32 = 10;
@@ -1,21 +1,100 @@
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, Symbol, Vec};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::token::Client as SorobanTokenClient;
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, vec, Address, BytesN, Env, IntoVal, Map, Symbol,
+    Vec,
+};
 
-use crate::interface::AdminInterface;
+use crate::constants::{FEE_SPLIT_DENOMINATOR, MAX_FEE_SPLIT_RECIPIENTS};
+use crate::errors::FeesCollectorError;
+use crate::events::Events;
+use crate::interface::{
+    AdminInterface, ConversionInterface, FeeSplitInterface, FeesCollectorInterface,
+};
+use crate::storage::{
+    get_fee_split, get_fee_split_deadline, get_future_fee_split, get_router, get_token_balance,
+    set_fee_split, set_fee_split_deadline, set_future_fee_split, set_router, set_token_balance,
+};
 use access_control::access::{AccessControl, AccessControlTrait};
-use access_control::emergency::{get_emergency_mode, set_emergency_mode};
+use access_control::delays::{
+    apply_delay, commit_delay, get_delay, get_future_delay, revert_delay, Delay, DelaysTrait,
+};
+use access_control::emergency::{
+    enable_scoped_emergency_mode, get_emergency_mode, get_emergency_mode_state, set_emergency_mode,
+    EmergencyModeState, EmergencyScope,
+};
 use access_control::errors::AccessControlError;
 use access_control::events::Events as AccessControlEvents;
-use access_control::interface::TransferableContract;
+use access_control::interface::{
+    AdminSignersContract, ConfigurableDelaysContract, EmergencyModeContract, RoleTimelockContract,
+    TransferableContract,
+};
 use access_control::management::SingleAddressManagementTrait;
 use access_control::role::{Role, SymbolRepresentation};
-use access_control::transfer::TransferOwnershipTrait;
+use access_control::threshold::{
+    apply_admin_signers, commit_admin_signers, get_admin_cosigners, get_admin_signers,
+    get_future_admin_signers, revert_admin_signers, set_admin_cosigners,
+};
+use access_control::transfer::{
+    apply_role_members_change, apply_role_transfer_delay, commit_role_member_change,
+    commit_role_transfer_delay, get_future_role_transfer_delay, get_pending_role_members_change,
+    get_role_transfer_delay, revert_role_members_change, revert_role_transfer_delay,
+    TransferOwnershipTrait,
+};
+use access_control::utils::require_operations_admin_or_owner;
+use upgrade::allowlist::{
+    apply_wasm_allowlist_change, commit_wasm_allowlist_change, get_allowed_wasms,
+    get_pending_allowlist_changes, is_wasm_allowed, revert_wasm_allowlist_change,
+    WasmAllowlistChange, WasmKind,
+};
 use upgrade::events::Events as UpgradeEvents;
-use upgrade::interface::UpgradeableContract;
-use upgrade::{apply_upgrade, commit_upgrade, revert_upgrade};
+use upgrade::history::UpgradeRecord;
+use upgrade::interface::{AdminStateContract, UpgradeableContract, WasmAllowlistContract};
+use upgrade::view::{get_admin_state, AdminState};
+use upgrade::{
+    apply_upgrade, commit_upgrade, get_storage_version, get_upgrade_history, migrate,
+    require_migrated, revert_upgrade, rollback_upgrade, set_storage_version,
+};
+use utils::storage_errors::StorageError;
 
 #[contract]
 pub struct FeesCollector;
 
+// Accounts tokens transferred to the collector directly. Returns newly accounted amount.
+fn sync_token_balance(e: &Env, token: &Address) -> u128 {
+    let balance =
+        SorobanTokenClient::new(e, token).balance(&e.current_contract_address()) as u128;
+    let accounted = get_token_balance(e, token);
+    if balance <= accounted {
+        return 0;
+    }
+
+    let amount = balance - accounted;
+    set_token_balance(e, token, &balance);
+    Events::new(e).receive_fee(token.clone(), amount);
+    amount
+}
+
+fn validate_fee_split(e: &Env, split: &Vec<(Address, u32)>) {
+    if split.len() == 0 || split.len() > MAX_FEE_SPLIT_RECIPIENTS {
+        panic_with_error!(e, FeesCollectorError::FeeSplitInvalid);
+    }
+
+    let mut recipients: Vec<Address> = Vec::new(e);
+    let mut total_weight = 0;
+    for (recipient, weight) in split.iter() {
+        if recipients.contains(&recipient) {
+            panic_with_error!(e, FeesCollectorError::DuplicatesNotAllowed);
+        }
+        recipients.push_back(recipient);
+        total_weight += weight as u64;
+    }
+
+    if total_weight != FEE_SPLIT_DENOMINATOR as u64 {
+        panic_with_error!(e, FeesCollectorError::FeeSplitInvalid);
+    }
+}
+
 #[contractimpl]
 impl AdminInterface for FeesCollector {
     // Initializes the admin user.
@@ -29,6 +108,391 @@ impl AdminInterface for FeesCollector {
             panic_with_error!(&e, AccessControlError::AdminAlreadySet);
         }
         access_control.set_role_address(&Role::Admin, &account);
+        set_storage_version(&e, Self::version());
+    }
+
+    // Sets the privileged addresses.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `operations_admin` - The address of the operations admin.
+    fn set_privileged_addrs(e: Env, admin: Address, operations_admin: Address) {
+        admin.require_auth();
+        let access_control = AccessControl::new(&e);
+        access_control.assert_address_has_role(&admin, &Role::Admin);
+
+        access_control.set_role_address(&Role::OperationsAdmin, &operations_admin);
+        // collector has no other privileged roles to be set
+        Events::new(&e).set_privileged_addrs(operations_admin);
+    }
+
+    // Returns a map of privileged roles.
+    //
+    // # Returns
+    //
+    // A map of privileged roles to their respective addresses.
+    fn get_privileged_addrs(e: Env) -> Map<Symbol, Vec<Address>> {
+        let access_control = AccessControl::new(&e);
+        let mut result: Map<Symbol, Vec<Address>> = Map::new(&e);
+        for role in [Role::Admin, Role::EmergencyAdmin, Role::OperationsAdmin] {
+            result.set(
+                role.as_symbol(&e),
+                match access_control.get_role_safe(&role) {
+                    Some(v) => Vec::from_array(&e, [v]),
+                    None => Vec::new(&e),
+                },
+            );
+        }
+
+        result
+    }
+}
+
+#[contractimpl]
+impl FeesCollectorInterface for FeesCollector {
+    // Transfers fees from the user to the collector and adds them to the accounted balance.
+    //
+    // # Arguments
+    //
+    // * `user` - The address sending the fees.
+    // * `token` - The address of the token.
+    // * `amount` - The amount of tokens to transfer.
+    fn deposit(e: Env, user: Address, token: Address, amount: u128) {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+        if amount == 0 {
+            panic_with_error!(&e, FeesCollectorError::ZeroAmount);
+        }
+
+        SorobanTokenClient::new(&e, &token).transfer(
+            &user,
+            &e.current_contract_address(),
+            &(amount as i128),
+        );
+        set_token_balance(&e, &token, &(get_token_balance(&e, &token) + amount));
+        Events::new(&e).receive_fee(token, amount);
+    }
+
+    // Accounts tokens which were transferred to the collector directly,
+    // e.g. fees sent by the pools or the provider fee collectors.
+    //
+    // # Arguments
+    //
+    // * `token` - The address of the token.
+    //
+    // # Returns
+    //
+    // The amount of tokens added to the accounted balance.
+    fn sync(e: Env, token: Address) -> u128 {
+        require_migrated(&e, Self::version());
+        sync_token_balance(&e, &token)
+    }
+
+    // Withdraws accounted tokens from the collector.
+    // Can be called by the admin or the operations admin.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin or operations admin.
+    // * `token` - The address of the token.
+    // * `to` - The destination address.
+    // * `amount` - The amount of tokens to withdraw.
+    fn withdraw(e: Env, admin: Address, token: Address, to: Address, amount: u128) {
+        require_migrated(&e, Self::version());
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        if amount == 0 {
+            panic_with_error!(&e, FeesCollectorError::ZeroAmount);
+        }
+        let balance = get_token_balance(&e, &token);
+        if amount > balance {
+            panic_with_error!(&e, FeesCollectorError::InsufficientBalance);
+        }
+
+        set_token_balance(&e, &token, &(balance - amount));
+        SorobanTokenClient::new(&e, &token).transfer(
+            &e.current_contract_address(),
+            &to,
+            &(amount as i128),
+        );
+        Events::new(&e).withdraw_fee(token, to, amount);
+    }
+
+    // Returns the accounted balances of the tokens.
+    //
+    // # Arguments
+    //
+    // * `tokens` - A vector of token addresses.
+    //
+    // # Returns
+    //
+    // A vector of balances in the same order as the tokens.
+    fn get_balances(e: Env, tokens: Vec<Address>) -> Vec<u128> {
+        let mut result = Vec::new(&e);
+        for token in tokens {
+            result.push_back(get_token_balance(&e, &token));
+        }
+        result
+    }
+}
+
+#[contractimpl]
+impl FeeSplitInterface for FeesCollector {
+    // Commits a new fees split.
+    // The split will be available through `apply_fee_split` after the standard admin actions delay
+    // unless it's being configured for the first time.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `split` - A vector of (recipient, weight) pairs. Weights are set in basis points
+    //     and should sum up to 100% (10000).
+    fn commit_fee_split(e: Env, admin: Address, split: Vec<(Address, u32)>) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        if get_fee_split_deadline(&e) != 0 {
+            panic_with_error!(&e, FeesCollectorError::AnotherActionActive);
+        }
+        validate_fee_split(&e, &split);
+
+        let deadline =
+            e.ledger().timestamp() + AccessControl::new(&e).get_delay(&Delay::AdminActions);
+        set_fee_split_deadline(&e, &deadline);
+        set_future_fee_split(&e, &split);
+        Events::new(&e).commit_fee_split(split);
+    }
+
+    // Applies the committed fees split.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn apply_fee_split(e: Env, admin: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        let deadline = get_fee_split_deadline(&e);
+        if get_fee_split(&e).is_some() && e.ledger().timestamp() < deadline {
+            panic_with_error!(&e, FeesCollectorError::ActionNotReadyYet);
+        }
+        if deadline == 0 {
+            panic_with_error!(&e, FeesCollectorError::NoActionActive);
+        }
+
+        set_fee_split_deadline(&e, &0);
+        let split = match get_future_fee_split(&e) {
+            Some(v) => v,
+            None => panic_with_error!(&e, StorageError::ValueNotInitialized),
+        };
+        set_fee_split(&e, &split);
+        Events::new(&e).apply_fee_split(split);
+    }
+
+    // Reverts the committed fees split.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn revert_fee_split(e: Env, admin: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        set_fee_split_deadline(&e, &0);
+        Events::new(&e).revert_fee_split();
+    }
+
+    // Returns the current fees split.
+    //
+    // # Returns
+    //
+    // A vector of (recipient, weight) pairs.
+    fn get_fee_split(e: Env) -> Vec<(Address, u32)> {
+        match get_fee_split(&e) {
+            Some(v) => v,
+            None => panic_with_error!(&e, FeesCollectorError::FeeSplitNotConfigured),
+        }
+    }
+
+    // Returns the fees split to be applied in future.
+    // The split will be defaulted to the current one if nothing is committed.
+    //
+    // # Returns
+    //
+    // A vector of (recipient, weight) pairs.
+    fn get_future_fee_split(e: Env) -> Vec<(Address, u32)> {
+        match get_fee_split_deadline(&e) {
+            0 => Self::get_fee_split(e),
+            _ => match get_future_fee_split(&e) {
+                Some(v) => v,
+                None => panic_with_error!(&e, StorageError::ValueNotInitialized),
+            },
+        }
+    }
+
+    // Returns the deadline of the committed fees split. Zero if nothing is committed.
+    fn get_fee_split_deadline(e: Env) -> u64 {
+        get_fee_split_deadline(&e)
+    }
+
+    // Distributes the accounted token balance between the recipients according to the split.
+    // Tokens sent to the collector directly are accounted before the distribution.
+    // Rounding dust is retained by the collector.
+    //
+    // # Arguments
+    //
+    // * `token` - The address of the token.
+    //
+    // # Returns
+    //
+    // The total amount of tokens distributed.
+    fn distribute(e: Env, token: Address) -> u128 {
+        require_migrated(&e, Self::version());
+        let split = Self::get_fee_split(e.clone());
+
+        sync_token_balance(&e, &token);
+        let balance = get_token_balance(&e, &token);
+
+        let token_client = SorobanTokenClient::new(&e, &token);
+        let mut distributed = 0;
+        for (recipient, weight) in split.iter() {
+            let amount = balance * weight as u128 / FEE_SPLIT_DENOMINATOR as u128;
+            if amount == 0 {
+                continue;
+            }
+
+            token_client.transfer(&e.current_contract_address(), &recipient, &(amount as i128));
+            Events::new(&e).distribute_fee(token.clone(), recipient, amount);
+            distributed += amount;
+        }
+
+        set_token_balance(&e, &token, &(balance - distributed));
+        distributed
+    }
+}
+
+#[contractimpl]
+impl ConversionInterface for FeesCollector {
+    // Sets the router used to convert fees.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `router` - The address of the router.
+    fn set_router(e: Env, admin: Address, router: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        set_router(&e, &router);
+        Events::new(&e).set_router(router);
+    }
+
+    // Returns the router used to convert fees.
+    fn get_router(e: Env) -> Address {
+        get_router(&e)
+    }
+
+    // Converts accounted fees into another token using the router.
+    // The output tokens are kept by the collector and added to the accounted balance.
+    //
+    // # Arguments
+    //
+    // * `operator` - The address of the operations admin.
+    // * `swaps_chain` - A vector describing the swap path; each element is a tuple of (pool tokens, pool index, output token address).
+    // * `token_in` - The address of the token to convert.
+    // * `amount` - The amount of the token to convert.
+    // * `out_min` - The minimum acceptable output amount from the swap.
+    //
+    // # Returns
+    //
+    // The amount of the output token received.
+    fn convert(
+        e: Env,
+        operator: Address,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        amount: u128,
+        out_min: u128,
+    ) -> u128 {
+        require_migrated(&e, Self::version());
+        operator.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&operator, &Role::OperationsAdmin);
+
+        let (_, _, token_out) = match swaps_chain.last() {
+            Some(v) => v,
+            None => panic_with_error!(&e, FeesCollectorError::PathIsEmpty),
+        };
+        if amount == 0 {
+            panic_with_error!(&e, FeesCollectorError::ZeroAmount);
+        }
+        let balance_in = get_token_balance(&e, &token_in);
+        if amount > balance_in {
+            panic_with_error!(&e, FeesCollectorError::InsufficientBalance);
+        }
+        set_token_balance(&e, &token_in, &(balance_in - amount));
+
+        let router = get_router(&e);
+        e.authorize_as_current_contract(vec![
+            &e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token_in.clone(),
+                    fn_name: Symbol::new(&e, "transfer"),
+                    args: (e.current_contract_address(), router.clone(), amount as i128)
+                        .into_val(&e),
+                },
+                sub_invocations: vec![&e],
+            }),
+        ]);
+        let out_amount: u128 = e.invoke_contract(
+            &router,
+            &Symbol::new(&e, "swap_chained"),
+            Vec::from_array(
+                &e,
+                [
+                    e.current_contract_address().to_val(),
+                    swaps_chain.to_val(),
+                    token_in.clone().to_val(),
+                    amount.into_val(&e),
+                    out_min.into_val(&e),
+                ],
+            ),
+        );
+
+        set_token_balance(
+            &e,
+            &token_out,
+            &(get_token_balance(&e, &token_out) + out_amount),
+        );
+        Events::new(&e).convert_fee(token_in, token_out, amount, out_amount);
+        out_amount
+    }
+
+    // Burns accounted tokens, e.g. after the fees were converted to the target token.
+    //
+    // # Arguments
+    //
+    // * `operator` - The address of the operations admin.
+    // * `token` - The address of the token to burn.
+    // * `amount` - The amount of the token to burn.
+    fn burn(e: Env, operator: Address, token: Address, amount: u128) {
+        require_migrated(&e, Self::version());
+        operator.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&operator, &Role::OperationsAdmin);
+
+        if amount == 0 {
+            panic_with_error!(&e, FeesCollectorError::ZeroAmount);
+        }
+        let balance = get_token_balance(&e, &token);
+        if amount > balance {
+            panic_with_error!(&e, FeesCollectorError::InsufficientBalance);
+        }
+
+        set_token_balance(&e, &token, &(balance - amount));
+        SorobanTokenClient::new(&e, &token).burn(&e.current_contract_address(), &(amount as i128));
+        Events::new(&e).burn_fee(token, amount);
     }
 }
 
@@ -55,7 +519,7 @@ impl UpgradeableContract for FeesCollector {
     fn commit_upgrade(e: Env, admin: Address, new_wasm_hash: BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        commit_upgrade(&e, &new_wasm_hash);
+        commit_upgrade(&e, &WasmKind::FeesCollector, &new_wasm_hash);
         UpgradeEvents::new(&e).commit_upgrade(Vec::from_array(&e, [new_wasm_hash.clone()]));
     }
 
@@ -67,7 +531,7 @@ impl UpgradeableContract for FeesCollector {
     fn apply_upgrade(e: Env, admin: Address) -> BytesN<32> {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        let new_wasm_hash = apply_upgrade(&e);
+        let new_wasm_hash = apply_upgrade(&e, &admin);
         UpgradeEvents::new(&e).apply_upgrade(Vec::from_array(&e, [new_wasm_hash.clone()]));
         new_wasm_hash
     }
@@ -87,8 +551,55 @@ impl UpgradeableContract for FeesCollector {
         UpgradeEvents::new(&e).revert_upgrade();
     }
 
+    // Rolls back the contract to the previously running wasm.
+    // The rollback is applied immediately as it's intended to fix the faulty release.
+    // The previous wasm is known only for the upgrades applied through `apply_upgrade`.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    //
+    // # Returns
+    //
+    // The wasm hash the contract has been rolled back to.
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32> {
+        emergency_admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
+        let wasm_hash = rollback_upgrade(&e, &emergency_admin);
+        UpgradeEvents::new(&e).rollback_upgrade(wasm_hash.clone());
+        wasm_hash
+    }
+
+    // Returns the latest applied upgrades and rollbacks.
+    //
+    // # Returns
+    //
+    // The list of records with wasm hash, timestamp and the admin applied it.
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord> {
+        get_upgrade_history(&e)
+    }
+
+    // Migrates the storage to the current contract version.
+    // Should be called once after every upgrade, state-changing methods are not available until then.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn migrate(e: Env, admin: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        let from_version = migrate(&e, Self::version());
+        UpgradeEvents::new(&e).migrate(from_version, Self::version());
+    }
+
+    // Returns the version of the storage schema.
+    fn get_storage_version(e: Env) -> u32 {
+        get_storage_version(&e)
+    }
+
     // Sets the emergency mode.
     // When the emergency mode is set to true, the contract will allow instant upgrades without the delay.
+    // Emergency mode enabled this way unlocks all emergency powers and expires after 7 days.
     // This is useful in case of critical issues that need to be fixed immediately.
     // When the emergency mode is set to false, the contract will require the standard upgrade delay.
     // The emergency mode can only be set by the emergency admin.
@@ -107,7 +618,9 @@ impl UpgradeableContract for FeesCollector {
         let access_control = AccessControl::new(&e);
         access_control.assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
         set_emergency_mode(&e, &value);
-        AccessControlEvents::new(&e).set_emergency_mode(value);
+        let events = AccessControlEvents::new(&e);
+        events.set_emergency_mode_state(get_emergency_mode_state(&e));
+        events.set_emergency_mode(value);
     }
 
     // Returns the emergency mode flag value.
@@ -196,3 +709,315 @@ impl TransferableContract for FeesCollector {
         }
     }
 }
+
+// The `ConfigurableDelaysContract` trait provides the interface for configuring admin actions delays.
+#[contractimpl]
+impl ConfigurableDelaysContract for FeesCollector {
+    // Commits new value for the delay: `AdminActions`, `Upgrade` or `WasmAllowlist`.
+    // The value is applied after the current delay passes.
+    fn commit_delay(e: Env, admin: Address, delay_name: Symbol, value: u64) {
+        commit_delay(&e, &admin, delay_name, value)
+    }
+
+    // Applies the committed delay value.
+    fn apply_delay(e: Env, admin: Address, delay_name: Symbol) {
+        apply_delay(&e, &admin, delay_name)
+    }
+
+    // Reverts the committed delay value.
+    fn revert_delay(e: Env, admin: Address, delay_name: Symbol) {
+        revert_delay(&e, &admin, delay_name)
+    }
+
+    // Returns the current delay value in seconds.
+    fn get_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_delay(&e, delay_name)
+    }
+
+    // Returns the committed delay value in seconds. Defaults to the current one if not committed.
+    fn get_future_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_future_delay(&e, delay_name)
+    }
+}
+
+// The `AdminSignersContract` trait provides M-of-N authorization for the admin role.
+#[contractimpl]
+impl AdminSignersContract for FeesCollector {
+    // Commits the signers set backing the admin role. Empty set switches back to the single admin.
+    fn commit_admin_signers(e: Env, admin: Address, signers: Vec<Address>, threshold: u32) {
+        commit_admin_signers(&e, &admin, &signers, threshold)
+    }
+
+    // Applies the committed admin signers set once the admin transfer delay passes.
+    fn apply_admin_signers(e: Env, admin: Address) {
+        apply_admin_signers(&e, &admin)
+    }
+
+    // Reverts the committed admin signers set.
+    fn revert_admin_signers(e: Env, admin: Address) {
+        revert_admin_signers(&e, &admin)
+    }
+
+    // Returns the admin signers set and threshold. Empty set if not configured.
+    fn get_admin_signers(e: Env) -> (Vec<Address>, u32) {
+        get_admin_signers(&e)
+    }
+
+    // Returns the committed admin signers set and threshold if the change is pending.
+    fn get_future_admin_signers(e: Env) -> Option<(Vec<Address>, u32)> {
+        get_future_admin_signers(&e)
+    }
+
+    // Nominates the admin signers authorizing the admin actions of the signer together with it.
+    fn set_admin_cosigners(e: Env, signer: Address, cosigners: Vec<Address>) {
+        set_admin_cosigners(&e, &signer, &cosigners)
+    }
+
+    // Returns the admin signers nominated by the signer.
+    fn get_admin_cosigners(e: Env, signer: Address) -> Vec<Address> {
+        get_admin_cosigners(&e, &signer)
+    }
+}
+
+// The `RoleTimelockContract` trait provides delayed changes for the rest of the roles.
+#[contractimpl]
+impl RoleTimelockContract for FeesCollector {
+    // Commits delay between commit and apply of the role transfer or members change.
+    // The value is applied after the current delay of the role passes.
+    // Once delay is set, role can be changed only through the commit-apply process.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    // * `delay` - Delay in seconds. Cannot be less than the minimum delay for the roles
+    //   transferred with mandatory delay like `Admin` and `EmergencyAdmin`.
+    fn commit_role_transfer_delay(e: Env, admin: Address, role_name: Symbol, delay: u64) {
+        commit_role_transfer_delay(&e, &admin, role_name, delay);
+    }
+
+    // Applies the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn apply_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        apply_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Reverts the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn revert_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Returns delay between commit and apply of the role transfer or members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_role_transfer_delay(&e, role_name)
+    }
+
+    // Returns the committed role transfer delay.
+    // The value will be defaulted to the current one if the change is not committed.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_future_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_future_role_transfer_delay(&e, role_name)
+    }
+
+    // Commits address addition to the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be added.
+    fn commit_add_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, true);
+    }
+
+    // Commits address removal from the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be removed.
+    fn commit_remove_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, false);
+    }
+
+    // Applies the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The new list of the role members.
+    fn apply_role_members_change(e: Env, admin: Address, role_name: Symbol) -> Vec<Address> {
+        apply_role_members_change(&e, &admin, role_name)
+    }
+
+    // Reverts the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    fn revert_role_members_change(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_members_change(&e, &admin, role_name);
+    }
+
+    // Returns the committed members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The (address, is_added) pair if change is committed.
+    fn get_pending_role_members_change(e: Env, role_name: Symbol) -> Option<(Address, bool)> {
+        get_pending_role_members_change(&e, role_name)
+    }
+}
+
+// The `AdminStateContract` trait provides the view of the roles and pending admin actions.
+#[contractimpl]
+impl AdminStateContract for FeesCollector {
+    // Returns the roles state along with the delays, pending admin actions and emergency mode.
+    //
+    // # Returns
+    //
+    // The list of roles with current holders, pending transfers, signers and their deadlines,
+    // the configurable delays, the pending wasm hash with the upgrade deadline,
+    // the pending allowlist changes and contract actions and the emergency mode state.
+    fn get_admin_state(e: Env) -> AdminState {
+        get_admin_state(&e, &[("FeeSplit", get_fee_split_deadline(&e))])
+    }
+}
+
+// The `WasmAllowlistContract` trait provides the delayed management of the audited wasm hashes.
+#[contractimpl]
+impl WasmAllowlistContract for FeesCollector {
+    // Commits allowlist change for the contract kind.
+    // Only allowlisted hashes can be committed for the kind, unconfigured allowlist rejects everything.
+    // Changes are tracked per (kind, hash), so the whole release can be committed at once.
+    // The change is applied after the wasm allowlist delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash to allow or disallow.
+    // * `is_allowed` - Whether the wasm hash should be allowed.
+    fn commit_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+        is_allowed: bool,
+    ) {
+        commit_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash, is_allowed);
+    }
+
+    // Applies the committed allowlist change for the contract kind and wasm hash.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash of the committed change.
+    fn apply_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    ) {
+        apply_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash);
+    }
+
+    // Reverts the committed allowlist change for the contract kind and wasm hash.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash of the committed change.
+    fn revert_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    ) {
+        revert_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash);
+    }
+
+    // Returns the allowed wasm hashes for the contract kind.
+    //
+    // # Arguments
+    //
+    // * `kind_name` - The name of the contract kind.
+    fn get_allowed_wasms(e: Env, kind_name: Symbol) -> Vec<BytesN<32>> {
+        get_allowed_wasms(&e, &WasmKind::from_symbol(&e, kind_name))
+    }
+
+    // Checks whether the wasm hash is allowed for the contract kind.
+    //
+    // # Arguments
+    //
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash to check.
+    fn is_wasm_allowed(e: Env, kind_name: Symbol, wasm_hash: BytesN<32>) -> bool {
+        is_wasm_allowed(&e, &WasmKind::from_symbol(&e, kind_name), &wasm_hash)
+    }
+
+    // Returns the committed allowlist changes along with their deadlines.
+    fn get_pending_wasm_allowlist_changes(e: Env) -> Vec<WasmAllowlistChange> {
+        get_pending_allowlist_changes(&e)
+    }
+}
+
+// The `EmergencyModeContract` trait provides the scoped emergency mode with automatic expiration.
+#[contractimpl]
+impl EmergencyModeContract for FeesCollector {
+    // Enables the emergency mode for the limited time.
+    // Scope defines what is unlocked, currently the instant upgrade only.
+    // The emergency mode is disabled automatically once expired.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    // * `scope` - The powers unlocked by the emergency mode.
+    // * `duration` - The emergency mode duration in seconds.
+    fn enable_emergency_mode(
+        e: Env,
+        emergency_admin: Address,
+        scope: EmergencyScope,
+        duration: u64,
+    ) {
+        enable_scoped_emergency_mode(&e, &emergency_admin, &scope, duration);
+    }
+
+    // Returns the emergency mode state.
+    //
+    // # Returns
+    //
+    // The emergency mode flag, its scope, expiration timestamp and the remaining time in seconds.
+    fn get_emergency_mode_state(e: Env) -> EmergencyModeState {
+        get_emergency_mode_state(&e)
+    }
+}
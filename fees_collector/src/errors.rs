@@ -0,0 +1,18 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone)]
+#[repr(u32)]
+pub enum FeesCollectorError {
+    PathIsEmpty = 307,
+    DuplicatesNotAllowed = 315,
+    ZeroAmount = 2018,
+    InsufficientBalance = 2019,
+
+    // fees split errors
+    AnotherActionActive = 2906,
+    NoActionActive = 2907,
+    ActionNotReadyYet = 2908,
+    FeeSplitNotConfigured = 2909,
+    FeeSplitInvalid = 2910, // weights should sum up to 100%
+}
@@ -0,0 +1,87 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+#[derive(Clone)]
+pub struct Events(Env);
+
+impl Events {
+    #[inline(always)]
+    pub fn env(&self) -> &Env {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn new(env: &Env) -> Events {
+        Events(env.clone())
+    }
+
+    pub fn set_privileged_addrs(&self, operations_admin: Address) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "set_privileged_addrs"),),
+            (operations_admin,),
+        )
+    }
+
+    pub fn receive_fee(&self, token: Address, amount: u128) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "receive_fee"), token),
+            (amount,),
+        )
+    }
+
+    pub fn withdraw_fee(&self, token: Address, to: Address, amount: u128) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "withdraw_fee"), token),
+            (to, amount),
+        )
+    }
+
+    pub fn commit_fee_split(&self, split: Vec<(Address, u32)>) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "commit_fee_split"),), (split,))
+    }
+
+    pub fn apply_fee_split(&self, split: Vec<(Address, u32)>) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "apply_fee_split"),), (split,))
+    }
+
+    pub fn revert_fee_split(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "revert_fee_split"),), ())
+    }
+
+    pub fn distribute_fee(&self, token: Address, to: Address, amount: u128) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "distribute_fee"), token),
+            (to, amount),
+        )
+    }
+
+    pub fn set_router(&self, router: Address) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "set_router"),), (router,))
+    }
+
+    pub fn convert_fee(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        in_amount: u128,
+        out_amount: u128,
+    ) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "convert_fee"), token_in, token_out),
+            (in_amount, out_amount),
+        )
+    }
+
+    pub fn burn_fee(&self, token: Address, amount: u128) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "burn_fee"), token), (amount,))
+    }
+}
@@ -1,6 +1,70 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, BytesN, Env, Map, Symbol, Vec};
 
 pub trait AdminInterface {
     // Initializes the admin user.
     fn init_admin(e: Env, account: Address);
+
+    // Set privileged addresses
+    fn set_privileged_addrs(e: Env, admin: Address, operations_admin: Address);
+
+    // Get map of privileged roles
+    fn get_privileged_addrs(e: Env) -> Map<Symbol, Vec<Address>>;
+}
+
+pub trait FeesCollectorInterface {
+    // Transfer fees from the user to the collector and account them.
+    fn deposit(e: Env, user: Address, token: Address, amount: u128);
+
+    // Account tokens transferred to the collector directly. Returns newly accounted amount.
+    fn sync(e: Env, token: Address) -> u128;
+
+    // Send accounted tokens to the destination address.
+    fn withdraw(e: Env, admin: Address, token: Address, to: Address, amount: u128);
+
+    // Get accounted balances for the list of tokens.
+    fn get_balances(e: Env, tokens: Vec<Address>) -> Vec<u128>;
+}
+
+pub trait FeeSplitInterface {
+    // Set new fees split to be applied in future. Weights are set in basis points.
+    fn commit_fee_split(e: Env, admin: Address, split: Vec<(Address, u32)>);
+
+    // Apply committed fees split
+    fn apply_fee_split(e: Env, admin: Address);
+
+    // Revert committed fees split
+    fn revert_fee_split(e: Env, admin: Address);
+
+    // Get current fees split
+    fn get_fee_split(e: Env) -> Vec<(Address, u32)>;
+
+    // Get fees split to be applied in future
+    fn get_future_fee_split(e: Env) -> Vec<(Address, u32)>;
+
+    // Get deadline of the committed fees split
+    fn get_fee_split_deadline(e: Env) -> u64;
+
+    // Send accounted token balance to the recipients according to the split
+    fn distribute(e: Env, token: Address) -> u128;
+}
+
+pub trait ConversionInterface {
+    // Set router used to convert fees
+    fn set_router(e: Env, admin: Address, router: Address);
+
+    // Get router used to convert fees
+    fn get_router(e: Env) -> Address;
+
+    // Swap accounted tokens through the router. Returns amount of the output token received.
+    fn convert(
+        e: Env,
+        operator: Address,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        amount: u128,
+        out_min: u128,
+    ) -> u128;
+
+    // Burn accounted tokens
+    fn burn(e: Env, operator: Address, token: Address, amount: u128);
 }
@@ -1 +1,73 @@
+use paste::paste;
+use soroban_sdk::{contracttype, panic_with_error, Address, Env, Vec};
+use utils::bump::{bump_instance, bump_persistent};
+use utils::storage_errors::StorageError;
+use utils::{
+    generate_instance_storage_getter, generate_instance_storage_getter_and_setter,
+    generate_instance_storage_setter,
+};
 
+#[derive(Clone)]
+#[contracttype]
+enum DataKey {
+    TokenBalance(Address), // accounted balance of the token held by the collector
+
+    // fees split - (recipient, weight in basis points)
+    FeeSplit,
+    FutureFeeSplit,
+    FeeSplitDeadline,
+
+    Router, // router used to convert fees
+}
+
+generate_instance_storage_getter_and_setter!(router, DataKey::Router, Address);
+
+pub(crate) fn get_token_balance(e: &Env, token: &Address) -> u128 {
+    let key = DataKey::TokenBalance(token.clone());
+    match e.storage().persistent().get(&key) {
+        Some(value) => {
+            bump_persistent(e, &key);
+            value
+        }
+        None => 0,
+    }
+}
+
+pub(crate) fn set_token_balance(e: &Env, token: &Address, value: &u128) {
+    let key = DataKey::TokenBalance(token.clone());
+    e.storage().persistent().set(&key, value);
+    bump_persistent(e, &key);
+}
+
+pub(crate) fn get_fee_split(e: &Env) -> Option<Vec<(Address, u32)>> {
+    bump_instance(e);
+    e.storage().instance().get(&DataKey::FeeSplit)
+}
+
+pub(crate) fn set_fee_split(e: &Env, value: &Vec<(Address, u32)>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::FeeSplit, value);
+}
+
+pub(crate) fn get_future_fee_split(e: &Env) -> Option<Vec<(Address, u32)>> {
+    bump_instance(e);
+    e.storage().instance().get(&DataKey::FutureFeeSplit)
+}
+
+pub(crate) fn set_future_fee_split(e: &Env, value: &Vec<(Address, u32)>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::FutureFeeSplit, value);
+}
+
+pub(crate) fn get_fee_split_deadline(e: &Env) -> u64 {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::FeeSplitDeadline)
+        .unwrap_or(0)
+}
+
+pub(crate) fn set_fee_split_deadline(e: &Env, value: &u64) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::FeeSplitDeadline, value);
+}
@@ -1,10 +1,14 @@
 #![cfg(test)]
 extern crate std;
 
-use crate::testutils::{create_contract, install_dummy_wasm, jump, Setup};
+use crate::testutils::{
+    create_contract, create_token_contract, deploy_router_with_pool, get_token_admin_client,
+    install_dummy_wasm, jump, Setup,
+};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::{Address as _, Events};
 use soroban_sdk::{symbol_short, vec, Address, Env, IntoVal, Symbol};
+use utils::test_utils::allow_wasm;
 
 #[test]
 fn test() {
@@ -24,6 +28,26 @@ fn test_init_admin_twice() {
     setup.collector.init_admin(&setup.admin);
 }
 
+#[test]
+fn test_set_privileged_addrs_event() {
+    let setup = Setup::default();
+    let collector = setup.collector;
+    let operations_admin = Address::generate(&setup.env);
+
+    collector.set_privileged_addrs(&setup.admin, &operations_admin);
+    assert_eq!(
+        vec![&setup.env, setup.env.events().all().last().unwrap()],
+        vec![
+            &setup.env,
+            (
+                collector.address.clone(),
+                (Symbol::new(&setup.env, "set_privileged_addrs"),).into_val(&setup.env),
+                (operations_admin,).into_val(&setup.env),
+            ),
+        ]
+    );
+}
+
 #[test]
 fn test_transfer_ownership_events() {
     let setup = Setup::default();
@@ -89,6 +113,13 @@ fn test_upgrade_events() {
     let setup = Setup::default();
     let contract = setup.collector;
     let new_wasm_hash = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "FeesCollector",
+        &new_wasm_hash,
+    );
 
     contract.commit_upgrade(&setup.admin, &new_wasm_hash);
     assert_eq!(
@@ -168,6 +199,13 @@ fn test_emergency_upgrade() {
     let setup = Setup::default();
     let contract = setup.collector;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "FeesCollector",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -184,6 +222,13 @@ fn test_regular_upgrade() {
     let setup = Setup::default();
     let contract = setup.collector;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "FeesCollector",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -195,3 +240,311 @@ fn test_regular_upgrade() {
 
     assert_eq!(contract.version(), 130)
 }
+
+#[test]
+fn test_deposit_and_withdraw() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let user = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_other = create_token_contract(&env, &token_admin);
+    get_token_admin_client(&env, &token.address).mint(&user, &1000);
+
+    collector.deposit(&user, &token.address, &600);
+    assert_eq!(token.balance(&user), 400);
+    assert_eq!(token.balance(&collector.address), 600);
+    assert_eq!(
+        collector.get_balances(&vec![&env, token.address.clone(), token_other.address.clone()]),
+        vec![&env, 600, 0]
+    );
+
+    collector.withdraw(&setup.admin, &token.address, &destination, &200);
+    collector.withdraw(&setup.operations_admin, &token.address, &destination, &100);
+    assert_eq!(token.balance(&destination), 300);
+    assert_eq!(
+        collector.get_balances(&vec![&env, token.address.clone()]),
+        vec![&env, 300]
+    );
+}
+
+#[test]
+fn test_sync() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    get_token_admin_client(&env, &token.address).mint(&user, &1000);
+
+    collector.deposit(&user, &token.address, &100);
+    // fees sent directly are not accounted until sync
+    token.transfer(&user, &collector.address, &250);
+    assert_eq!(
+        collector.get_balances(&vec![&env, token.address.clone()]),
+        vec![&env, 100]
+    );
+
+    assert_eq!(collector.sync(&token.address), 250);
+    assert_eq!(collector.sync(&token.address), 0);
+    assert_eq!(
+        collector.get_balances(&vec![&env, token.address.clone()]),
+        vec![&env, 350]
+    );
+}
+
+#[should_panic(expected = "Error(Contract, #2019)")]
+#[test]
+fn test_withdraw_over_balance() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    get_token_admin_client(&env, &token.address).mint(&user, &1000);
+
+    collector.deposit(&user, &token.address, &100);
+    // tokens not accounted yet are not available for withdrawal
+    token.transfer(&user, &collector.address, &100);
+    collector.withdraw(&setup.admin, &token.address, &user, &101);
+}
+
+#[should_panic(expected = "Error(Contract, #2018)")]
+#[test]
+fn test_deposit_zero() {
+    let setup = Setup::default();
+    let token = create_token_contract(&setup.env, &Address::generate(&setup.env));
+    setup
+        .collector
+        .deposit(&Address::generate(&setup.env), &token.address, &0);
+}
+
+#[test]
+fn test_fees_events() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let user = Address::generate(&env);
+    let destination = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    get_token_admin_client(&env, &token.address).mint(&user, &1000);
+
+    collector.deposit(&user, &token.address, &100);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                collector.address.clone(),
+                (Symbol::new(&env, "receive_fee"), token.address.clone()).into_val(&env),
+                (100_u128,).into_val(&env),
+            ),
+        ]
+    );
+
+    collector.withdraw(&setup.admin, &token.address, &destination, &40);
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                collector.address.clone(),
+                (Symbol::new(&env, "withdraw_fee"), token.address.clone()).into_val(&env),
+                (destination.clone(), 40_u128).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_fee_split_distribute() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let user = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let buyback = Address::generate(&env);
+    let insurance = Address::generate(&env);
+    let token = create_token_contract(&env, &Address::generate(&env));
+    get_token_admin_client(&env, &token.address).mint(&user, &1000);
+
+    // first split is applied without delay
+    let split = vec![
+        &env,
+        (treasury.clone(), 5000),
+        (buyback.clone(), 3333),
+        (insurance.clone(), 1667),
+    ];
+    collector.commit_fee_split(&setup.admin, &split);
+    collector.apply_fee_split(&setup.admin);
+    assert_eq!(collector.get_fee_split(), split);
+
+    collector.deposit(&user, &token.address, &100);
+    token.transfer(&user, &collector.address, &1);
+    assert_eq!(collector.distribute(&token.address), 99);
+    assert_eq!(token.balance(&treasury), 50);
+    assert_eq!(token.balance(&buyback), 33);
+    assert_eq!(token.balance(&insurance), 16);
+
+    // rounding dust is retained
+    assert_eq!(token.balance(&collector.address), 2);
+    assert_eq!(
+        collector.get_balances(&vec![&env, token.address.clone()]),
+        vec![&env, 2]
+    );
+}
+
+#[test]
+fn test_fee_split_change_delay() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let treasury = Address::generate(&env);
+    let buyback = Address::generate(&env);
+
+    let split = vec![&env, (treasury.clone(), 10000)];
+    collector.commit_fee_split(&setup.admin, &split);
+    collector.apply_fee_split(&setup.admin);
+
+    let new_split = vec![&env, (treasury.clone(), 8000), (buyback.clone(), 2000)];
+    collector.commit_fee_split(&setup.admin, &new_split);
+    assert_eq!(collector.get_fee_split(), split);
+    assert_eq!(collector.get_future_fee_split(), new_split);
+    assert!(collector.try_apply_fee_split(&setup.admin).is_err());
+
+    collector.revert_fee_split(&setup.admin);
+    assert_eq!(collector.get_future_fee_split(), split);
+    assert!(collector.try_apply_fee_split(&setup.admin).is_err());
+
+    collector.commit_fee_split(&setup.admin, &new_split);
+    jump(&env, ADMIN_ACTIONS_DELAY + 1);
+    collector.apply_fee_split(&setup.admin);
+    assert_eq!(collector.get_fee_split(), new_split);
+    assert_eq!(collector.get_fee_split_deadline(), 0);
+}
+
+#[should_panic(expected = "Error(Contract, #2910)")]
+#[test]
+fn test_fee_split_invalid_weights() {
+    let setup = Setup::default();
+    let env = setup.env;
+    setup.collector.commit_fee_split(
+        &setup.admin,
+        &vec![
+            &env,
+            (Address::generate(&env), 5000),
+            (Address::generate(&env), 4999),
+        ],
+    );
+}
+
+#[should_panic(expected = "Error(Contract, #315)")]
+#[test]
+fn test_fee_split_duplicate_recipients() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let recipient = Address::generate(&env);
+    setup.collector.commit_fee_split(
+        &setup.admin,
+        &vec![&env, (recipient.clone(), 5000), (recipient, 5000)],
+    );
+}
+
+#[should_panic(expected = "Error(Contract, #2909)")]
+#[test]
+fn test_distribute_split_not_configured() {
+    let setup = Setup::default();
+    let token = create_token_contract(&setup.env, &Address::generate(&setup.env));
+    setup.collector.distribute(&token.address);
+}
+
+#[test]
+fn test_convert_and_burn() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let user = Address::generate(&env);
+
+    let mut token_a = create_token_contract(&env, &setup.admin);
+    let mut token_b = create_token_contract(&env, &setup.admin);
+    if token_b.address < token_a.address {
+        std::mem::swap(&mut token_a, &mut token_b);
+    }
+    let (router, pool_index) = deploy_router_with_pool(&env, &setup.admin, &token_a, &token_b);
+    collector.set_router(&setup.admin, &router.address);
+
+    get_token_admin_client(&env, &token_a.address).mint(&user, &1_0000000);
+    collector.deposit(&user, &token_a.address, &1_0000000);
+
+    let tokens = vec![&env, token_a.address.clone(), token_b.address.clone()];
+    let swaps_chain = vec![&env, (tokens, pool_index, token_b.address.clone())];
+    let out_amount = collector.convert(
+        &setup.operations_admin,
+        &swaps_chain,
+        &token_a.address,
+        &1_0000000,
+        &9969999,
+    );
+    assert_eq!(out_amount, 9969999); // 9999999 - .3%
+    assert_eq!(
+        vec![&env, env.events().all().last().unwrap()],
+        vec![
+            &env,
+            (
+                collector.address.clone(),
+                (
+                    Symbol::new(&env, "convert_fee"),
+                    token_a.address.clone(),
+                    token_b.address.clone()
+                )
+                    .into_val(&env),
+                (1_0000000_u128, 9969999_u128).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(
+        collector.get_balances(&vec![
+            &env,
+            token_a.address.clone(),
+            token_b.address.clone()
+        ]),
+        vec![&env, 0, 9969999]
+    );
+    assert_eq!(token_b.balance(&collector.address), 9969999);
+
+    collector.burn(&setup.operations_admin, &token_b.address, &9969999);
+    assert_eq!(token_b.balance(&collector.address), 0);
+    assert_eq!(
+        collector.get_balances(&vec![&env, token_b.address.clone()]),
+        vec![&env, 0]
+    );
+}
+
+#[should_panic(expected = "Error(Contract, #2019)")]
+#[test]
+fn test_convert_over_balance() {
+    let setup = Setup::default();
+    let env = setup.env;
+    let collector = setup.collector;
+    let token_a = create_token_contract(&env, &setup.admin);
+    let token_b = create_token_contract(&env, &setup.admin);
+    collector.convert(
+        &setup.operations_admin,
+        &vec![
+            &env,
+            (
+                vec![&env, token_a.address.clone(), token_b.address.clone()],
+                install_dummy_wasm(&env),
+                token_b.address.clone(),
+            ),
+        ],
+        &token_a.address,
+        &1,
+        &0,
+    );
+}
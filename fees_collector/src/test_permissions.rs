@@ -1,9 +1,13 @@
 #![cfg(test)]
 
-use crate::testutils::{create_contract, install_dummy_wasm, jump, Setup};
+use crate::testutils::{
+    create_contract, create_token_contract, get_token_admin_client, install_dummy_wasm, jump,
+    Setup,
+};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{symbol_short, Address, Env, Symbol};
+use soroban_sdk::{symbol_short, vec, Address, Env, Symbol};
+use utils::test_utils::allow_wasm;
 
 // test admin transfer ownership
 #[test]
@@ -316,8 +320,16 @@ fn test_commit_upgrade_emergency_admin() {
 fn test_commit_upgrade_admin() {
     let setup = Setup::default();
     let collector = setup.collector;
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &collector.address,
+        &setup.admin,
+        "FeesCollector",
+        &new_wasm,
+    );
     assert!(collector
-        .try_commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env))
+        .try_commit_upgrade(&setup.admin, &new_wasm)
         .is_ok());
 }
 
@@ -326,7 +338,15 @@ fn test_apply_upgrade_third_party_user() {
     let setup = Setup::default();
     let collector = setup.collector;
     let user = Address::generate(&setup.env);
-    collector.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &collector.address,
+        &setup.admin,
+        "FeesCollector",
+        &new_wasm,
+    );
+    collector.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(collector.try_apply_upgrade(&user).is_err());
 }
@@ -335,7 +355,15 @@ fn test_apply_upgrade_third_party_user() {
 fn test_apply_upgrade_emergency_admin() {
     let setup = Setup::default();
     let collector = setup.collector;
-    collector.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &collector.address,
+        &setup.admin,
+        "FeesCollector",
+        &new_wasm,
+    );
+    collector.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(collector.try_apply_upgrade(&setup.emergency_admin).is_err());
 }
@@ -344,7 +372,15 @@ fn test_apply_upgrade_emergency_admin() {
 fn test_apply_upgrade_admin() {
     let setup = Setup::default();
     let collector = setup.collector;
-    collector.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &collector.address,
+        &setup.admin,
+        "FeesCollector",
+        &new_wasm,
+    );
+    collector.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(collector.try_apply_upgrade(&setup.admin).is_ok());
 }
@@ -375,3 +411,229 @@ fn test_set_emergency_mode_admin() {
         .try_set_emergency_mode(&setup.emergency_admin, &false)
         .is_ok());
 }
+
+// privileged addresses
+#[test]
+fn test_set_privileged_addrs_third_party_user() {
+    let setup = Setup::default();
+    let collector = setup.collector;
+    let user = Address::generate(&setup.env);
+    assert!(collector.try_set_privileged_addrs(&user, &user).is_err());
+}
+
+#[test]
+fn test_set_privileged_addrs_operations_admin() {
+    let setup = Setup::default();
+    let collector = setup.collector;
+    assert!(collector
+        .try_set_privileged_addrs(&setup.operations_admin, &setup.operations_admin)
+        .is_err());
+}
+
+#[test]
+fn test_set_privileged_addrs_admin() {
+    let setup = Setup::default();
+    let collector = setup.collector;
+    let operations_admin_new = Address::generate(&setup.env);
+    assert!(collector
+        .try_set_privileged_addrs(&setup.admin, &operations_admin_new)
+        .is_ok());
+    assert_eq!(
+        collector
+            .get_privileged_addrs()
+            .get(symbol_short!("Admin"))
+            .unwrap(),
+        vec![&setup.env, setup.admin.clone()]
+    );
+    assert_eq!(
+        collector
+            .get_privileged_addrs()
+            .get(Symbol::new(&setup.env, "OperationsAdmin"))
+            .unwrap(),
+        vec![&setup.env, operations_admin_new]
+    );
+}
+
+// withdraw
+fn setup_collector_balance(setup: &Setup) -> Address {
+    let user = Address::generate(&setup.env);
+    let token = create_token_contract(&setup.env, &Address::generate(&setup.env));
+    get_token_admin_client(&setup.env, &token.address).mint(&user, &1000);
+    setup.collector.deposit(&user, &token.address, &1000);
+    token.address
+}
+
+#[test]
+fn test_withdraw_third_party_user() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    let user = Address::generate(&setup.env);
+    assert!(setup
+        .collector
+        .try_withdraw(&user, &token, &user, &100)
+        .is_err());
+}
+
+#[test]
+fn test_withdraw_emergency_admin() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    assert!(setup
+        .collector
+        .try_withdraw(&setup.emergency_admin, &token, &setup.emergency_admin, &100)
+        .is_err());
+}
+
+#[test]
+fn test_withdraw_operations_admin() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    assert!(setup
+        .collector
+        .try_withdraw(&setup.operations_admin, &token, &setup.operations_admin, &100)
+        .is_ok());
+}
+
+#[test]
+fn test_withdraw_admin() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    assert!(setup
+        .collector
+        .try_withdraw(&setup.admin, &token, &setup.admin, &100)
+        .is_ok());
+}
+
+// fees split
+#[test]
+fn test_commit_fee_split_third_party_user() {
+    let setup = Setup::default();
+    let user = Address::generate(&setup.env);
+    assert!(setup
+        .collector
+        .try_commit_fee_split(&user, &vec![&setup.env, (user.clone(), 10000)])
+        .is_err());
+}
+
+#[test]
+fn test_commit_fee_split_operations_admin() {
+    let setup = Setup::default();
+    assert!(setup
+        .collector
+        .try_commit_fee_split(
+            &setup.operations_admin,
+            &vec![&setup.env, (setup.operations_admin.clone(), 10000)]
+        )
+        .is_err());
+}
+
+#[test]
+fn test_commit_fee_split_admin() {
+    let setup = Setup::default();
+    assert!(setup
+        .collector
+        .try_commit_fee_split(&setup.admin, &vec![&setup.env, (setup.admin.clone(), 10000)])
+        .is_ok());
+}
+
+#[test]
+fn test_apply_fee_split_third_party_user() {
+    let setup = Setup::default();
+    let user = Address::generate(&setup.env);
+    setup
+        .collector
+        .commit_fee_split(&setup.admin, &vec![&setup.env, (user.clone(), 10000)]);
+    assert!(setup.collector.try_apply_fee_split(&user).is_err());
+    assert!(setup.collector.try_revert_fee_split(&user).is_err());
+}
+
+#[test]
+fn test_distribute_third_party_user() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    setup
+        .collector
+        .commit_fee_split(&setup.admin, &vec![&setup.env, (setup.admin.clone(), 10000)]);
+    setup.collector.apply_fee_split(&setup.admin);
+
+    // distribution is permissionless
+    setup.env.set_auths(&[]);
+    assert_eq!(setup.collector.distribute(&token), 1000);
+}
+
+// conversion
+#[test]
+fn test_set_router_third_party_user() {
+    let setup = Setup::default();
+    let user = Address::generate(&setup.env);
+    assert!(setup.collector.try_set_router(&user, &user).is_err());
+}
+
+#[test]
+fn test_set_router_operations_admin() {
+    let setup = Setup::default();
+    assert!(setup
+        .collector
+        .try_set_router(&setup.operations_admin, &setup.operations_admin)
+        .is_err());
+}
+
+#[test]
+fn test_set_router_admin() {
+    let setup = Setup::default();
+    let router = Address::generate(&setup.env);
+    assert!(setup.collector.try_set_router(&setup.admin, &router).is_ok());
+    assert_eq!(setup.collector.get_router(), router);
+}
+
+#[test]
+fn test_burn_third_party_user() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    let user = Address::generate(&setup.env);
+    assert!(setup.collector.try_burn(&user, &token, &100).is_err());
+}
+
+#[test]
+fn test_burn_admin() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    assert!(setup
+        .collector
+        .try_burn(&setup.admin, &token, &100)
+        .is_err());
+}
+
+#[test]
+fn test_burn_operations_admin() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    assert!(setup
+        .collector
+        .try_burn(&setup.operations_admin, &token, &100)
+        .is_ok());
+}
+
+#[test]
+fn test_convert_third_party_user() {
+    let setup = Setup::default();
+    let token = setup_collector_balance(&setup);
+    let user = Address::generate(&setup.env);
+    assert!(setup
+        .collector
+        .try_convert(
+            &user,
+            &vec![
+                &setup.env,
+                (
+                    vec![&setup.env, token.clone()],
+                    install_dummy_wasm(&setup.env),
+                    token.clone()
+                )
+            ],
+            &token,
+            &100,
+            &0
+        )
+        .is_err());
+}
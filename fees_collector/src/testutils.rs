@@ -2,7 +2,11 @@
 
 use crate::FeesCollectorClient;
 use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-use soroban_sdk::{Address, BytesN, Env, Symbol};
+use soroban_sdk::token::{
+    StellarAssetClient as SorobanTokenAdminClient, TokenClient as SorobanTokenClient,
+};
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+use utils::test_utils::allow_wasm;
 
 pub fn install_dummy_wasm<'a>(e: &Env) -> BytesN<32> {
     soroban_sdk::contractimport!(file = "../contracts/dummy_contract.wasm");
@@ -14,6 +18,122 @@ pub fn create_contract<'a>(e: &Env) -> FeesCollectorClient<'a> {
     client
 }
 
+pub(crate) fn create_token_contract<'a>(e: &Env, admin: &Address) -> SorobanTokenClient<'a> {
+    SorobanTokenClient::new(
+        e,
+        &e.register_stellar_asset_contract_v2(admin.clone())
+            .address(),
+    )
+}
+
+pub(crate) fn get_token_admin_client<'a>(
+    e: &'a Env,
+    address: &'a Address,
+) -> SorobanTokenAdminClient<'a> {
+    SorobanTokenAdminClient::new(e, address)
+}
+
+pub mod swap_router {
+    soroban_sdk::contractimport!(
+        file =
+            "../target/wasm32-unknown-unknown/release/soroban_liquidity_pool_router_contract.wasm"
+    );
+}
+
+pub mod liquidity_pool {
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/soroban_liquidity_pool_contract.wasm"
+    );
+}
+
+mod reward_boost_feed {
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/soroban_locker_feed_contract.wasm"
+    );
+}
+
+fn install_token_wasm(e: &Env) -> BytesN<32> {
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/soroban_token_contract.wasm"
+    );
+    e.deployer().upload_contract_wasm(WASM)
+}
+
+fn install_stableswap_liq_pool_hash(e: &Env) -> BytesN<32> {
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/soroban_liquidity_pool_stableswap_contract.wasm"
+    );
+    e.deployer().upload_contract_wasm(WASM)
+}
+
+fn deploy_plane_contract<'a>(e: &Env) -> Address {
+    soroban_sdk::contractimport!(
+        file =
+            "../target/wasm32-unknown-unknown/release/soroban_liquidity_pool_plane_contract.wasm"
+    );
+    Client::new(e, &e.register(WASM, ())).address
+}
+
+// Deploys router with single standard pool for the pair of tokens.
+// Returns router client and the pool index.
+pub(crate) fn deploy_router_with_pool<'a>(
+    e: &Env,
+    admin: &Address,
+    token_a: &SorobanTokenClient,
+    token_b: &SorobanTokenClient,
+) -> (swap_router::Client<'a>, BytesN<32>) {
+    let token_a_admin_client = get_token_admin_client(e, &token_a.address);
+    let token_b_admin_client = get_token_admin_client(e, &token_b.address);
+
+    let boost_feed = reward_boost_feed::Client::new(
+        e,
+        &e.register(
+            reward_boost_feed::WASM,
+            reward_boost_feed::Args::__constructor(admin, admin, admin),
+        ),
+    );
+    let router = swap_router::Client::new(e, &e.register(swap_router::WASM, ()));
+    router.init_admin(admin);
+    let pool_hash = e.deployer().upload_contract_wasm(liquidity_pool::WASM);
+    let stableswap_pool_hash = install_stableswap_liq_pool_hash(e);
+    let token_hash = install_token_wasm(e);
+    allow_wasm(e, &router.address, admin, "StandardPool", &pool_hash);
+    allow_wasm(
+        e,
+        &router.address,
+        admin,
+        "StableSwapPool",
+        &stableswap_pool_hash,
+    );
+    allow_wasm(e, &router.address, admin, "Token", &token_hash);
+    router.set_pool_hash(admin, &pool_hash);
+    router.set_stableswap_pool_hash(admin, &stableswap_pool_hash);
+    router.set_token_hash(admin, &token_hash);
+    router.set_reward_token(admin, &token_a.address);
+    router.set_pools_plane(admin, &deploy_plane_contract(e));
+    router.configure_init_pool_payment(
+        admin,
+        &token_a.address,
+        &10_0000000,
+        &1_0000000,
+        &router.address,
+    );
+    router.set_reward_boost_config(admin, &token_a.address, &boost_feed.address);
+
+    token_a_admin_client.mint(admin, &10_0000000);
+    let tokens = Vec::from_array(e, [token_a.address.clone(), token_b.address.clone()]);
+    let (pool_index, pool_address) = router.init_standard_pool(admin, &tokens, &30);
+    token_a_admin_client.mint(admin, &1_000_000_000_0000000);
+    token_b_admin_client.mint(admin, &1_000_000_000_0000000);
+    liquidity_pool::Client::new(e, &pool_address).deposit(
+        admin,
+        &Vec::from_array(e, [1_000_000_000_0000000, 1_000_000_000_0000000]),
+        &1,
+    );
+
+    (router, pool_index)
+}
+
 pub(crate) fn jump(e: &Env, time: u64) {
     e.ledger().set(LedgerInfo {
         timestamp: e.ledger().timestamp().saturating_add(time),
@@ -32,6 +152,7 @@ pub(crate) struct Setup<'a> {
 
     pub(crate) admin: Address,
     pub(crate) emergency_admin: Address,
+    pub(crate) operations_admin: Address,
     pub(crate) collector: FeesCollectorClient<'a>,
 }
 
@@ -54,10 +175,14 @@ impl Default for Setup<'_> {
         );
         collector.apply_transfer_ownership(&admin, &Symbol::new(&env, "EmergencyAdmin"));
 
+        let operations_admin = Address::generate(&env);
+        collector.set_privileged_addrs(&admin, &operations_admin);
+
         Setup {
             env,
             admin,
             emergency_admin,
+            operations_admin,
             collector,
         }
     }
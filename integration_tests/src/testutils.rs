@@ -6,6 +6,7 @@ use soroban_sdk::token::{
     StellarAssetClient as SorobanTokenAdminClient, TokenClient as SorobanTokenClient,
 };
 use soroban_sdk::{Address, BytesN, Env, Vec};
+use utils::test_utils::allow_wasm;
 
 pub(crate) struct Setup<'a> {
     pub(crate) env: Env,
@@ -51,15 +52,24 @@ impl Setup<'_> {
             .upload_contract_wasm(contracts::constant_product_pool::WASM);
         let token_hash = e.deployer().upload_contract_wasm(contracts::lp_token::WASM);
         let plane = deploy_plane_contract(&e);
+        plane.init_admin(&admin);
 
         let router = deploy_liqpool_router_contract(e.clone());
         router.init_admin(&admin);
-        router.set_pool_hash(&admin, &pool_hash);
-        router.set_stableswap_pool_hash(
+        let stableswap_pool_hash = e
+            .deployer()
+            .upload_contract_wasm(contracts::stableswap_pool::WASM);
+        allow_wasm(&e, &router.address, &admin, "StandardPool", &pool_hash);
+        allow_wasm(
+            &e,
+            &router.address,
             &admin,
-            &e.deployer()
-                .upload_contract_wasm(contracts::stableswap_pool::WASM),
+            "StableSwapPool",
+            &stableswap_pool_hash,
         );
+        allow_wasm(&e, &router.address, &admin, "Token", &token_hash);
+        router.set_pool_hash(&admin, &pool_hash);
+        router.set_stableswap_pool_hash(&admin, &stableswap_pool_hash);
         router.set_token_hash(&admin, &token_hash);
         router.set_reward_token(&admin, &reward_token.address);
         router.set_pools_plane(&admin, &plane.address);
@@ -1 +1,6 @@
 pub(crate) const FEE_MULTIPLIER: u128 = 10_000;
+
+// price oracle
+pub(crate) const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000; // 1e18
+pub(crate) const OBSERVATIONS_LIMIT: u32 = 24;
+pub(crate) const OBSERVATION_PERIOD: u64 = 3600; // 1 hour between observations, 24 hours of history
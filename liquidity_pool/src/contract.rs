@@ -1,34 +1,59 @@
 use crate::constants::FEE_MULTIPLIER;
 use crate::errors::LiquidityPoolError;
+use crate::events::Events;
+use crate::oracle;
+use crate::oracle::update_price_oracle;
 use crate::plane::update_plane;
 use crate::plane_interface::Plane;
 use crate::pool;
-use crate::pool::{get_amount_out, get_amount_out_strict_receive};
+use crate::pool::{get_amount_out, get_amount_out_strict_receive, get_protocol_fee};
 use crate::pool_interface::{
-    AdminInterfaceTrait, LiquidityPoolCrunch, LiquidityPoolTrait, RewardsTrait,
-    UpgradeableContract, UpgradeableLPTokenTrait,
+    AdminInterfaceTrait, FlashLoanTrait, LiquidityPoolCrunch, LiquidityPoolTrait, PriceOracleTrait,
+    ProtocolFeeTrait, RewardsTrait, UpgradeableContract, UpgradeableLPTokenTrait,
 };
 use crate::rewards::get_rewards_manager;
 use crate::storage::{
-    get_fee_fraction, get_is_killed_claim, get_is_killed_deposit, get_is_killed_swap, get_plane,
-    get_reserve_a, get_reserve_b, get_router, get_token_a, get_token_b, get_token_future_wasm,
-    has_plane, put_fee_fraction, put_reserve_a, put_reserve_b, put_token_a, put_token_b,
-    set_is_killed_claim, set_is_killed_deposit, set_is_killed_swap, set_plane, set_router,
-    set_token_future_wasm,
+    get_admin_actions_deadline, get_fee_destination, get_fee_fraction,
+    get_future_protocol_fee_fraction, get_is_killed_claim, get_is_killed_deposit,
+    get_is_killed_swap, get_plane, get_protocol_fee_a, get_protocol_fee_b,
+    get_protocol_fee_fraction, get_reserve_a, get_reserve_b, get_router, get_token_a, get_token_b,
+    get_token_future_wasm, has_plane, put_fee_fraction, put_reserve_a, put_reserve_b, put_token_a,
+    put_token_b, set_admin_actions_deadline, set_fee_destination,
+    set_future_protocol_fee_fraction, set_is_killed_claim, set_is_killed_deposit,
+    set_is_killed_swap, set_plane, set_protocol_fee_a, set_protocol_fee_b,
+    set_protocol_fee_fraction, set_router, set_token_future_wasm,
 };
 use crate::token::{create_contract, transfer_a, transfer_b};
 use access_control::access::{AccessControl, AccessControlTrait};
-use access_control::emergency::{get_emergency_mode, set_emergency_mode};
+use access_control::delays::{
+    apply_delay, commit_delay, get_delay, get_future_delay, revert_delay, Delay, DelaysTrait,
+};
+use access_control::emergency::{
+    enable_scoped_emergency_mode, get_emergency_mode, get_emergency_mode_state,
+    toggle_emergency_mode, EmergencyModeState, EmergencyScope,
+};
 use access_control::errors::AccessControlError;
 use access_control::events::Events as AccessControlEvents;
-use access_control::interface::TransferableContract;
+use access_control::interface::{
+    AdminSignersContract, ConfigurableDelaysContract, EmergencyModeContract, RoleTimelockContract,
+    TransferableContract,
+};
 use access_control::management::{MultipleAddressesManagementTrait, SingleAddressManagementTrait};
 use access_control::role::Role;
 use access_control::role::SymbolRepresentation;
-use access_control::transfer::TransferOwnershipTrait;
+use access_control::threshold::{
+    apply_admin_signers, commit_admin_signers, get_admin_cosigners, get_admin_signers,
+    get_future_admin_signers, revert_admin_signers, set_admin_cosigners,
+};
+use access_control::transfer::{
+    apply_role_members_change, apply_role_transfer_delay, commit_role_member_change,
+    commit_role_transfer_delay, get_future_role_transfer_delay, get_pending_role_members_change,
+    get_role_transfer_delay, revert_role_members_change, revert_role_transfer_delay,
+    TransferOwnershipTrait,
+};
 use access_control::utils::{
-    require_pause_admin_or_owner, require_pause_or_emergency_pause_admin_or_owner,
-    require_rewards_admin_or_owner,
+    require_operations_admin_or_owner, require_pause_admin_or_owner,
+    require_pause_or_emergency_pause_admin_or_owner, require_rewards_admin_or_owner,
 };
 use liquidity_pool_events::Events as PoolEvents;
 use liquidity_pool_events::LiquidityPoolEvents;
@@ -40,16 +65,23 @@ use rewards::storage::{
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::token::TokenClient as SorobanTokenClient;
 use soroban_sdk::{
-    contract, contractimpl, contractmeta, panic_with_error, symbol_short, Address, BytesN, Env,
-    IntoVal, Map, Symbol, Val, Vec, U256,
+    contract, contractimpl, contractmeta, panic_with_error, symbol_short, Address, Bytes, BytesN,
+    Env, IntoVal, Map, Symbol, Val, Vec, U256,
 };
 use token_share::{
     burn_shares, get_token_share, get_total_shares, get_user_balance_shares, mint_shares,
     put_token_share, Client as LPTokenClient,
 };
+use upgrade::allowlist::{require_wasm_allowed_by, WasmKind};
 use upgrade::events::Events as UpgradeEvents;
-use upgrade::{apply_upgrade, commit_upgrade, revert_upgrade};
-use utils::u256_math::ExtraMath;
+use upgrade::history::UpgradeRecord;
+use upgrade::interface::AdminStateContract;
+use upgrade::view::{get_admin_state, AdminState};
+use upgrade::{
+    apply_upgrade, commit_checked_upgrade, commit_upgrade_allowed_by, get_storage_version,
+    get_upgrade_history, migrate, require_migrated, revert_upgrade, rollback_upgrade,
+    set_storage_version,
+};
 
 // Metadata that is added on to the WASM custom section
 contractmeta!(
@@ -155,6 +187,7 @@ impl LiquidityPoolTrait for LiquidityPool {
             panic_with_error!(&e, LiquidityPoolError::AlreadyInitialized);
         }
         access_control.set_role_address(&Role::Admin, &admin);
+        set_storage_version(&e, Self::version());
         access_control.set_role_address(&Role::EmergencyAdmin, &privileged_addrs.0);
         access_control.set_role_address(&Role::RewardsAdmin, &privileged_addrs.1);
         access_control.set_role_address(&Role::OperationsAdmin, &privileged_addrs.2);
@@ -238,6 +271,7 @@ impl LiquidityPoolTrait for LiquidityPool {
         desired_amounts: Vec<u128>,
         min_shares: u128,
     ) -> (Vec<u128>, u128) {
+        require_migrated(&e, Self::version());
         // Depositor needs to authorize the deposit
         user.require_auth();
 
@@ -278,6 +312,9 @@ impl LiquidityPoolTrait for LiquidityPool {
         let amounts =
             pool::get_deposit_amounts(&e, desired_a, min_a, desired_b, min_b, reserve_a, reserve_b);
 
+        // Accumulate oracle prices with the reserves before the deposit
+        update_price_oracle(&e);
+
         // Increase reserves
         put_reserve_a(&e, reserve_a + amounts.0);
         put_reserve_b(&e, reserve_b + amounts.1);
@@ -301,19 +338,14 @@ impl LiquidityPoolTrait for LiquidityPool {
         let (new_reserve_a, new_reserve_b) = (get_reserve_a(&e), get_reserve_b(&e));
         let total_shares = get_total_shares(&e);
 
-        let zero = 0;
-        let new_total_shares = if reserve_a > zero && reserve_b > zero {
-            let shares_a = new_reserve_a.fixed_mul_floor(&e, &total_shares, &reserve_a);
-            let shares_b = new_reserve_b.fixed_mul_floor(&e, &total_shares, &reserve_b);
-            shares_a.min(shares_b)
-        } else {
-            // if .mul doesn't fail, sqrt also won't -> safe to unwrap
-            U256::from_u128(&e, new_reserve_a)
-                .mul(&U256::from_u128(&e, new_reserve_b))
-                .sqrt()
-                .to_u128()
-                .unwrap()
-        };
+        let new_total_shares = pool::get_new_total_shares(
+            &e,
+            reserve_a,
+            reserve_b,
+            new_reserve_a,
+            new_reserve_b,
+            total_shares,
+        );
 
         let shares_to_mint = new_total_shares - total_shares;
         if shares_to_mint < min_shares {
@@ -343,6 +375,162 @@ impl LiquidityPoolTrait for LiquidityPool {
         (amounts_vec, shares_to_mint)
     }
 
+    // Deposits a single token into the pool.
+    // Part of the token is swapped internally, so the rest is deposited along with the swap result.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user depositing the token.
+    // * `token_idx` - The index of the token to deposit.
+    // * `amount` - The amount of the token to deposit.
+    // * `min_shares` - The minimum amount of pool tokens to mint.
+    //
+    // # Returns
+    //
+    // The amount of pool tokens minted.
+    fn deposit_single(
+        e: Env,
+        user: Address,
+        token_idx: u32,
+        amount: u128,
+        min_shares: u128,
+    ) -> u128 {
+        require_migrated(&e, Self::version());
+        // Depositor needs to authorize the deposit
+        user.require_auth();
+
+        if get_is_killed_deposit(&e) {
+            panic_with_error!(e, LiquidityPoolError::PoolDepositKilled);
+        }
+
+        if get_is_killed_swap(&e) {
+            panic_with_error!(e, LiquidityPoolError::PoolSwapKilled);
+        }
+
+        if token_idx > 1 {
+            panic_with_error!(&e, LiquidityPoolValidationError::InTokenOutOfBounds);
+        }
+
+        if amount == 0 {
+            panic_with_error!(e, LiquidityPoolValidationError::ZeroAmount);
+        }
+
+        let (reserve_a, reserve_b) = (get_reserve_a(&e), get_reserve_b(&e));
+        let deposit = pool::get_deposit_single_amounts(&e, token_idx, amount, reserve_a, reserve_b);
+
+        // Before actual changes were made to the pool, update total rewards data and refresh/initialize user reward
+        let rewards = get_rewards_manager(&e);
+        let total_shares = get_total_shares(&e);
+        let user_shares = get_user_balance_shares(&e, &user);
+        rewards
+            .manager()
+            .checkpoint_user(&user, total_shares, user_shares);
+
+        let tokens = Self::get_tokens(e.clone());
+        let out_idx = 1 - token_idx;
+        let token_in_client = SorobanTokenClient::new(&e, &tokens.get(token_idx).unwrap());
+        let token_out_client = SorobanTokenClient::new(&e, &tokens.get(out_idx).unwrap());
+        token_in_client.transfer(&user, &e.current_contract_address(), &(amount as i128));
+
+        let (swapped_reserve_a, swapped_reserve_b) = deposit.reserves;
+        let new_total_shares = pool::get_new_total_shares(
+            &e,
+            swapped_reserve_a,
+            swapped_reserve_b,
+            swapped_reserve_a + deposit.amounts.0,
+            swapped_reserve_b + deposit.amounts.1,
+            total_shares,
+        );
+        let shares_to_mint = new_total_shares - total_shares;
+        if shares_to_mint < min_shares {
+            panic_with_error!(&e, LiquidityPoolValidationError::OutMinNotSatisfied);
+        }
+
+        // Accumulate oracle prices with the reserves before the deposit
+        update_price_oracle(&e);
+
+        // part of the swap fee goes to the protocol and is excluded from the reserves
+        if out_idx == 0 {
+            set_protocol_fee_a(&e, &(get_protocol_fee_a(&e) + deposit.protocol_fee));
+        } else {
+            set_protocol_fee_b(&e, &(get_protocol_fee_b(&e) + deposit.protocol_fee));
+        }
+        mint_shares(&e, &user, shares_to_mint as i128);
+        put_reserve_a(&e, swapped_reserve_a + deposit.amounts.0);
+        put_reserve_b(&e, swapped_reserve_b + deposit.amounts.1);
+
+        // return back the rounding leftovers which don't fit the pool ratio
+        let amounts = Vec::from_array(&e, [deposit.amounts.0, deposit.amounts.1]);
+        let in_leftover = amount - deposit.swap_in - amounts.get(token_idx).unwrap();
+        let out_leftover = deposit.swap_out - amounts.get(out_idx).unwrap();
+        if in_leftover > 0 {
+            token_in_client.transfer(&e.current_contract_address(), &user, &(in_leftover as i128));
+        }
+        if out_leftover > 0 {
+            token_out_client.transfer(
+                &e.current_contract_address(),
+                &user,
+                &(out_leftover as i128),
+            );
+        }
+
+        // Checkpoint resulting working balance
+        rewards.manager().update_working_balance(
+            &user,
+            new_total_shares,
+            user_shares + shares_to_mint,
+        );
+
+        // update plane data for every pool update
+        update_plane(&e);
+
+        let pool_events = PoolEvents::new(&e);
+        pool_events.trade(
+            user,
+            tokens.get(token_idx).unwrap(),
+            tokens.get(out_idx).unwrap(),
+            deposit.swap_in,
+            deposit.swap_out,
+            deposit.swap_fee,
+        );
+        pool_events.deposit_liquidity(tokens, amounts, shares_to_mint);
+
+        shares_to_mint
+    }
+
+    // Estimates the result of a single token deposit.
+    //
+    // # Arguments
+    //
+    // * `token_idx` - The index of the token to deposit.
+    // * `amount` - The amount of the token to deposit.
+    //
+    // # Returns
+    //
+    // The estimated amount of pool tokens to be minted.
+    fn estimate_deposit_single(e: Env, token_idx: u32, amount: u128) -> u128 {
+        if token_idx > 1 {
+            panic_with_error!(&e, LiquidityPoolValidationError::InTokenOutOfBounds);
+        }
+
+        if amount == 0 {
+            panic_with_error!(e, LiquidityPoolValidationError::ZeroAmount);
+        }
+
+        let (reserve_a, reserve_b) = (get_reserve_a(&e), get_reserve_b(&e));
+        let deposit = pool::get_deposit_single_amounts(&e, token_idx, amount, reserve_a, reserve_b);
+        let (swapped_reserve_a, swapped_reserve_b) = deposit.reserves;
+        let total_shares = get_total_shares(&e);
+        pool::get_new_total_shares(
+            &e,
+            swapped_reserve_a,
+            swapped_reserve_b,
+            swapped_reserve_a + deposit.amounts.0,
+            swapped_reserve_b + deposit.amounts.1,
+            total_shares,
+        ) - total_shares
+    }
+
     // Swaps tokens in the pool.
     //
     // # Arguments
@@ -364,6 +552,7 @@ impl LiquidityPoolTrait for LiquidityPool {
         in_amount: u128,
         out_min: u128,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
         if get_is_killed_swap(&e) {
@@ -408,6 +597,9 @@ impl LiquidityPoolTrait for LiquidityPool {
         let sell_token_client = SorobanTokenClient::new(&e, &sell_token);
         sell_token_client.transfer(&user, &e.current_contract_address(), &(in_amount as i128));
 
+        // Accumulate oracle prices with the reserves before the swap
+        update_price_oracle(&e);
+
         if in_idx == 0 {
             put_reserve_a(&e, reserve_a + in_amount);
         } else {
@@ -448,12 +640,16 @@ impl LiquidityPoolTrait for LiquidityPool {
             panic_with_error!(&e, LiquidityPoolError::InvariantDoesNotHold);
         }
 
+        // part of the fee goes to the protocol and is excluded from the reserves
+        let protocol_fee = get_protocol_fee(&e, fee);
         if out_idx == 0 {
             transfer_a(&e, &user, out_a);
-            put_reserve_a(&e, reserve_a - out);
+            put_reserve_a(&e, reserve_a - out - protocol_fee);
+            set_protocol_fee_a(&e, &(get_protocol_fee_a(&e) + protocol_fee));
         } else {
             transfer_b(&e, &user, out_b);
-            put_reserve_b(&e, reserve_b - out);
+            put_reserve_b(&e, reserve_b - out - protocol_fee);
+            set_protocol_fee_b(&e, &(get_protocol_fee_b(&e) + protocol_fee));
         }
 
         // update plane data for every pool update
@@ -483,6 +679,22 @@ impl LiquidityPoolTrait for LiquidityPool {
     //
     // The estimated amount of the output token that would be received.
     fn estimate_swap(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> u128 {
+        Self::estimate_swap_with_fee(e, in_idx, out_idx, in_amount).0
+    }
+
+    // Estimates the result of a swap operation along with the fee charged by the pool.
+    //
+    // # Arguments
+    //
+    // * `in_idx` - The index of the input token to be swapped.
+    // * `out_idx` - The index of the output token to be received.
+    // * `in_amount` - The amount of the input token to be swapped.
+    //
+    // # Returns
+    //
+    // The estimated amount of the output token that would be received
+    //  and the fee charged in the output token.
+    fn estimate_swap_with_fee(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> (u128, u128) {
         if in_idx == out_idx {
             panic_with_error!(&e, LiquidityPoolValidationError::CannotSwapSameToken);
         }
@@ -501,7 +713,7 @@ impl LiquidityPoolTrait for LiquidityPool {
         let reserve_sell = reserves.get(in_idx).unwrap();
         let reserve_buy = reserves.get(out_idx).unwrap();
 
-        get_amount_out(&e, in_amount, reserve_sell, reserve_buy).0
+        get_amount_out(&e, in_amount, reserve_sell, reserve_buy)
     }
 
     // Swaps tokens in the pool.
@@ -526,6 +738,7 @@ impl LiquidityPoolTrait for LiquidityPool {
         out_amount: u128,
         in_max: u128,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
         if get_is_killed_swap(&e) {
@@ -578,6 +791,9 @@ impl LiquidityPoolTrait for LiquidityPool {
             &((in_max - in_amount) as i128),
         );
 
+        // Accumulate oracle prices with the reserves before the swap
+        update_price_oracle(&e);
+
         if in_idx == 0 {
             put_reserve_a(&e, reserve_a + in_amount);
         } else {
@@ -622,12 +838,16 @@ impl LiquidityPoolTrait for LiquidityPool {
             panic_with_error!(&e, LiquidityPoolError::InvariantDoesNotHold);
         }
 
+        // part of the fee goes to the protocol and is excluded from the reserves
+        let protocol_fee = get_protocol_fee(&e, fee);
         if out_idx == 0 {
             transfer_a(&e, &user, out_a);
-            put_reserve_a(&e, reserve_a - out_amount);
+            put_reserve_a(&e, reserve_a - out_amount - protocol_fee);
+            set_protocol_fee_a(&e, &(get_protocol_fee_a(&e) + protocol_fee));
         } else {
             transfer_b(&e, &user, out_b);
-            put_reserve_b(&e, reserve_b - out_amount);
+            put_reserve_b(&e, reserve_b - out_amount - protocol_fee);
+            set_protocol_fee_b(&e, &(get_protocol_fee_b(&e) + protocol_fee));
         }
 
         // update plane data for every pool update
@@ -657,6 +877,28 @@ impl LiquidityPoolTrait for LiquidityPool {
     //
     // The estimated amount of the output token that would be received.
     fn estimate_swap_strict_receive(e: Env, in_idx: u32, out_idx: u32, out_amount: u128) -> u128 {
+        Self::estimate_swap_strict_receive_with_fee(e, in_idx, out_idx, out_amount).0
+    }
+
+    // Estimate amount of coins to retrieve using swap_strict_receive function
+    //  along with the fee charged by the pool.
+    //
+    // # Arguments
+    //
+    // * `in_idx` - The index of the input token to be swapped.
+    // * `out_idx` - The index of the output token to be received.
+    // * `out_amount` - The amount of the output token to be received.
+    //
+    // # Returns
+    //
+    // The estimated amount of the input token that would be sent
+    //  and the fee charged in the output token.
+    fn estimate_swap_strict_receive_with_fee(
+        e: Env,
+        in_idx: u32,
+        out_idx: u32,
+        out_amount: u128,
+    ) -> (u128, u128) {
         if in_idx == out_idx {
             panic_with_error!(&e, LiquidityPoolValidationError::CannotSwapSameToken);
         }
@@ -675,7 +917,7 @@ impl LiquidityPoolTrait for LiquidityPool {
         let reserve_sell = reserves.get(in_idx).unwrap();
         let reserve_buy = reserves.get(out_idx).unwrap();
 
-        get_amount_out_strict_receive(&e, out_amount, reserve_sell, reserve_buy).0
+        get_amount_out_strict_receive(&e, out_amount, reserve_sell, reserve_buy)
     }
 
     // Withdraws tokens from the pool.
@@ -690,6 +932,7 @@ impl LiquidityPoolTrait for LiquidityPool {
     //
     // A vector of actual amounts of each token withdrawn.
     fn withdraw(e: Env, user: Address, share_amount: u128, min_amounts: Vec<u128>) -> Vec<u128> {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
         if min_amounts.len() != 2 {
@@ -719,6 +962,9 @@ impl LiquidityPoolTrait for LiquidityPool {
             panic_with_error!(&e, LiquidityPoolValidationError::OutMinNotSatisfied);
         }
 
+        // Accumulate oracle prices with the reserves before the withdrawal
+        update_price_oracle(&e);
+
         transfer_a(&e, &user, out_a);
         transfer_b(&e, &user, out_b);
         put_reserve_a(&e, reserve_a - out_a);
@@ -744,6 +990,145 @@ impl LiquidityPoolTrait for LiquidityPool {
         withdraw_amounts
     }
 
+    // Calculates the amount received when withdrawing a single token.
+    //
+    // # Arguments
+    //
+    // * `share_amount` - The amount of pool tokens to burn.
+    // * `i` - The index of the token to withdraw.
+    //
+    // # Returns
+    //
+    // The amount of the token to be withdrawn.
+    fn calc_withdraw_one_coin(e: Env, share_amount: u128, i: u32) -> u128 {
+        if i > 1 {
+            panic_with_error!(&e, LiquidityPoolValidationError::OutTokenOutOfBounds);
+        }
+
+        let withdrawal = pool::get_withdraw_one_coin_amounts(
+            &e,
+            i,
+            share_amount,
+            get_total_shares(&e),
+            get_reserve_a(&e),
+            get_reserve_b(&e),
+        );
+        let (out_a, out_b) = withdrawal.amounts;
+        match i {
+            0 => out_a + withdrawal.swap_out,
+            _ => out_b + withdrawal.swap_out,
+        }
+    }
+
+    // Withdraws a single token from the pool.
+    // Counter token part of the withdrawal is swapped internally, charging the swap fee.
+    // Whole pool supply can't be withdrawn this way as nothing is left to swap against,
+    // such withdrawal fails with EmptyPool and regular `withdraw` should be used instead.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user withdrawing the token.
+    // * `share_amount` - The amount of pool tokens to burn.
+    // * `i` - The index of the token to withdraw.
+    // * `min_amount` - The minimum amount of the token to be received.
+    //
+    // # Returns
+    //
+    // A vector of amounts of each token withdrawn.
+    fn withdraw_one_coin(
+        e: Env,
+        user: Address,
+        share_amount: u128,
+        i: u32,
+        min_amount: u128,
+    ) -> Vec<u128> {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+
+        if get_is_killed_swap(&e) {
+            panic_with_error!(e, LiquidityPoolError::PoolSwapKilled);
+        }
+
+        if i > 1 {
+            panic_with_error!(&e, LiquidityPoolValidationError::OutTokenOutOfBounds);
+        }
+
+        // Before actual changes were made to the pool, update total rewards data and refresh user reward
+        let rewards = get_rewards_manager(&e);
+        let total_shares = get_total_shares(&e);
+        let user_shares = get_user_balance_shares(&e, &user);
+        rewards
+            .manager()
+            .checkpoint_user(&user, total_shares, user_shares);
+
+        let (reserve_a, reserve_b) = (get_reserve_a(&e), get_reserve_b(&e));
+        let withdrawal = pool::get_withdraw_one_coin_amounts(
+            &e,
+            i,
+            share_amount,
+            total_shares,
+            reserve_a,
+            reserve_b,
+        );
+        let (out_a, out_b) = withdrawal.amounts;
+        let out = match i {
+            0 => out_a + withdrawal.swap_out,
+            _ => out_b + withdrawal.swap_out,
+        };
+        if out < min_amount {
+            panic_with_error!(&e, LiquidityPoolValidationError::OutMinNotSatisfied);
+        }
+
+        burn_shares(&e, &user, share_amount);
+
+        // Accumulate oracle prices with the reserves before the withdrawal
+        update_price_oracle(&e);
+
+        // counter token stays in the pool, part of the swap fee goes to the protocol
+        let protocol_fee = withdrawal.protocol_fee;
+        if i == 0 {
+            transfer_a(&e, &user, out);
+            put_reserve_a(&e, reserve_a - out - protocol_fee);
+            set_protocol_fee_a(&e, &(get_protocol_fee_a(&e) + protocol_fee));
+        } else {
+            transfer_b(&e, &user, out);
+            put_reserve_b(&e, reserve_b - out - protocol_fee);
+            set_protocol_fee_b(&e, &(get_protocol_fee_b(&e) + protocol_fee));
+        }
+
+        // Checkpoint resulting working balance
+        rewards.manager().update_working_balance(
+            &user,
+            total_shares - share_amount,
+            user_shares - share_amount,
+        );
+
+        // update plane data for every pool update
+        update_plane(&e);
+
+        let tokens = Self::get_tokens(e.clone());
+        let pool_events = PoolEvents::new(&e);
+        let (sell_amount, out_idx) = match i {
+            0 => (out_b, 1),
+            _ => (out_a, 0),
+        };
+        pool_events.trade(
+            user,
+            tokens.get(out_idx).unwrap(),
+            tokens.get(i).unwrap(),
+            sell_amount,
+            withdrawal.swap_out,
+            withdrawal.swap_fee,
+        );
+        let amounts = match i {
+            0 => Vec::from_array(&e, [out, 0]),
+            _ => Vec::from_array(&e, [0, out]),
+        };
+        pool_events.withdraw_liquidity(tokens, amounts.clone(), share_amount);
+
+        amounts
+    }
+
     // Returns the pool's reserves.
     //
     // # Returns
@@ -778,6 +1163,132 @@ impl LiquidityPoolTrait for LiquidityPool {
     }
 }
 
+#[contractimpl]
+impl PriceOracleTrait for LiquidityPool {
+    // Returns the time weighted average price of the token in units of the other pool token.
+    //
+    // # Arguments
+    //
+    // * `token_in` - The address of the token to be priced.
+    // * `window_seconds` - The minimal period to average the price over.
+    //
+    // # Returns
+    //
+    // The average price scaled by 1e18.
+    fn consult(e: Env, token_in: Address, window_seconds: u64) -> U256 {
+        let in_idx = match Self::get_tokens(e.clone()).first_index_of(&token_in) {
+            Some(v) => v,
+            None => panic_with_error!(&e, LiquidityPoolValidationError::InTokenOutOfBounds),
+        };
+        oracle::consult(&e, in_idx, window_seconds)
+    }
+}
+
+#[contractimpl]
+impl FlashLoanTrait for LiquidityPool {
+    // Lends pool reserves to the receiver contract within a single transaction.
+    //
+    // # Arguments
+    //
+    // * `initiator` - The address of the user initiating the loan. Passed to the receiver callback.
+    // * `receiver` - The address of the contract to receive the loan and the callback.
+    // * `amounts` - A vector of amounts of each token to borrow.
+    // * `data` - Arbitrary data passed to the receiver callback.
+    //
+    // # Returns
+    //
+    // A vector of fees paid for each token.
+    fn flash_loan(
+        e: Env,
+        initiator: Address,
+        receiver: Address,
+        amounts: Vec<u128>,
+        data: Bytes,
+    ) -> Vec<u128> {
+        require_migrated(&e, Self::version());
+        initiator.require_auth();
+
+        if get_is_killed_swap(&e) {
+            panic_with_error!(e, LiquidityPoolError::PoolSwapKilled);
+        }
+
+        if amounts.len() != 2 {
+            panic_with_error!(&e, LiquidityPoolValidationError::WrongInputVecSize);
+        }
+
+        let (reserve_a, reserve_b) = (get_reserve_a(&e), get_reserve_b(&e));
+        let reserves = Vec::from_array(&e, [reserve_a, reserve_b]);
+        if amounts.iter().all(|amount| amount == 0) {
+            panic_with_error!(&e, LiquidityPoolValidationError::ZeroAmount);
+        }
+
+        let tokens = Self::get_tokens(e.clone());
+        let fee_fraction = get_fee_fraction(&e) as u128;
+        let mut fees = Vec::new(&e);
+        let mut balances_before = Vec::new(&e);
+        for i in 0..2 {
+            let amount = amounts.get(i).unwrap();
+            if amount > reserves.get(i).unwrap() {
+                panic_with_error!(&e, LiquidityPoolValidationError::InsufficientBalance);
+            }
+            fees.push_back(amount.fixed_mul_ceil(&e, &fee_fraction, &FEE_MULTIPLIER));
+
+            let token_client = SorobanTokenClient::new(&e, &tokens.get(i).unwrap());
+            balances_before.push_back(token_client.balance(&e.current_contract_address()) as u128);
+            if amount > 0 {
+                token_client.transfer(&e.current_contract_address(), &receiver, &(amount as i128));
+            }
+        }
+
+        e.invoke_contract::<()>(
+            &receiver,
+            &Symbol::new(&e, "on_flash_loan"),
+            Vec::from_array(
+                &e,
+                [
+                    initiator.into_val(&e),
+                    e.current_contract_address().into_val(&e),
+                    tokens.into_val(&e),
+                    amounts.into_val(&e),
+                    fees.into_val(&e),
+                    data.into_val(&e),
+                ],
+            ),
+        );
+
+        // everything returned above the borrowed amount is considered as a fee
+        let mut fees_paid = Vec::new(&e);
+        for i in 0..2 {
+            let token_client = SorobanTokenClient::new(&e, &tokens.get(i).unwrap());
+            let balance_after = token_client.balance(&e.current_contract_address()) as u128;
+            let balance_before = balances_before.get(i).unwrap();
+            if balance_after < balance_before + fees.get(i).unwrap() {
+                panic_with_error!(&e, LiquidityPoolError::FlashLoanNotRepaid);
+            }
+            fees_paid.push_back(balance_after - balance_before);
+        }
+
+        // Accumulate oracle prices with the reserves before the fee is added
+        update_price_oracle(&e);
+
+        // fee goes to the LPs, except the protocol share of it
+        let (fee_a, fee_b) = (fees_paid.get(0).unwrap(), fees_paid.get(1).unwrap());
+        let (protocol_fee_a, protocol_fee_b) =
+            (get_protocol_fee(&e, fee_a), get_protocol_fee(&e, fee_b));
+        put_reserve_a(&e, reserve_a + fee_a - protocol_fee_a);
+        put_reserve_b(&e, reserve_b + fee_b - protocol_fee_b);
+        set_protocol_fee_a(&e, &(get_protocol_fee_a(&e) + protocol_fee_a));
+        set_protocol_fee_b(&e, &(get_protocol_fee_b(&e) + protocol_fee_b));
+
+        // update plane data for every pool update
+        update_plane(&e);
+
+        Events::new(&e).flash_loan(initiator, receiver, amounts, fees_paid.clone());
+
+        fees_paid
+    }
+}
+
 #[contractimpl]
 impl AdminInterfaceTrait for LiquidityPool {
     // Sets the privileged addresses.
@@ -939,6 +1450,144 @@ impl AdminInterfaceTrait for LiquidityPool {
     }
 }
 
+// The `ProtocolFeeTrait` trait provides the interface for the protocol share of the swap fee.
+#[contractimpl]
+impl ProtocolFeeTrait for LiquidityPool {
+    // Commits a new protocol fee fraction to be applied after the admin actions delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `new_fraction` - The new protocol share of the swap fee. 1 = 0.01%
+    fn commit_protocol_fee(e: Env, admin: Address, new_fraction: u32) {
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        if get_admin_actions_deadline(&e) != 0 {
+            panic_with_error!(&e, LiquidityPoolError::AnotherActionActive);
+        }
+        if new_fraction as u128 > FEE_MULTIPLIER {
+            panic_with_error!(e, LiquidityPoolValidationError::FeeOutOfBounds);
+        }
+
+        let deadline =
+            e.ledger().timestamp() + AccessControl::new(&e).get_delay(&Delay::AdminActions);
+        set_admin_actions_deadline(&e, &deadline);
+        set_future_protocol_fee_fraction(&e, &new_fraction);
+
+        Events::new(&e).commit_protocol_fee(new_fraction);
+    }
+
+    // Applies the committed protocol fee fraction.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn apply_protocol_fee(e: Env, admin: Address) {
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        if e.ledger().timestamp() < get_admin_actions_deadline(&e) {
+            panic_with_error!(&e, LiquidityPoolError::ActionNotReadyYet);
+        }
+        if get_admin_actions_deadline(&e) == 0 {
+            panic_with_error!(&e, LiquidityPoolError::NoActionActive);
+        }
+
+        set_admin_actions_deadline(&e, &0);
+        let fraction = get_future_protocol_fee_fraction(&e);
+        set_protocol_fee_fraction(&e, &fraction);
+
+        Events::new(&e).apply_protocol_fee(fraction);
+    }
+
+    // Reverts the committed protocol fee fraction.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn revert_protocol_fee(e: Env, admin: Address) {
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        set_admin_actions_deadline(&e, &0);
+
+        Events::new(&e).revert_protocol_fee();
+    }
+
+    // Returns the protocol share of the swap fee. 1 = 0.01%
+    fn get_protocol_fee_fraction(e: Env) -> u32 {
+        get_protocol_fee_fraction(&e)
+    }
+
+    // Returns the committed protocol share of the swap fee.
+    fn get_future_protocol_fee_fraction(e: Env) -> u32 {
+        get_future_protocol_fee_fraction(&e)
+    }
+
+    // Returns the deadline of the committed admin action. 0 if there is no active action.
+    fn get_admin_actions_deadline(e: Env) -> u64 {
+        get_admin_actions_deadline(&e)
+    }
+
+    // Sets the address which receives claimed protocol fees.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `destination` - The address of the fee destination.
+    fn set_protocol_fee_destination(e: Env, admin: Address, destination: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        set_fee_destination(&e, &destination);
+
+        Events::new(&e).set_protocol_fee_destination(destination);
+    }
+
+    // Returns the address which receives claimed protocol fees.
+    fn get_protocol_fee_destination(e: Env) -> Address {
+        get_fee_destination(&e)
+    }
+
+    // Returns the protocol fees accrued but not claimed yet.
+    fn get_protocol_fees(e: Env) -> Vec<u128> {
+        Vec::from_array(&e, [get_protocol_fee_a(&e), get_protocol_fee_b(&e)])
+    }
+
+    // Sends accrued protocol fees to the fee destination.
+    //
+    // # Returns
+    //
+    // The amounts of each token sent.
+    fn claim_protocol_fees(e: Env) -> Vec<u128> {
+        require_migrated(&e, Self::version());
+        let destination = get_fee_destination(&e);
+        let fees = Self::get_protocol_fees(e.clone());
+        let tokens = Self::get_tokens(e.clone());
+
+        set_protocol_fee_a(&e, &0);
+        set_protocol_fee_b(&e, &0);
+
+        for i in 0..fees.len() {
+            let amount = fees.get(i).unwrap();
+            if amount == 0 {
+                continue;
+            }
+
+            SorobanTokenClient::new(&e, &tokens.get(i).unwrap()).transfer(
+                &e.current_contract_address(),
+                &destination,
+                &(amount as i128),
+            );
+        }
+
+        Events::new(&e).claim_protocol_fees(destination, fees.clone());
+
+        fees
+    }
+}
+
 // The `UpgradeableContract` trait provides the interface for upgrading the contract.
 #[contractimpl]
 impl UpgradeableContract for LiquidityPool {
@@ -957,7 +1606,7 @@ impl UpgradeableContract for LiquidityPool {
     //
     // # Arguments
     //
-    // * `admin` - The address of the admin.
+    // * `admin` - The address of the admin or the router.
     // * `new_wasm_hash` - The new wasm hash to commit.
     // * `new_token_wasm_hash` - The new token wasm hash to commit.
     fn commit_upgrade(
@@ -967,8 +1616,18 @@ impl UpgradeableContract for LiquidityPool {
         token_new_wasm_hash: BytesN<32>,
     ) {
         admin.require_auth();
-        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        commit_upgrade(&e, &new_wasm_hash);
+        let router = get_router(&e);
+        if admin == router {
+            // pool is not able to query the router allowlist here: the router is the caller
+            // and contract re-entry is not allowed. Router checks both hashes against its allowlist
+            // right before the call and once again before applying the upgrade
+            commit_checked_upgrade(&e, &new_wasm_hash);
+        } else {
+            AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+            // pools share the audited wasm hashes allowlist of the router
+            commit_upgrade_allowed_by(&e, &router, &WasmKind::StandardPool, &new_wasm_hash);
+            require_wasm_allowed_by(&e, &router, &WasmKind::Token, &token_new_wasm_hash);
+        }
         // handle token upgrade manually together with pool upgrade
         set_token_future_wasm(&e, &token_new_wasm_hash);
 
@@ -986,7 +1645,7 @@ impl UpgradeableContract for LiquidityPool {
     fn apply_upgrade(e: Env, admin: Address) -> (BytesN<32>, BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        let new_wasm_hash = apply_upgrade(&e);
+        let new_wasm_hash = apply_upgrade(&e, &admin);
         let token_new_wasm_hash = get_token_future_wasm(&e);
         token_share::Client::new(&e, &get_token_share(&e))
             .upgrade(&e.current_contract_address(), &token_new_wasm_hash);
@@ -1014,8 +1673,55 @@ impl UpgradeableContract for LiquidityPool {
         UpgradeEvents::new(&e).revert_upgrade();
     }
 
+    // Rolls back the contract to the previously running wasm.
+    // The rollback is applied immediately as it's intended to fix the faulty release.
+    // The previous wasm is known only for the upgrades applied through `apply_upgrade`.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    //
+    // # Returns
+    //
+    // The wasm hash the contract has been rolled back to.
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32> {
+        emergency_admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
+        let wasm_hash = rollback_upgrade(&e, &emergency_admin);
+        UpgradeEvents::new(&e).rollback_upgrade(wasm_hash.clone());
+        wasm_hash
+    }
+
+    // Returns the latest applied upgrades and rollbacks.
+    //
+    // # Returns
+    //
+    // The list of records with wasm hash, timestamp and the admin applied it.
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord> {
+        get_upgrade_history(&e)
+    }
+
+    // Migrates the storage to the current contract version.
+    // Should be called once after every upgrade, state-changing methods are not available until then.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn migrate(e: Env, admin: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        let from_version = migrate(&e, Self::version());
+        UpgradeEvents::new(&e).migrate(from_version, Self::version());
+    }
+
+    // Returns the version of the storage schema.
+    fn get_storage_version(e: Env) -> u32 {
+        get_storage_version(&e)
+    }
+
     // Sets the emergency mode.
     // When the emergency mode is set to true, the contract will allow instant upgrades without the delay.
+    // Emergency mode enabled this way unlocks all emergency powers and expires after 7 days.
     // This is useful in case of critical issues that need to be fixed immediately.
     // When the emergency mode is set to false, the contract will require the standard upgrade delay.
     // The emergency mode can only be set by the emergency admin.
@@ -1025,10 +1731,7 @@ impl UpgradeableContract for LiquidityPool {
     // * `emergency_admin` - The address of the emergency admin.
     // * `value` - The value to set the emergency mode to.
     fn set_emergency_mode(e: Env, emergency_admin: Address, value: bool) {
-        emergency_admin.require_auth();
-        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
-        set_emergency_mode(&e, &value);
-        AccessControlEvents::new(&e).set_emergency_mode(value);
+        toggle_emergency_mode(&e, &emergency_admin, value);
     }
 
     // Returns the emergency mode flag value.
@@ -1137,7 +1840,9 @@ impl RewardsTrait for LiquidityPool {
         match Self::get_tokens(e.clone()).first_index_of(reward_token) {
             Some(idx) => {
                 // since reward token is in the reserves, we need to keep also the reserves value
+                // and protocol fees accrued but not yet claimed
                 reward_balance_to_keep += Self::get_reserves(e.clone()).get(idx).unwrap();
+                reward_balance_to_keep += Self::get_protocol_fees(e.clone()).get(idx).unwrap();
             }
             None => {}
         };
@@ -1350,6 +2055,7 @@ impl RewardsTrait for LiquidityPool {
     //
     // The amount of tokens rewarded to the user as a u128.
     fn claim(e: Env, user: Address) -> u128 {
+        require_migrated(&e, Self::version());
         if get_is_killed_claim(&e) {
             panic_with_error!(e, LiquidityPoolError::PoolClaimKilled);
         }
@@ -1365,6 +2071,7 @@ impl RewardsTrait for LiquidityPool {
         let tokens = Self::get_tokens(e.clone());
         let reward_token = rewards_storage.get_reward_token();
         let reserves = Self::get_reserves(e.clone());
+        let protocol_fees = Self::get_protocol_fees(e.clone());
 
         for i in 0..reserves.len() {
             let token = tokens.get(i).unwrap();
@@ -1374,7 +2081,7 @@ impl RewardsTrait for LiquidityPool {
 
             let balance = SorobanTokenClient::new(&e, &tokens.get(i).unwrap())
                 .balance(&e.current_contract_address()) as u128;
-            if reserves.get(i).unwrap() > balance {
+            if reserves.get(i).unwrap() + protocol_fees.get(i).unwrap() > balance {
                 panic_with_error!(&e, LiquidityPoolValidationError::InsufficientBalance);
             }
         }
@@ -1511,3 +2218,234 @@ impl TransferableContract for LiquidityPool {
         }
     }
 }
+
+// The `ConfigurableDelaysContract` trait provides the interface for configuring admin actions delays.
+#[contractimpl]
+impl ConfigurableDelaysContract for LiquidityPool {
+    // Commits new value for the delay: `AdminActions`, `Upgrade` or `WasmAllowlist`.
+    // The value is applied after the current delay passes.
+    fn commit_delay(e: Env, admin: Address, delay_name: Symbol, value: u64) {
+        commit_delay(&e, &admin, delay_name, value)
+    }
+
+    // Applies the committed delay value.
+    fn apply_delay(e: Env, admin: Address, delay_name: Symbol) {
+        apply_delay(&e, &admin, delay_name)
+    }
+
+    // Reverts the committed delay value.
+    fn revert_delay(e: Env, admin: Address, delay_name: Symbol) {
+        revert_delay(&e, &admin, delay_name)
+    }
+
+    // Returns the current delay value in seconds.
+    fn get_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_delay(&e, delay_name)
+    }
+
+    // Returns the committed delay value in seconds. Defaults to the current one if not committed.
+    fn get_future_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_future_delay(&e, delay_name)
+    }
+}
+
+// The `AdminSignersContract` trait provides M-of-N authorization for the admin role.
+#[contractimpl]
+impl AdminSignersContract for LiquidityPool {
+    // Commits the signers set backing the admin role. Empty set switches back to the single admin.
+    fn commit_admin_signers(e: Env, admin: Address, signers: Vec<Address>, threshold: u32) {
+        commit_admin_signers(&e, &admin, &signers, threshold)
+    }
+
+    // Applies the committed admin signers set once the admin transfer delay passes.
+    fn apply_admin_signers(e: Env, admin: Address) {
+        apply_admin_signers(&e, &admin)
+    }
+
+    // Reverts the committed admin signers set.
+    fn revert_admin_signers(e: Env, admin: Address) {
+        revert_admin_signers(&e, &admin)
+    }
+
+    // Returns the admin signers set and threshold. Empty set if not configured.
+    fn get_admin_signers(e: Env) -> (Vec<Address>, u32) {
+        get_admin_signers(&e)
+    }
+
+    // Returns the committed admin signers set and threshold if the change is pending.
+    fn get_future_admin_signers(e: Env) -> Option<(Vec<Address>, u32)> {
+        get_future_admin_signers(&e)
+    }
+
+    // Nominates the admin signers authorizing the admin actions of the signer together with it.
+    fn set_admin_cosigners(e: Env, signer: Address, cosigners: Vec<Address>) {
+        set_admin_cosigners(&e, &signer, &cosigners)
+    }
+
+    // Returns the admin signers nominated by the signer.
+    fn get_admin_cosigners(e: Env, signer: Address) -> Vec<Address> {
+        get_admin_cosigners(&e, &signer)
+    }
+}
+
+// The `RoleTimelockContract` trait provides delayed changes for the rest of the roles.
+#[contractimpl]
+impl RoleTimelockContract for LiquidityPool {
+    // Commits delay between commit and apply of the role transfer or members change.
+    // The value is applied after the current delay of the role passes.
+    // Once delay is set, role can be changed only through the commit-apply process.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    // * `delay` - Delay in seconds. Cannot be less than the minimum delay for the roles
+    //   transferred with mandatory delay like `Admin` and `EmergencyAdmin`.
+    fn commit_role_transfer_delay(e: Env, admin: Address, role_name: Symbol, delay: u64) {
+        commit_role_transfer_delay(&e, &admin, role_name, delay);
+    }
+
+    // Applies the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn apply_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        apply_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Reverts the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn revert_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Returns delay between commit and apply of the role transfer or members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_role_transfer_delay(&e, role_name)
+    }
+
+    // Returns the committed role transfer delay.
+    // The value will be defaulted to the current one if the change is not committed.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_future_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_future_role_transfer_delay(&e, role_name)
+    }
+
+    // Commits address addition to the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be added.
+    fn commit_add_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, true);
+    }
+
+    // Commits address removal from the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be removed.
+    fn commit_remove_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, false);
+    }
+
+    // Applies the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The new list of the role members.
+    fn apply_role_members_change(e: Env, admin: Address, role_name: Symbol) -> Vec<Address> {
+        apply_role_members_change(&e, &admin, role_name)
+    }
+
+    // Reverts the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    fn revert_role_members_change(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_members_change(&e, &admin, role_name);
+    }
+
+    // Returns the committed members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The (address, is_added) pair if change is committed.
+    fn get_pending_role_members_change(e: Env, role_name: Symbol) -> Option<(Address, bool)> {
+        get_pending_role_members_change(&e, role_name)
+    }
+}
+
+// The `AdminStateContract` trait provides the view of the roles and pending admin actions.
+#[contractimpl]
+impl AdminStateContract for LiquidityPool {
+    // Returns the roles state along with the delays, pending admin actions and emergency mode.
+    //
+    // # Returns
+    //
+    // The list of roles with current holders, pending transfers, signers and their deadlines,
+    // the configurable delays, the pending wasm hash with the upgrade deadline,
+    // the pending allowlist changes and contract actions and the emergency mode state.
+    fn get_admin_state(e: Env) -> AdminState {
+        get_admin_state(&e, &[("ProtocolFee", get_admin_actions_deadline(&e))])
+    }
+}
+
+// The `EmergencyModeContract` trait provides the scoped emergency mode with automatic expiration.
+#[contractimpl]
+impl EmergencyModeContract for LiquidityPool {
+    // Enables the emergency mode for the limited time.
+    // Scope defines what is unlocked, currently the instant upgrade only.
+    // The emergency mode is disabled automatically once expired.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    // * `scope` - The powers unlocked by the emergency mode.
+    // * `duration` - The emergency mode duration in seconds.
+    fn enable_emergency_mode(
+        e: Env,
+        emergency_admin: Address,
+        scope: EmergencyScope,
+        duration: u64,
+    ) {
+        enable_scoped_emergency_mode(&e, &emergency_admin, &scope, duration);
+    }
+
+    // Returns the emergency mode state.
+    //
+    // # Returns
+    //
+    // The emergency mode flag, its scope, expiration timestamp and the remaining time in seconds.
+    fn get_emergency_mode_state(e: Env) -> EmergencyModeState {
+        get_emergency_mode_state(&e)
+    }
+}
@@ -12,5 +12,14 @@ pub enum LiquidityPoolError {
     PoolSwapKilled = 206,
     PoolClaimKilled = 207,
     FutureShareIdNotSet = 208,
+    BadOracleWindow = 210,
+    OracleHistoryNotAvailable = 211,
+    FlashLoanNotRepaid = 212,
+
     // pool specific validation errors
+
+    // admin actions errors
+    AnotherActionActive = 2906,
+    NoActionActive = 2907,
+    ActionNotReadyYet = 2908,
 }
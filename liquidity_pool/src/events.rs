@@ -0,0 +1,63 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+#[derive(Clone)]
+pub struct Events(Env);
+
+impl Events {
+    #[inline(always)]
+    pub fn env(&self) -> &Env {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub fn new(env: &Env) -> Events {
+        Events(env.clone())
+    }
+
+    pub fn commit_protocol_fee(&self, new_fraction: u32) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "commit_protocol_fee"),),
+            (new_fraction,),
+        )
+    }
+
+    pub fn apply_protocol_fee(&self, fraction: u32) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "apply_protocol_fee"),),
+            (fraction,),
+        )
+    }
+
+    pub fn revert_protocol_fee(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "revert_protocol_fee"),), ())
+    }
+
+    pub fn set_protocol_fee_destination(&self, destination: Address) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "set_protocol_fee_destination"),),
+            (destination,),
+        )
+    }
+
+    pub fn claim_protocol_fees(&self, destination: Address, amounts: Vec<u128>) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "claim_protocol_fees"), destination),
+            (amounts,),
+        )
+    }
+
+    pub fn flash_loan(
+        &self,
+        initiator: Address,
+        receiver: Address,
+        amounts: Vec<u128>,
+        fees: Vec<u128>,
+    ) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "flash_loan"), initiator, receiver),
+            (amounts, fees),
+        )
+    }
+}
@@ -3,6 +3,8 @@
 mod constants;
 mod contract;
 pub mod errors;
+mod events;
+mod oracle;
 mod plane;
 mod plane_interface;
 mod pool;
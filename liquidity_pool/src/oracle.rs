@@ -0,0 +1,120 @@
+use crate::constants::{OBSERVATIONS_LIMIT, OBSERVATION_PERIOD, PRICE_PRECISION};
+use crate::errors::LiquidityPoolError;
+use crate::storage::{
+    get_observation, get_observation_index, get_price_cumulative_a, get_price_cumulative_b,
+    get_price_last_updated, get_reserve_a, get_reserve_b, put_observation, set_observation_index,
+    set_price_cumulative_a, set_price_cumulative_b, set_price_last_updated, PriceObservation,
+};
+use soroban_sdk::{panic_with_error, Env, U256};
+
+// Price of reserve_in token in units of reserve_out token scaled by PRICE_PRECISION
+fn get_spot_price(e: &Env, reserve_in: u128, reserve_out: u128) -> U256 {
+    U256::from_u128(e, reserve_out)
+        .mul(&U256::from_u128(e, PRICE_PRECISION))
+        .div(&U256::from_u128(e, reserve_in))
+}
+
+// Cumulative prices as of the current ledger timestamp, without writing them into the storage
+pub fn get_current_cumulative_prices(e: &Env) -> (U256, U256) {
+    let mut price_a_cumulative = get_price_cumulative_a(e);
+    let mut price_b_cumulative = get_price_cumulative_b(e);
+
+    let last_updated = get_price_last_updated(e);
+    let now = e.ledger().timestamp();
+    let (reserve_a, reserve_b) = (get_reserve_a(e), get_reserve_b(e));
+
+    // nothing to accumulate before the oracle is initialized or while the pool is empty
+    if last_updated > 0 && now > last_updated && reserve_a > 0 && reserve_b > 0 {
+        let elapsed = U256::from_u128(e, (now - last_updated) as u128);
+        price_a_cumulative =
+            price_a_cumulative.add(&get_spot_price(e, reserve_a, reserve_b).mul(&elapsed));
+        price_b_cumulative =
+            price_b_cumulative.add(&get_spot_price(e, reserve_b, reserve_a).mul(&elapsed));
+    }
+
+    (price_a_cumulative, price_b_cumulative)
+}
+
+// Accumulate prices for the time passed since the last update.
+// Should be called before reserves are changed, so the elapsed period is priced with the old reserves.
+pub fn update_price_oracle(e: &Env) {
+    let now = e.ledger().timestamp();
+    if get_price_last_updated(e) == now {
+        return;
+    }
+
+    let (price_a_cumulative, price_b_cumulative) = get_current_cumulative_prices(e);
+    set_price_cumulative_a(e, &price_a_cumulative);
+    set_price_cumulative_b(e, &price_b_cumulative);
+    set_price_last_updated(e, &now);
+
+    // write snapshot into the ring buffer not more often than once per observation period
+    let next_index = get_observation_index(e);
+    let last_index = (next_index + OBSERVATIONS_LIMIT - 1) % OBSERVATIONS_LIMIT;
+    let is_due = match get_observation(e, last_index) {
+        Some(last) => now - last.timestamp >= OBSERVATION_PERIOD,
+        None => true,
+    };
+    if is_due {
+        put_observation(
+            e,
+            next_index,
+            &PriceObservation {
+                timestamp: now,
+                price_a_cumulative,
+                price_b_cumulative,
+            },
+        );
+        set_observation_index(e, &((next_index + 1) % OBSERVATIONS_LIMIT));
+    }
+}
+
+// Time weighted average price of token with in_idx in units of the other token scaled by PRICE_PRECISION.
+// Average is taken since the latest observation made at least window_seconds ago,
+// so the actual window may be longer than requested by up to the observation period.
+pub fn consult(e: &Env, in_idx: u32, window_seconds: u64) -> U256 {
+    if window_seconds == 0 {
+        panic_with_error!(e, LiquidityPoolError::BadOracleWindow);
+    }
+
+    let now = e.ledger().timestamp();
+    if window_seconds > now {
+        panic_with_error!(e, LiquidityPoolError::OracleHistoryNotAvailable);
+    }
+    let target = now - window_seconds;
+
+    // observations are sorted by time starting from the oldest one
+    let next_index = get_observation_index(e);
+    let (oldest_index, count) = match get_observation(e, next_index) {
+        Some(_) => (next_index, OBSERVATIONS_LIMIT),
+        None => (0, next_index),
+    };
+    let observation_at =
+        |position: u32| get_observation(e, (oldest_index + position) % OBSERVATIONS_LIMIT).unwrap();
+
+    if count == 0 || observation_at(0).timestamp > target {
+        panic_with_error!(e, LiquidityPoolError::OracleHistoryNotAvailable);
+    }
+
+    // binary search for the latest observation not newer than the target
+    let (mut low, mut high) = (0, count - 1);
+    while low < high {
+        let mid = (low + high + 1) / 2;
+        if observation_at(mid).timestamp <= target {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    let observation = observation_at(low);
+
+    let (price_a_cumulative, price_b_cumulative) = get_current_cumulative_prices(e);
+    let (current, past) = match in_idx {
+        0 => (price_a_cumulative, observation.price_a_cumulative),
+        _ => (price_b_cumulative, observation.price_b_cumulative),
+    };
+
+    current
+        .sub(&past)
+        .div(&U256::from_u128(e, (now - observation.timestamp) as u128))
+}
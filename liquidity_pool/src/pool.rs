@@ -1,8 +1,9 @@
 use crate::constants::FEE_MULTIPLIER;
-use crate::storage::get_fee_fraction;
+use crate::storage::{get_fee_fraction, get_protocol_fee_fraction};
 use liquidity_pool_validation_errors::LiquidityPoolValidationError;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{panic_with_error, Env};
+use soroban_sdk::{panic_with_error, Env, U256};
+use utils::u256_math::ExtraMath;
 
 pub fn get_deposit_amounts(
     e: &Env,
@@ -74,3 +75,163 @@ pub fn get_amount_out_strict_receive(
         + 1;
     (result, dy_w_fee - out_amount)
 }
+
+// part of the swap fee which goes to the fee destination
+pub fn get_protocol_fee(e: &Env, fee: u128) -> u128 {
+    fee.fixed_mul_floor(e, &(get_protocol_fee_fraction(e) as u128), &FEE_MULTIPLIER)
+}
+
+// total shares after the pool reserves were increased by the deposit
+pub fn get_new_total_shares(
+    e: &Env,
+    reserve_a: u128,
+    reserve_b: u128,
+    new_reserve_a: u128,
+    new_reserve_b: u128,
+    total_shares: u128,
+) -> u128 {
+    if reserve_a > 0 && reserve_b > 0 {
+        let shares_a = new_reserve_a.fixed_mul_floor(e, &total_shares, &reserve_a);
+        let shares_b = new_reserve_b.fixed_mul_floor(e, &total_shares, &reserve_b);
+        shares_a.min(shares_b)
+    } else {
+        // if .mul doesn't fail, sqrt also won't -> safe to unwrap
+        U256::from_u128(e, new_reserve_a)
+            .mul(&U256::from_u128(e, new_reserve_b))
+            .sqrt()
+            .to_u128()
+            .unwrap()
+    }
+}
+
+// Part of the single-sided deposit to be swapped, so the rest can be deposited in the pool ratio.
+// Positive root of s^2 + s * ((2 - f) * reserve_in - f * amount) - amount * reserve_in = 0,
+// where fee f is charged from the swap output
+pub fn get_deposit_single_swap_amount(e: &Env, amount: u128, reserve_in: u128) -> u128 {
+    let fee_fraction = get_fee_fraction(e) as u128;
+    let multiplier = U256::from_u128(e, FEE_MULTIPLIER);
+    let amount_u256 = U256::from_u128(e, amount);
+    let reserve_u256 = U256::from_u128(e, reserve_in);
+
+    // linear coefficient scaled by FEE_MULTIPLIER, sign is tracked separately
+    let b_pos = U256::from_u128(e, 2 * FEE_MULTIPLIER - fee_fraction).mul(&reserve_u256);
+    let b_neg = U256::from_u128(e, fee_fraction).mul(&amount_u256);
+    let (b_abs, b_is_negative) = match b_pos >= b_neg {
+        true => (b_pos.sub(&b_neg), false),
+        false => (b_neg.sub(&b_pos), true),
+    };
+
+    let discriminant_sqrt = b_abs
+        .mul(&b_abs)
+        .add(
+            &U256::from_u32(e, 4)
+                .mul(&multiplier)
+                .mul(&multiplier)
+                .mul(&amount_u256)
+                .mul(&reserve_u256),
+        )
+        .sqrt();
+    let numerator = match b_is_negative {
+        true => discriminant_sqrt.add(&b_abs),
+        false => discriminant_sqrt.sub(&b_abs),
+    };
+    let result = numerator
+        .div(&U256::from_u32(e, 2).mul(&multiplier))
+        .to_u128()
+        .unwrap();
+    result.min(amount)
+}
+
+pub struct DepositSingleAmounts {
+    // amount of the deposited token being swapped
+    pub swap_in: u128,
+    // amount received from the swap and the swap fee
+    pub swap_out: u128,
+    pub swap_fee: u128,
+    // part of the swap fee which goes to the fee destination
+    pub protocol_fee: u128,
+    // reserves after the swap
+    pub reserves: (u128, u128),
+    // amounts deposited against the reserves after the swap
+    pub amounts: (u128, u128),
+}
+
+// Split single-sided deposit into the swap and the deposit of both tokens in the pool ratio
+pub fn get_deposit_single_amounts(
+    e: &Env,
+    in_idx: u32,
+    amount: u128,
+    reserve_a: u128,
+    reserve_b: u128,
+) -> DepositSingleAmounts {
+    let (reserve_in, reserve_out) = match in_idx {
+        0 => (reserve_a, reserve_b),
+        _ => (reserve_b, reserve_a),
+    };
+    if reserve_in == 0 || reserve_out == 0 {
+        panic_with_error!(e, LiquidityPoolValidationError::EmptyPool);
+    }
+
+    let swap_in = get_deposit_single_swap_amount(e, amount, reserve_in);
+    let (swap_out, swap_fee) = get_amount_out(e, swap_in, reserve_in, reserve_out);
+    let protocol_fee = get_protocol_fee(e, swap_fee);
+
+    let reserves_in_out = (reserve_in + swap_in, reserve_out - swap_out - protocol_fee);
+    let (reserves, desired) = match in_idx {
+        0 => (reserves_in_out, (amount - swap_in, swap_out)),
+        _ => (
+            (reserves_in_out.1, reserves_in_out.0),
+            (swap_out, amount - swap_in),
+        ),
+    };
+    let amounts = get_deposit_amounts(e, desired.0, 0, desired.1, 0, reserves.0, reserves.1);
+
+    DepositSingleAmounts {
+        swap_in,
+        swap_out,
+        swap_fee,
+        protocol_fee,
+        reserves,
+        amounts,
+    }
+}
+
+pub struct WithdrawOneCoinAmounts {
+    // amounts withdrawn proportionally to the reserves
+    pub amounts: (u128, u128),
+    // amount received from the swap of the counter token and the swap fee
+    pub swap_out: u128,
+    pub swap_fee: u128,
+    // part of the swap fee which goes to the fee destination
+    pub protocol_fee: u128,
+}
+
+// Split single token withdrawal into the proportional withdrawal and the swap of the counter token
+pub fn get_withdraw_one_coin_amounts(
+    e: &Env,
+    out_idx: u32,
+    share_amount: u128,
+    total_shares: u128,
+    reserve_a: u128,
+    reserve_b: u128,
+) -> WithdrawOneCoinAmounts {
+    let out_a = reserve_a.fixed_mul_floor(e, &share_amount, &total_shares);
+    let out_b = reserve_b.fixed_mul_floor(e, &share_amount, &total_shares);
+
+    // counter token is sold to the pool remaining after the withdrawal
+    let (reserve_out, reserve_sell, sell_amount) = match out_idx {
+        0 => (reserve_a - out_a, reserve_b - out_b, out_b),
+        _ => (reserve_b - out_b, reserve_a - out_a, out_a),
+    };
+    if reserve_out == 0 || reserve_sell == 0 {
+        panic_with_error!(e, LiquidityPoolValidationError::EmptyPool);
+    }
+
+    let (swap_out, swap_fee) = get_amount_out(e, sell_amount, reserve_sell, reserve_out);
+    WithdrawOneCoinAmounts {
+        amounts: (out_a, out_b),
+        swap_out,
+        swap_fee,
+        protocol_fee: get_protocol_fee(e, swap_fee),
+    }
+}
@@ -1,4 +1,5 @@
-use soroban_sdk::{Address, BytesN, Env, Map, Symbol, Val, Vec};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Map, Symbol, Val, Vec, U256};
+use upgrade::history::UpgradeRecord;
 
 pub trait LiquidityPoolCrunch {
     // Initialize pool completely to reduce calculations cost
@@ -48,6 +49,23 @@ pub trait LiquidityPoolTrait {
         min_shares: u128,
     ) -> (Vec<u128>, u128);
 
+    // Deposits single token. Optimal part of it is swapped internally and the rest is deposited
+    // along with the swap result, so user doesn't need to hold both tokens.
+    // token_idx: Index value of the coin to deposit
+    // amount: Amount of token_idx to deposit
+    // min_shares: Minimum amount of pool shares to mint
+    // Returns amount of pool shares minted.
+    fn deposit_single(
+        e: Env,
+        user: Address,
+        token_idx: u32,
+        amount: u128,
+        min_shares: u128,
+    ) -> u128;
+
+    // Estimate amount of pool shares to mint using deposit_single function
+    fn estimate_deposit_single(e: Env, token_idx: u32, amount: u128) -> u128;
+
     // Perform an exchange between two coins.
     // in_idx: Index value for the coin to send
     // out_idx: Index value of the coin to receive
@@ -66,6 +84,9 @@ pub trait LiquidityPoolTrait {
     // Estimate amount of coins to retrieve using swap function
     fn estimate_swap(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> u128;
 
+    // Estimate amount of coins to retrieve using swap function and the fee charged in coin out_idx
+    fn estimate_swap_with_fee(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> (u128, u128);
+
     // Perform an exchange between two coins with strict amount to receive.
     // in_idx: Index value for the coin to send
     // out_idx: Index value of the coin to receive
@@ -83,12 +104,40 @@ pub trait LiquidityPoolTrait {
     // Estimate amount of coins to retrieve using swap_strict_receive function
     fn estimate_swap_strict_receive(e: Env, in_idx: u32, out_idx: u32, out_amount: u128) -> u128;
 
+    // Estimate amount of coins to send using swap_strict_receive function and the fee charged in coin out_idx
+    fn estimate_swap_strict_receive_with_fee(
+        e: Env,
+        in_idx: u32,
+        out_idx: u32,
+        out_amount: u128,
+    ) -> (u128, u128);
+
     // Transfers share_amount of pool share tokens to this contract,
     // burns all pools share tokens in this contracts, and sends
     // the corresponding amount of tokens to user.
     // Returns amount of tokens withdrawn
     fn withdraw(e: Env, user: Address, share_amount: u128, min_amounts: Vec<u128>) -> Vec<u128>;
 
+    // Calculate the amount received when withdrawing a single coin.
+    // share_amount: Amount of pool shares to burn in the withdrawal
+    // i: Index value of the coin to withdraw
+    fn calc_withdraw_one_coin(e: Env, share_amount: u128, i: u32) -> u128;
+
+    // Withdraw a single coin from the pool. Share of the other coin is swapped internally.
+    // Not available while swaps are killed. Whole pool supply can't be withdrawn this way,
+    // regular withdraw should be used for it.
+    // share_amount: Amount of pool shares to burn in the withdrawal
+    // i: Index value of the coin to withdraw
+    // min_amount: Minimum amount of coin to receive
+    // Returns amounts of tokens withdrawn.
+    fn withdraw_one_coin(
+        e: Env,
+        user: Address,
+        share_amount: u128,
+        i: u32,
+        min_amount: u128,
+    ) -> Vec<u128>;
+
     // Get pool reserves
     fn get_reserves(e: Env) -> Vec<u128>;
 
@@ -99,6 +148,28 @@ pub trait LiquidityPoolTrait {
     fn get_info(e: Env) -> Map<Symbol, Val>;
 }
 
+pub trait PriceOracleTrait {
+    // Time weighted average price of token_in in units of the other pool token, scaled by 1e18.
+    // Averaged over at least window_seconds, the window is rounded to the hourly observations.
+    fn consult(e: Env, token_in: Address, window_seconds: u64) -> U256;
+}
+
+pub trait FlashLoanTrait {
+    // Lend pool reserves within a single transaction.
+    // Receiver contract is called back with
+    // `on_flash_loan(initiator, pool, tokens, amounts, fees, data)` and has to transfer
+    // borrowed amounts plus fees back to the pool before the callback returns.
+    // Initiator authorizes the loan, so the receiver is able to reject loans started by others.
+    // Returns the fees paid for each token.
+    fn flash_loan(
+        e: Env,
+        initiator: Address,
+        receiver: Address,
+        amounts: Vec<u128>,
+        data: Bytes,
+    ) -> Vec<u128>;
+}
+
 pub trait AdminInterfaceTrait {
     // Set privileged addresses
     fn set_privileged_addrs(
@@ -129,6 +200,38 @@ pub trait AdminInterfaceTrait {
     fn get_is_killed_claim(e: Env) -> bool;
 }
 
+pub trait ProtocolFeeTrait {
+    // Commit new protocol share of the swap fee. 1 = 0.01%
+    fn commit_protocol_fee(e: Env, admin: Address, new_fraction: u32);
+
+    // Apply committed protocol fee fraction
+    fn apply_protocol_fee(e: Env, admin: Address);
+
+    // Revert committed protocol fee fraction
+    fn revert_protocol_fee(e: Env, admin: Address);
+
+    // Get protocol share of the swap fee. 1 = 0.01%
+    fn get_protocol_fee_fraction(e: Env) -> u32;
+
+    // Get protocol fee fraction to be applied in future
+    fn get_future_protocol_fee_fraction(e: Env) -> u32;
+
+    // Get deadline of the committed admin action
+    fn get_admin_actions_deadline(e: Env) -> u64;
+
+    // Set address to receive protocol fees
+    fn set_protocol_fee_destination(e: Env, admin: Address, destination: Address);
+
+    // Get address to receive protocol fees
+    fn get_protocol_fee_destination(e: Env) -> Address;
+
+    // Get accrued protocol fees for each token
+    fn get_protocol_fees(e: Env) -> Vec<u128>;
+
+    // Send accrued protocol fees to the fee destination
+    fn claim_protocol_fees(e: Env) -> Vec<u128>;
+}
+
 pub trait UpgradeableContract {
     // Get contract version
     fn version() -> u32;
@@ -143,6 +246,16 @@ pub trait UpgradeableContract {
     fn apply_upgrade(e: Env, admin: Address) -> (BytesN<32>, BytesN<32>);
     fn revert_upgrade(e: Env, admin: Address);
 
+    // Rollback to the previously running wasm without delay
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32>;
+
+    // Get latest applied upgrades
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord>;
+
+    // Migrate storage to the current contract version after the upgrade
+    fn migrate(e: Env, admin: Address);
+    fn get_storage_version(e: Env) -> u32;
+
     // Emergency mode - bypass upgrade deadline
     fn set_emergency_mode(e: Env, admin: Address, value: bool);
     fn get_emergency_mode(e: Env) -> bool;
@@ -1,8 +1,9 @@
 use paste::paste;
-use soroban_sdk::{contracttype, panic_with_error, Address, BytesN, Env};
-pub use utils::bump::bump_instance;
+use soroban_sdk::{contracttype, panic_with_error, Address, BytesN, Env, U256};
+pub use utils::bump::{bump_instance, bump_persistent};
 use utils::storage_errors::StorageError;
 use utils::{
+    generate_instance_storage_getter, generate_instance_storage_getter_and_setter,
     generate_instance_storage_getter_and_setter_with_default,
     generate_instance_storage_getter_with_default, generate_instance_storage_setter,
 };
@@ -22,6 +23,29 @@ enum DataKey {
     IsKilledClaim,
 
     TokenFutureWASM,
+
+    // protocol fee - share of the swap fee which goes to the fee destination instead of LPs
+    ProtocolFeeFraction, // 1 = 0.01% of the swap fee
+    FutureProtocolFeeFraction,
+    AdminActionsDeadline,
+    ProtocolFeeA,
+    ProtocolFeeB,
+    FeeDestination,
+
+    // price oracle - cumulative prices scaled by PRICE_PRECISION
+    PriceCumulativeA, // price of token a in units of token b
+    PriceCumulativeB, // price of token b in units of token a
+    PriceLastUpdated,
+    ObservationIndex,
+    Observation(u32), // ring buffer of cumulative price snapshots in persistent storage
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceObservation {
+    pub timestamp: u64,
+    pub price_a_cumulative: U256,
+    pub price_b_cumulative: U256,
 }
 
 generate_instance_storage_getter_and_setter_with_default!(
@@ -43,6 +67,95 @@ generate_instance_storage_getter_and_setter_with_default!(
     false
 );
 
+generate_instance_storage_getter_and_setter_with_default!(
+    protocol_fee_fraction,
+    DataKey::ProtocolFeeFraction,
+    u32,
+    0
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    future_protocol_fee_fraction,
+    DataKey::FutureProtocolFeeFraction,
+    u32,
+    0
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    admin_actions_deadline,
+    DataKey::AdminActionsDeadline,
+    u64,
+    0
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    protocol_fee_a,
+    DataKey::ProtocolFeeA,
+    u128,
+    0
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    protocol_fee_b,
+    DataKey::ProtocolFeeB,
+    u128,
+    0
+);
+generate_instance_storage_getter_and_setter!(fee_destination, DataKey::FeeDestination, Address);
+generate_instance_storage_getter_and_setter_with_default!(
+    price_last_updated,
+    DataKey::PriceLastUpdated,
+    u64,
+    0
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    observation_index,
+    DataKey::ObservationIndex,
+    u32,
+    0
+);
+
+pub fn get_price_cumulative_a(e: &Env) -> U256 {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::PriceCumulativeA)
+        .unwrap_or(U256::from_u32(e, 0))
+}
+
+pub fn set_price_cumulative_a(e: &Env, value: &U256) {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .set(&DataKey::PriceCumulativeA, value)
+}
+
+pub fn get_price_cumulative_b(e: &Env) -> U256 {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::PriceCumulativeB)
+        .unwrap_or(U256::from_u32(e, 0))
+}
+
+pub fn set_price_cumulative_b(e: &Env, value: &U256) {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .set(&DataKey::PriceCumulativeB, value)
+}
+
+pub fn get_observation(e: &Env, index: u32) -> Option<PriceObservation> {
+    let key = DataKey::Observation(index);
+    let value = e.storage().persistent().get(&key);
+    if value.is_some() {
+        bump_persistent(e, &key);
+    }
+    value
+}
+
+pub fn put_observation(e: &Env, index: u32, observation: &PriceObservation) {
+    let key = DataKey::Observation(index);
+    e.storage().persistent().set(&key, observation);
+    bump_persistent(e, &key);
+}
+
 pub fn get_token_a(e: &Env) -> Address {
     bump_instance(e);
     match e.storage().instance().get(&DataKey::TokenA) {
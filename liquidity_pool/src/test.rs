@@ -2,17 +2,20 @@
 extern crate std;
 
 use crate::testutils::{
-    create_liqpool_contract, create_plane_contract, create_reward_boost_feed_contract,
-    create_token_contract, get_token_admin_client, install_token_wasm, Setup, TestConfig,
+    create_flash_loan_receiver, create_liqpool_contract, create_plane_contract,
+    create_reward_boost_feed_contract, create_token_contract, get_token_admin_client,
+    install_token_wasm, Setup, TestConfig,
 };
-use access_control::constants::ADMIN_ACTIONS_DELAY;
+use access_control::constants::{ADMIN_ACTIONS_DELAY, MAX_EMERGENCY_MODE_DURATION};
+use access_control::emergency::EmergencyScope;
 use core::cmp::min;
 use soroban_sdk::testutils::{AuthorizedFunction, AuthorizedInvocation, Events};
 use soroban_sdk::token::{
     StellarAssetClient as SorobanTokenAdminClient, TokenClient as SorobanTokenClient,
 };
 use soroban_sdk::{
-    symbol_short, testutils::Address as _, vec, Address, Env, Error, IntoVal, Symbol, Val, Vec,
+    symbol_short, testutils::Address as _, vec, Address, Bytes, Env, Error, IntoVal, Symbol, Val,
+    Vec, U256,
 };
 use token_share::Client as ShareTokenClient;
 use utils::test_utils::{assert_approx_eq_abs, install_dummy_wasm, jump};
@@ -2445,6 +2448,99 @@ fn test_emergency_upgrade() {
     assert_eq!(token.version(), 130);
 }
 
+#[test]
+fn test_scoped_emergency_mode() {
+    let setup = Setup::default();
+    let contract = setup.liq_pool;
+    let token_wasm = setup
+        .env
+        .deployer()
+        .upload_contract_wasm(token_share::token::WASM);
+
+    for duration in [0, MAX_EMERGENCY_MODE_DURATION + 1] {
+        assert_eq!(
+            contract
+                .try_enable_emergency_mode(
+                    &setup.emergency_admin,
+                    &EmergencyScope::Upgrade,
+                    &duration
+                )
+                .unwrap_err(),
+            Ok(Error::from_contract_error(109))
+        );
+    }
+    assert_eq!(
+        contract
+            .try_enable_emergency_mode(&setup.admin, &EmergencyScope::Upgrade, &3600)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(102))
+    );
+
+    contract.enable_emergency_mode(&setup.emergency_admin, &EmergencyScope::Upgrade, &3600);
+    let state = contract.get_emergency_mode_state();
+    assert!(state.enabled);
+    assert_eq!(state.scope, EmergencyScope::Upgrade);
+    assert_eq!(state.expires_at, setup.env.ledger().timestamp() + 3600);
+    assert_eq!(state.remaining, 3600);
+    let events = setup.env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 2..),
+        vec![
+            &setup.env,
+            (
+                contract.address.clone(),
+                (Symbol::new(&setup.env, "emergency_mode_state"),).into_val(&setup.env),
+                (true, EmergencyScope::Upgrade, state.expires_at).into_val(&setup.env),
+            ),
+            (
+                contract.address.clone(),
+                (Symbol::new(&setup.env, "enable_emergency_mode"),).into_val(&setup.env),
+                ().into_val(&setup.env),
+            ),
+        ]
+    );
+
+    // emergency mode does not give the emergency admin any pause powers
+    assert!(contract.try_kill_deposit(&setup.emergency_admin).is_err());
+
+    // emergency mode is disabled automatically once expired
+    jump(&setup.env, 1800);
+    assert_eq!(contract.get_emergency_mode_state().remaining, 1800);
+    assert!(contract.get_emergency_mode());
+    jump(&setup.env, 1800);
+    assert!(!contract.get_emergency_mode());
+    let state = contract.get_emergency_mode_state();
+    assert!(!state.enabled);
+    assert_eq!(state.expires_at, 0);
+    assert_eq!(state.remaining, 0);
+    contract.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env), &token_wasm);
+    assert_eq!(
+        contract.try_apply_upgrade(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+}
+
+#[test]
+fn test_legacy_emergency_mode_without_expiry() {
+    let setup = Setup::default();
+    let contract = setup.liq_pool;
+
+    // emergency mode enabled by the previous code version has only the flag stored
+    let key = vec![&setup.env, Symbol::new(&setup.env, "EmergencyMode")];
+    setup.env.as_contract(&contract.address, || {
+        setup.env.storage().instance().set(&key, &true);
+    });
+    jump(&setup.env, MAX_EMERGENCY_MODE_DURATION + 1);
+    assert!(contract.get_emergency_mode());
+    let state = contract.get_emergency_mode_state();
+    assert!(state.enabled);
+    assert_eq!(state.scope, EmergencyScope::All);
+    assert_eq!(state.expires_at, u64::MAX);
+
+    contract.set_emergency_mode(&setup.emergency_admin, &false);
+    assert!(!contract.get_emergency_mode());
+}
+
 #[test]
 fn test_regular_upgrade_token() {
     let setup = Setup::default();
@@ -2544,3 +2640,432 @@ fn test_claim_event() {
         ]
     );
 }
+
+#[test]
+fn test_protocol_fee() {
+    let config = TestConfig {
+        mint_to_user: 1000000_0000000,
+        ..TestConfig::default()
+    };
+    let setup = Setup::new_with_config(&config);
+    let liq_pool = setup.liq_pool;
+    let user1 = setup.users[0].clone();
+    let destination = Address::generate(&setup.env);
+
+    liq_pool.set_protocol_fee_destination(&setup.admin, &destination);
+    liq_pool.commit_protocol_fee(&setup.admin, &5000);
+    assert_eq!(liq_pool.get_future_protocol_fee_fraction(), 5000);
+    assert_eq!(
+        liq_pool.try_apply_protocol_fee(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    liq_pool.apply_protocol_fee(&setup.admin);
+    assert_eq!(liq_pool.get_protocol_fee_fraction(), 5000);
+    assert_eq!(liq_pool.get_admin_actions_deadline(), 0);
+
+    liq_pool.deposit(
+        &user1,
+        &Vec::from_array(&setup.env, [100_0000000, 100_0000000]),
+        &0,
+    );
+
+    // user receives the same amount, half of the fee (29703) goes to the protocol
+    assert_eq!(liq_pool.swap(&user1, &0, &1, &1_0000000, &0), 9871287);
+    assert_eq!(liq_pool.get_protocol_fees(), Vec::from_array(&setup.env, [0, 14851]));
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&setup.env, [101_0000000, 99_0113862])
+    );
+    assert_eq!(
+        setup.token2.balance(&liq_pool.address) as u128,
+        liq_pool.get_reserves().get(1).unwrap() + 14851
+    );
+
+    assert_eq!(
+        liq_pool.claim_protocol_fees(),
+        Vec::from_array(&setup.env, [0, 14851])
+    );
+    assert_eq!(setup.token2.balance(&destination), 14851);
+    assert_eq!(liq_pool.get_protocol_fees(), Vec::from_array(&setup.env, [0, 0]));
+    assert_eq!(
+        setup.token2.balance(&liq_pool.address) as u128,
+        liq_pool.get_reserves().get(1).unwrap()
+    );
+}
+
+#[test]
+fn test_protocol_fee_revert() {
+    let setup = Setup::default();
+    let liq_pool = setup.liq_pool;
+
+    liq_pool.commit_protocol_fee(&setup.admin, &5000);
+    let pending_action = liq_pool.get_admin_state().pending_actions.get(0).unwrap();
+    assert_eq!(pending_action.name, Symbol::new(&setup.env, "ProtocolFee"));
+    assert_eq!(
+        pending_action.deadline,
+        setup.env.ledger().timestamp() + ADMIN_ACTIONS_DELAY
+    );
+    assert_eq!(
+        liq_pool
+            .try_commit_protocol_fee(&setup.admin, &1000)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2906))
+    );
+    liq_pool.revert_protocol_fee(&setup.admin);
+    assert_eq!(liq_pool.get_admin_state().pending_actions.len(), 0);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    assert_eq!(
+        liq_pool.try_apply_protocol_fee(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2907))
+    );
+    assert_eq!(liq_pool.get_protocol_fee_fraction(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2003)")]
+fn test_protocol_fee_out_of_bounds() {
+    let setup = Setup::default();
+    setup.liq_pool.commit_protocol_fee(&setup.admin, &10001);
+}
+
+#[test]
+fn test_price_oracle() {
+    let setup = Setup::new_with_config(&TestConfig {
+        mint_to_user: i128::MAX,
+        ..TestConfig::default()
+    });
+    let e = setup.env;
+    let liq_pool = setup.liq_pool;
+    let user1 = setup.users[0].clone();
+    let tokens = liq_pool.get_tokens();
+    let (token_a, token_b) = (tokens.get(0).unwrap(), tokens.get(1).unwrap());
+    let precision = 1_000_000_000_000_000_000_u128;
+
+    jump(&e, 1000);
+    liq_pool.deposit(&user1, &Vec::from_array(&e, [100_0000000, 100_0000000]), &0);
+
+    // no history yet
+    assert_eq!(
+        liq_pool.try_consult(&token_a, &1).unwrap_err(),
+        Ok(Error::from_contract_error(211))
+    );
+    assert_eq!(
+        liq_pool.try_consult(&token_a, &0).unwrap_err(),
+        Ok(Error::from_contract_error(210))
+    );
+    assert_eq!(
+        liq_pool
+            .try_consult(&Address::generate(&e), &1)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2008))
+    );
+
+    // price stays 1:1 for an hour before the swap
+    jump(&e, 3600);
+    liq_pool.swap(&user1, &0, &1, &10_0000000, &0);
+    assert_eq!(
+        liq_pool.consult(&token_a, &3600),
+        U256::from_u128(&e, precision)
+    );
+    assert_eq!(
+        liq_pool.consult(&token_b, &3600),
+        U256::from_u128(&e, precision)
+    );
+
+    // new price holds for the next hour
+    jump(&e, 3600);
+    let reserves = liq_pool.get_reserves();
+    let (reserve_a, reserve_b) = (reserves.get(0).unwrap(), reserves.get(1).unwrap());
+    let price_a = reserve_b * precision / reserve_a;
+    let price_b = reserve_a * precision / reserve_b;
+    assert_eq!(
+        liq_pool.consult(&token_a, &3600),
+        U256::from_u128(&e, price_a)
+    );
+    assert_eq!(
+        liq_pool.consult(&token_b, &3600),
+        U256::from_u128(&e, price_b)
+    );
+    assert_eq!(
+        liq_pool.consult(&token_a, &7200),
+        U256::from_u128(&e, (precision + price_a) / 2)
+    );
+    // window is extended to the latest observation made before it
+    assert_eq!(
+        liq_pool.consult(&token_a, &1800),
+        liq_pool.consult(&token_a, &3600)
+    );
+    assert_eq!(
+        liq_pool.try_consult(&token_a, &7201).unwrap_err(),
+        Ok(Error::from_contract_error(211))
+    );
+
+    // prices are not changed by the withdrawal itself
+    let shares = liq_pool.get_total_shares();
+    liq_pool.withdraw(&user1, &(shares / 2), &Vec::from_array(&e, [0, 0]));
+    assert_eq!(
+        liq_pool.consult(&token_a, &3600),
+        U256::from_u128(&e, price_a)
+    );
+}
+
+#[test]
+fn test_flash_loan() {
+    let setup = Setup::new_with_config(&TestConfig {
+        mint_to_user: i128::MAX,
+        ..TestConfig::default()
+    });
+    let e = setup.env;
+    let liq_pool = setup.liq_pool;
+    let user1 = setup.users[0].clone();
+    liq_pool.deposit(&user1, &Vec::from_array(&e, [100_0000000, 100_0000000]), &0);
+
+    let receiver = create_flash_loan_receiver(&e, &user1);
+    setup
+        .token1_admin_client
+        .mint(&receiver.address, &1_0000000);
+    setup
+        .token2_admin_client
+        .mint(&receiver.address, &1_0000000);
+    let amounts = Vec::from_array(&e, [10_0000000, 5_0000000]);
+    let repay_with_fee = Bytes::from_array(&e, &[1]);
+
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user1,
+                &receiver.address,
+                &amounts,
+                &Bytes::from_array(&e, &[0])
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(212))
+    );
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user1,
+                &receiver.address,
+                &Vec::from_array(&e, [1]),
+                &repay_with_fee
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2001))
+    );
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user1,
+                &receiver.address,
+                &Vec::from_array(&e, [0, 0]),
+                &repay_with_fee
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2018))
+    );
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user1,
+                &receiver.address,
+                &Vec::from_array(&e, [100_0000001, 0]),
+                &repay_with_fee
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2019))
+    );
+
+    // receiver accepts loans initiated by its owner only
+    let third_party = Address::generate(&e);
+    assert!(liq_pool
+        .try_flash_loan(&third_party, &receiver.address, &amounts, &repay_with_fee)
+        .is_err());
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [100_0000000, 100_0000000])
+    );
+
+    // fee is charged as for the swap and goes to the LPs
+    let fees = liq_pool.flash_loan(&user1, &receiver.address, &amounts, &repay_with_fee);
+    assert_eq!(
+        e.auths()[0],
+        (
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    liq_pool.address.clone(),
+                    Symbol::new(&e, "flash_loan"),
+                    (
+                        &user1,
+                        &receiver.address,
+                        amounts.clone(),
+                        repay_with_fee.clone()
+                    )
+                        .into_val(&e),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )
+    );
+    assert_eq!(fees, Vec::from_array(&e, [300000, 150000]));
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [100_0300000, 100_0150000])
+    );
+    assert_eq!(setup.token1.balance(&receiver.address), 9700000);
+    assert_eq!(setup.token2.balance(&receiver.address), 9850000);
+
+    // protocol share of the fee is excluded from the reserves
+    liq_pool.commit_protocol_fee(&setup.admin, &5000);
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
+    liq_pool.apply_protocol_fee(&setup.admin);
+    liq_pool.flash_loan(&user1, &receiver.address, &amounts, &repay_with_fee);
+    assert_eq!(
+        liq_pool.get_protocol_fees(),
+        Vec::from_array(&e, [150000, 75000])
+    );
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [100_0450000, 100_0225000])
+    );
+
+    liq_pool.kill_swap(&setup.admin);
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(&user1, &receiver.address, &amounts, &repay_with_fee)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(206))
+    );
+}
+
+#[test]
+fn test_deposit_single() {
+    let setup = Setup::new_with_config(&TestConfig {
+        mint_to_user: i128::MAX,
+        ..TestConfig::default()
+    });
+    let e = setup.env;
+    let liq_pool = setup.liq_pool;
+    let user1 = setup.users[0].clone();
+    let user2 = setup.users[1].clone();
+
+    assert_eq!(
+        liq_pool
+            .try_deposit_single(&user2, &0, &10_0000000, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2010))
+    );
+    liq_pool.deposit(&user1, &Vec::from_array(&e, [100_0000000, 100_0000000]), &0);
+
+    assert_eq!(
+        liq_pool
+            .try_deposit_single(&user2, &2, &10_0000000, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2008))
+    );
+    assert_eq!(
+        liq_pool.try_deposit_single(&user2, &0, &0, &0).unwrap_err(),
+        Ok(Error::from_contract_error(2018))
+    );
+    assert_eq!(
+        liq_pool.try_estimate_deposit_single(&0, &0).unwrap_err(),
+        Ok(Error::from_contract_error(2018))
+    );
+
+    // 4_8885753 is swapped for 4_6467497 and deposited along with the rest 5_1114247
+    assert_eq!(liq_pool.estimate_deposit_single(&0, &10_0000000), 4_8731948);
+    assert_eq!(
+        liq_pool
+            .try_deposit_single(&user2, &0, &10_0000000, &4_8731949)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2006))
+    );
+    assert_eq!(
+        liq_pool.deposit_single(&user2, &0, &10_0000000, &4_8731948),
+        4_8731948
+    );
+    assert_eq!(setup.token_share.balance(&user2), 4_8731948);
+    assert_eq!(setup.token1.balance(&user2), i128::MAX - 10_0000000);
+    assert_eq!(setup.token2.balance(&user2), i128::MAX);
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [110_0000000, 100_0000000])
+    );
+    assert_eq!(setup.token1.balance(&liq_pool.address), 110_0000000);
+    assert_eq!(setup.token2.balance(&liq_pool.address), 100_0000000);
+
+    liq_pool.kill_deposit(&setup.admin);
+    assert_eq!(
+        liq_pool
+            .try_deposit_single(&user2, &1, &10_0000000, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(205))
+    );
+}
+
+#[test]
+fn test_withdraw_one_coin() {
+    let setup = Setup::new_with_config(&TestConfig {
+        mint_to_user: i128::MAX,
+        ..TestConfig::default()
+    });
+    let e = setup.env;
+    let liq_pool = setup.liq_pool;
+    let user1 = setup.users[0].clone();
+    liq_pool.deposit(&user1, &Vec::from_array(&e, [100_0000000, 100_0000000]), &0);
+
+    assert_eq!(
+        liq_pool
+            .try_calc_withdraw_one_coin(&10_0000000, &2)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2009))
+    );
+    // pool can't be drained into a single token
+    assert_eq!(
+        liq_pool
+            .try_withdraw_one_coin(&user1, &100_0000000, &0, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2010))
+    );
+
+    // 10_0000000 of each token is withdrawn, token b part is swapped for 8_9730000
+    assert_eq!(liq_pool.calc_withdraw_one_coin(&10_0000000, &0), 18_9730000);
+    assert_eq!(
+        liq_pool
+            .try_withdraw_one_coin(&user1, &10_0000000, &0, &18_9730001)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2006))
+    );
+    assert_eq!(
+        liq_pool.withdraw_one_coin(&user1, &10_0000000, &0, &18_9730000),
+        Vec::from_array(&e, [18_9730000, 0])
+    );
+    assert_eq!(setup.token_share.balance(&user1), 90_0000000);
+    assert_eq!(
+        setup.token1.balance(&user1),
+        i128::MAX - 100_0000000 + 18_9730000
+    );
+    assert_eq!(setup.token2.balance(&user1), i128::MAX - 100_0000000);
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [81_0270000, 100_0000000])
+    );
+    assert_eq!(setup.token1.balance(&liq_pool.address), 81_0270000);
+    assert_eq!(setup.token2.balance(&liq_pool.address), 100_0000000);
+
+    liq_pool.kill_swap(&setup.admin);
+    assert_eq!(
+        liq_pool
+            .try_withdraw_one_coin(&user1, &10_0000000, &0, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(206))
+    );
+
+    // rest of the supply is withdrawn with the regular withdraw
+    assert_eq!(
+        liq_pool.withdraw(&user1, &90_0000000, &Vec::from_array(&e, [0, 0])),
+        Vec::from_array(&e, [81_0270000, 100_0000000])
+    );
+    assert_eq!(liq_pool.get_reserves(), Vec::from_array(&e, [0, 0]));
+}
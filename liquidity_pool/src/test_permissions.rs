@@ -1,9 +1,9 @@
 #![cfg(test)]
 
-use crate::testutils::Setup;
+use crate::testutils::{install_token_wasm, RouterStubClient, Setup};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{symbol_short, Address, Symbol, Vec};
+use soroban_sdk::{symbol_short, Address, Error, Symbol, Vec};
 use token_share::Client as ShareTokenClient;
 use utils::test_utils::{install_dummy_wasm, jump};
 
@@ -287,6 +287,64 @@ fn test_commit_upgrade() {
     }
 }
 
+#[test]
+fn test_commit_upgrade_router_allowlist() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let router = RouterStubClient::new(&setup.env, &setup.router);
+    let new_wasm = install_dummy_wasm(&setup.env);
+    let token_wasm = install_token_wasm(&setup.env);
+
+    router.deny_wasm(&new_wasm);
+    assert_eq!(
+        pool.try_commit_upgrade(&setup.admin, &new_wasm, &token_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2915))
+    );
+    assert_eq!(
+        pool.try_commit_upgrade(&setup.admin, &token_wasm, &new_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2915))
+    );
+
+    // router checks the hashes itself when upgrading the pools
+    pool.commit_upgrade(&setup.router, &new_wasm, &token_wasm);
+}
+
+#[test]
+fn test_admin_signers_cosigners() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let signer1 = Address::generate(&setup.env);
+    let signer2 = Address::generate(&setup.env);
+    let new_wasm = install_dummy_wasm(&setup.env);
+    let token_wasm = install_token_wasm(&setup.env);
+
+    pool.commit_admin_signers(
+        &setup.admin,
+        &Vec::from_array(&setup.env, [signer1.clone(), signer2.clone()]),
+        &2,
+    );
+    jump(&setup.env, ADMIN_ACTIONS_DELAY);
+    pool.apply_admin_signers(&setup.admin);
+
+    // neither the previous admin nor the single signer is enough
+    assert!(pool
+        .try_commit_upgrade(&setup.admin, &new_wasm, &token_wasm)
+        .is_err());
+    assert!(pool
+        .try_commit_upgrade(&signer1, &new_wasm, &token_wasm)
+        .is_err());
+
+    pool.set_admin_cosigners(&signer1, &Vec::from_array(&setup.env, [signer2.clone()]));
+    pool.commit_upgrade(&signer1, &new_wasm, &token_wasm);
+    assert!(setup
+        .env
+        .auths()
+        .iter()
+        .any(|(address, _)| address == &signer2));
+}
+
 #[test]
 fn test_apply_upgrade_third_party_user() {
     let setup = Setup::default();
@@ -496,6 +554,92 @@ fn test_set_privileged_addresses() {
     }
 }
 
+#[test]
+fn test_privileged_roles_delayed_change() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let rewards_admin_role = Symbol::new(&setup.env, "RewardsAdmin");
+    let emergency_pause_admin_role = Symbol::new(&setup.env, "EmergencyPauseAdmin");
+    let rewards_admin_new = Address::generate(&setup.env);
+    let emergency_pause_admin_new = Address::generate(&setup.env);
+    let delay = 86400;
+
+    // zero deadline stands for no pending change, so move off the genesis timestamp
+    jump(&setup.env, 1);
+    assert!(pool
+        .try_commit_role_transfer_delay(&setup.rewards_admin, &rewards_admin_role, &delay)
+        .is_err());
+    for role_name in [
+        rewards_admin_role.clone(),
+        emergency_pause_admin_role.clone(),
+    ] {
+        pool.commit_role_transfer_delay(&setup.admin, &role_name, &delay);
+        pool.apply_role_transfer_delay(&setup.admin, &role_name);
+        assert_eq!(pool.get_role_transfer_delay(&role_name), delay);
+    }
+
+    // addresses cannot be replaced instantly anymore
+    assert!(pool
+        .try_set_privileged_addrs(
+            &setup.admin,
+            &rewards_admin_new,
+            &setup.operations_admin,
+            &setup.pause_admin,
+            &Vec::from_array(&setup.env, [setup.emergency_pause_admin.clone()]),
+        )
+        .is_err());
+    assert!(pool
+        .try_set_privileged_addrs(
+            &setup.admin,
+            &setup.rewards_admin,
+            &setup.operations_admin,
+            &setup.pause_admin,
+            &Vec::from_array(&setup.env, [emergency_pause_admin_new.clone()]),
+        )
+        .is_err());
+
+    pool.commit_transfer_ownership(&setup.admin, &rewards_admin_role, &rewards_admin_new);
+    assert!(pool
+        .try_commit_add_role_member(
+            &setup.rewards_admin,
+            &emergency_pause_admin_role,
+            &emergency_pause_admin_new
+        )
+        .is_err());
+    pool.commit_add_role_member(
+        &setup.admin,
+        &emergency_pause_admin_role,
+        &emergency_pause_admin_new,
+    );
+    assert_eq!(
+        pool.get_pending_role_members_change(&emergency_pause_admin_role),
+        Some((emergency_pause_admin_new.clone(), true))
+    );
+    assert!(pool
+        .try_apply_transfer_ownership(&setup.admin, &rewards_admin_role)
+        .is_err());
+    assert!(pool
+        .try_apply_role_members_change(&setup.admin, &emergency_pause_admin_role)
+        .is_err());
+
+    jump(&setup.env, delay);
+    pool.apply_transfer_ownership(&setup.admin, &rewards_admin_role);
+    pool.apply_role_members_change(&setup.admin, &emergency_pause_admin_role);
+
+    let privileged_addrs = pool.get_privileged_addrs();
+    assert_eq!(
+        privileged_addrs.get(rewards_admin_role).unwrap(),
+        Vec::from_array(&setup.env, [rewards_admin_new])
+    );
+    assert_eq!(
+        privileged_addrs.get(emergency_pause_admin_role).unwrap(),
+        Vec::from_array(
+            &setup.env,
+            [setup.emergency_pause_admin, emergency_pause_admin_new]
+        )
+    );
+}
+
 #[test]
 fn test_set_pools_plane() {
     let setup = Setup::default();
@@ -541,3 +685,70 @@ fn test_set_rewards_config() {
         jump(&setup.env, 10);
     }
 }
+
+#[test]
+fn test_commit_protocol_fee() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let user = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.admin, true),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, true),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+    ] {
+        assert_eq!(pool.try_commit_protocol_fee(&addr, &1000).is_ok(), is_ok);
+        if is_ok {
+            pool.revert_protocol_fee(&addr);
+        }
+    }
+}
+
+#[test]
+fn test_apply_protocol_fee() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let user = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.admin, true),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, true),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+    ] {
+        pool.commit_protocol_fee(&setup.operations_admin, &1000);
+        jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+        assert_eq!(pool.try_apply_protocol_fee(&addr).is_ok(), is_ok);
+        if !is_ok {
+            pool.revert_protocol_fee(&setup.operations_admin);
+        }
+    }
+}
+
+#[test]
+fn test_set_protocol_fee_destination() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let destination = Address::generate(&setup.env);
+    let user = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.admin, true),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, false),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+    ] {
+        assert_eq!(
+            pool.try_set_protocol_fee_destination(&addr, &destination)
+                .is_ok(),
+            is_ok
+        );
+    }
+}
@@ -6,11 +6,84 @@ use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::token::{
     StellarAssetClient as SorobanTokenAdminClient, TokenClient as SorobanTokenClient,
 };
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, testutils::Address as _, Address, Bytes, BytesN, Env, Symbol, Vec,
+};
 use std::vec;
 use token_share::token_contract::{Client as ShareTokenClient, WASM};
 use utils::test_utils::jump;
 
+// Flash loan receiver. Returns the borrowed amounts with fees if the first byte of data is 1,
+// otherwise only the borrowed amounts. Accepts loans initiated by the owner only
+#[contract]
+pub struct FlashLoanReceiver;
+
+#[contractimpl]
+impl FlashLoanReceiver {
+    pub fn __constructor(e: Env, owner: Address) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "owner"), &owner);
+    }
+
+    pub fn on_flash_loan(
+        e: Env,
+        initiator: Address,
+        pool: Address,
+        tokens: Vec<Address>,
+        amounts: Vec<u128>,
+        fees: Vec<u128>,
+        data: Bytes,
+    ) {
+        let owner: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "owner"))
+            .unwrap();
+        if initiator != owner {
+            panic!("loan is not initiated by the owner");
+        }
+
+        let with_fee = data.get(0) == Some(1);
+        for i in 0..tokens.len() {
+            let mut amount = amounts.get(i).unwrap();
+            if with_fee {
+                amount += fees.get(i).unwrap();
+            }
+            if amount > 0 {
+                SorobanTokenClient::new(&e, &tokens.get(i).unwrap()).transfer(
+                    &e.current_contract_address(),
+                    &pool,
+                    &(amount as i128),
+                );
+            }
+        }
+    }
+}
+
+pub fn create_flash_loan_receiver<'a>(e: &Env, owner: &Address) -> FlashLoanReceiverClient<'a> {
+    FlashLoanReceiverClient::new(e, &e.register(FlashLoanReceiver {}, (owner,)))
+}
+
+// Router stub serving the shared wasm allowlist. Every hash is allowed unless it has been denied
+#[contract]
+pub struct RouterStub;
+
+#[contractimpl]
+impl RouterStub {
+    pub fn deny_wasm(e: Env, wasm_hash: BytesN<32>) {
+        e.storage().instance().set(&wasm_hash, &true);
+    }
+
+    pub fn is_wasm_allowed(e: Env, _kind_name: Symbol, wasm_hash: BytesN<32>) -> bool {
+        !e.storage().instance().has(&wasm_hash)
+    }
+}
+
+pub fn create_router_stub<'a>(e: &Env) -> RouterStubClient<'a> {
+    RouterStubClient::new(e, &e.register(RouterStub {}, ()))
+}
+
 pub(crate) struct TestConfig {
     pub(crate) users_count: u32,
     pub(crate) mint_to_user: i128,
@@ -113,7 +186,7 @@ impl Setup<'_> {
         let token2_admin_client = get_token_admin_client(&e, &token2.address.clone());
         let token_reward_admin_client = get_token_admin_client(&e, &reward_token.address.clone());
 
-        let router = Address::generate(&e);
+        let router = create_router_stub(&e).address;
 
         let liq_pool = create_liqpool_contract(
             &e,
@@ -217,7 +290,10 @@ pub(crate) fn get_token_admin_client<'a>(
 }
 
 pub(crate) fn create_plane_contract<'a>(e: &Env) -> PoolPlaneClient<'a> {
-    PoolPlaneClient::new(e, &e.register(pool_plane::WASM, ()))
+    let plane = PoolPlaneClient::new(e, &e.register(pool_plane::WASM, ()));
+    // storage version is set on init, plane refuses updates until then
+    plane.init_admin(&Address::generate(e));
+    plane
 }
 
 mod reward_boost_feed {
@@ -5,4 +5,5 @@ pub trait Calculator {
     fn set_pools_plane(e: Env, admin: Address, plane: Address);
     fn get_pools_plane(e: Env) -> Address;
     fn get_liquidity(e: Env, pools: Vec<Address>) -> Vec<U256>;
+    fn estimate_swaps(e: Env, swaps: Vec<(Address, Vec<Address>, u32, u32, u128)>) -> Vec<u128>;
 }
@@ -2,7 +2,8 @@ use crate::calculator::{get_max_reserve, get_next_in_amt, normalize_reserves, pr
 use crate::constants::{FEE_MULTIPLIER, PRECISION};
 use crate::errors::LiquidityPoolCalculatorError;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{panic_with_error, Env, Vec, U256};
+use soroban_sdk::token::Client as SorobanTokenClient;
+use soroban_sdk::{panic_with_error, Address, Env, Vec, U256};
 
 pub(crate) fn a(
     e: &Env,
@@ -277,6 +278,55 @@ pub fn get_liquidity(
     result_big / PRECISION
 }
 
+// Multipliers the pool uses to scale token amounts to the maximum precision of its tokens
+pub(crate) fn get_precision_mul(e: &Env, tokens: &Vec<Address>) -> Vec<u128> {
+    let mut decimals = Vec::new(e);
+    for token in tokens.iter() {
+        decimals.push_back(SorobanTokenClient::new(e, &token).decimals());
+    }
+    let precision = 10u128.pow(decimals.iter().max().unwrap());
+    let mut precision_mul = Vec::new(e);
+    for token_decimals in decimals.iter() {
+        precision_mul.push_back(precision / 10u128.pow(token_decimals));
+    }
+    precision_mul
+}
+
+// Estimates amount of token received from the swap in real token units.
+// Reserves are normalized to keep u128 math safe, so result is approximate for the very large pools.
+//
+// * `xp`: pool balances list in normalized form
+// * `precision_mul`: multipliers used by the pool to normalize balances of each token
+pub(crate) fn estimate_swap_amount(
+    e: &Env,
+    fee_fraction: u128,
+    amp: u128,
+    xp: &Vec<u128>,
+    precision_mul: &Vec<u128>,
+    in_idx: u32,
+    out_idx: u32,
+    in_amount: u128,
+) -> u128 {
+    let (xp_norm, nominator, denominator) = normalize_reserves(xp);
+    let xp_in = xp_norm.get(in_idx).unwrap();
+    let xp_out = xp_norm.get(out_idx).unwrap();
+    let dx = in_amount * precision_mul.get(in_idx).unwrap() * denominator / nominator;
+    if dx == 0 || xp_in == 0 || xp_out == 0 {
+        return 0;
+    }
+
+    let n_coins = xp_norm.len();
+    let d = get_d(e, n_coins, &xp_norm, amp);
+    let y = get_y(e, d, n_coins, in_idx, out_idx, xp_in + dx, &xp_norm, amp);
+
+    // convert back to the real units before the fee is applied as the pool does
+    let dy = xp_out.saturating_sub(y + 1) * nominator
+        / denominator
+        / precision_mul.get(out_idx).unwrap();
+    let fee = fee_fraction.fixed_mul_ceil(e, &dy, &FEE_MULTIPLIER);
+    dy - fee
+}
+
 pub(crate) fn get_pool_liquidity(e: &Env, fee_fraction: u128, amp: u128, xp: &Vec<u128>) -> U256 {
     let n_tokens = xp.len();
     let (xp_norm, nominator, denominator) = normalize_reserves(xp);
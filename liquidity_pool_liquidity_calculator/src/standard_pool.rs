@@ -80,3 +80,25 @@ pub fn get_liquidity(
     let x = reserves.get(in_idx).unwrap();
     x.fixed_mul_floor(e, &FEE_MULTIPLIER, &(56 * (FEE_MULTIPLIER - fee_fraction)))
 }
+
+// Estimates amount of token received from the swap. Same math as the pool uses:
+//   in * reserve_buy / (reserve_sell + in) - fee
+pub fn estimate_swap(
+    e: &Env,
+    fee_fraction: u128,
+    reserves: &Vec<u128>,
+    in_idx: u32,
+    out_idx: u32,
+    in_amount: u128,
+) -> u128 {
+    // plane returns empty standard pool if the pool didn't report its data yet
+    let reserve_sell = reserves.get(in_idx).unwrap_or(0);
+    let reserve_buy = reserves.get(out_idx).unwrap_or(0);
+    if in_amount == 0 || reserve_sell == 0 || reserve_buy == 0 {
+        return 0;
+    }
+
+    let result = in_amount.fixed_mul_floor(e, &reserve_buy, &(reserve_sell + in_amount));
+    let fee = result.fixed_mul_ceil(e, &fee_fraction, &FEE_MULTIPLIER);
+    result - fee
+}
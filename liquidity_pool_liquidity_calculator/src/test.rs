@@ -5,7 +5,8 @@ use crate::testutils::{install_dummy_wasm, jump, Setup};
 use crate::{contract::LiquidityPoolLiquidityCalculator, LiquidityPoolLiquidityCalculatorClient};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::{Address as _, Events};
-use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, IntoVal, Symbol, Vec, U256};
+use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, Error, IntoVal, Symbol, Vec, U256};
+use utils::test_utils::allow_wasm;
 
 fn create_contract<'a>(e: &Env) -> LiquidityPoolLiquidityCalculatorClient<'a> {
     let client = LiquidityPoolLiquidityCalculatorClient::new(
@@ -23,7 +24,10 @@ mod pool_plane {
 }
 
 fn create_plane_contract<'a>(e: &Env) -> pool_plane::Client<'a> {
-    pool_plane::Client::new(e, &e.register(pool_plane::WASM, ()))
+    let plane = pool_plane::Client::new(e, &e.register(pool_plane::WASM, ()));
+    // storage version is set on init, plane refuses updates until then
+    plane.init_admin(&Address::generate(e));
+    plane
 }
 
 #[test]
@@ -782,6 +786,61 @@ fn test_bad_address() {
     );
 }
 
+#[test]
+fn test_estimate_swaps() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(&e);
+
+    let address1 = Address::generate(&e);
+    let address2 = Address::generate(&e);
+    let address3 = Address::generate(&e);
+    let tokens = Vec::from_array(
+        &e,
+        [
+            e.register_stellar_asset_contract_v2(admin.clone())
+                .address(),
+            e.register_stellar_asset_contract_v2(admin.clone())
+                .address(),
+        ],
+    );
+
+    let plane = create_plane_contract(&e);
+    plane.update(
+        &address1,
+        &symbol_short!("standard"),
+        &Vec::from_array(&e, [30_u128]),
+        &Vec::from_array(&e, [1000_0000000_u128, 1000_0000000_u128]),
+    );
+    plane.update(
+        &address2,
+        &symbol_short!("stable"),
+        &Vec::from_array(&e, [6_u128, 85_u128, 0_u128, 85_u128, 0_u128]),
+        &Vec::from_array(&e, [1000_0000000_u128, 1000_0000000_u128]),
+    );
+
+    let calculator = create_contract(&e);
+    calculator.init_admin(&admin);
+    calculator.set_pools_plane(&admin, &plane.address);
+
+    let results = calculator.estimate_swaps(&Vec::from_array(
+        &e,
+        [
+            (address1.clone(), tokens.clone(), 0, 1, 100_0000000),
+            (address2.clone(), tokens.clone(), 1, 0, 100_0000000),
+            // pool without data in the plane
+            (address3.clone(), tokens.clone(), 0, 1, 100_0000000),
+        ],
+    ));
+    // 100 * 1000 / 1100 minus 0.3% fee
+    assert_eq!(results.get(0).unwrap(), 90_6363636);
+    assert!(results.get(1).unwrap() > 99_0000000);
+    assert!(results.get(1).unwrap() < 100_0000000);
+    assert_eq!(results.get(2).unwrap(), 0);
+}
+
 #[test]
 fn test_transfer_ownership_events() {
     let setup = Setup::default();
@@ -847,6 +906,13 @@ fn test_upgrade_events() {
     let setup = Setup::default();
     let contract = setup.calculator;
     let new_wasm_hash = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "LiquidityCalculator",
+        &new_wasm_hash,
+    );
 
     contract.commit_upgrade(&setup.admin, &new_wasm_hash);
     assert_eq!(
@@ -926,6 +992,13 @@ fn test_emergency_upgrade() {
     let setup = Setup::default();
     let contract = setup.calculator;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "LiquidityCalculator",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -942,6 +1015,13 @@ fn test_regular_upgrade() {
     let setup = Setup::default();
     let contract = setup.calculator;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "LiquidityCalculator",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -953,3 +1033,37 @@ fn test_regular_upgrade() {
 
     assert_eq!(contract.version(), 130)
 }
+
+#[test]
+fn test_not_migrated() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let calculator = setup.calculator;
+    let plane = create_plane_contract(&e);
+    calculator.set_pools_plane(&setup.admin, &plane.address);
+
+    // simulate upgrade from the previous version
+    e.as_contract(&calculator.address, || {
+        upgrade::set_storage_version(&e, calculator.version() - 1)
+    });
+    assert_eq!(
+        calculator
+            .try_set_pools_plane(&setup.admin, &plane.address)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2914))
+    );
+    assert_eq!(
+        calculator.try_get_pools_plane().unwrap_err(),
+        Ok(Error::from_contract_error(2914))
+    );
+    assert_eq!(
+        calculator.try_get_liquidity(&Vec::new(&e)).unwrap_err(),
+        Ok(Error::from_contract_error(2914))
+    );
+    assert_eq!(
+        calculator.try_estimate_swaps(&Vec::new(&e)).unwrap_err(),
+        Ok(Error::from_contract_error(2914))
+    );
+    calculator.migrate(&setup.admin);
+    assert_eq!(calculator.get_liquidity(&Vec::new(&e)), Vec::new(&e));
+}
@@ -4,6 +4,7 @@ use crate::testutils::{create_contract, install_dummy_wasm, jump, Setup};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{symbol_short, Address, Env, Symbol};
+use utils::test_utils::allow_wasm;
 
 // test admin transfer ownership
 #[test]
@@ -317,8 +318,16 @@ fn test_commit_upgrade_emergency_admin() {
 fn test_commit_upgrade_admin() {
     let setup = Setup::default();
     let calculator = setup.calculator;
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &calculator.address,
+        &setup.admin,
+        "LiquidityCalculator",
+        &new_wasm,
+    );
     assert!(calculator
-        .try_commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env))
+        .try_commit_upgrade(&setup.admin, &new_wasm)
         .is_ok());
 }
 
@@ -327,7 +336,15 @@ fn test_apply_upgrade_third_party_user() {
     let setup = Setup::default();
     let calculator = setup.calculator;
     let user = Address::generate(&setup.env);
-    calculator.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &calculator.address,
+        &setup.admin,
+        "LiquidityCalculator",
+        &new_wasm,
+    );
+    calculator.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(calculator.try_apply_upgrade(&user).is_err());
 }
@@ -336,7 +353,15 @@ fn test_apply_upgrade_third_party_user() {
 fn test_apply_upgrade_emergency_admin() {
     let setup = Setup::default();
     let calculator = setup.calculator;
-    calculator.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &calculator.address,
+        &setup.admin,
+        "LiquidityCalculator",
+        &new_wasm,
+    );
+    calculator.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(calculator
         .try_apply_upgrade(&setup.emergency_admin)
@@ -347,7 +372,15 @@ fn test_apply_upgrade_emergency_admin() {
 fn test_apply_upgrade_admin() {
     let setup = Setup::default();
     let calculator = setup.calculator;
-    calculator.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &calculator.address,
+        &setup.admin,
+        "LiquidityCalculator",
+        &new_wasm,
+    );
+    calculator.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert_ne!(calculator.version(), 130);
     assert!(calculator.try_apply_upgrade(&setup.admin).is_ok());
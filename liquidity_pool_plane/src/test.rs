@@ -5,7 +5,8 @@ use crate::testutils::{install_dummy_wasm, jump, Setup};
 use crate::{contract::LiquidityPoolPlane, LiquidityPoolPlaneClient};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::{Address as _, Events};
-use soroban_sdk::{symbol_short, vec, Address, Env, IntoVal, Symbol, Vec};
+use soroban_sdk::{symbol_short, vec, Address, Env, Error, IntoVal, Symbol, Vec};
+use utils::test_utils::allow_wasm;
 
 fn create_plane_contract<'a>(e: &Env) -> LiquidityPoolPlaneClient<'a> {
     let client = LiquidityPoolPlaneClient::new(e, &e.register(LiquidityPoolPlane {}, ()));
@@ -129,6 +130,13 @@ fn test_upgrade_events() {
     let setup = Setup::default();
     let contract = setup.plane;
     let new_wasm_hash = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "Plane",
+        &new_wasm_hash,
+    );
 
     contract.commit_upgrade(&setup.admin, &new_wasm_hash);
     assert_eq!(
@@ -208,6 +216,13 @@ fn test_emergency_upgrade() {
     let setup = Setup::default();
     let contract = setup.plane;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "Plane",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -224,6 +239,13 @@ fn test_regular_upgrade() {
     let setup = Setup::default();
     let contract = setup.plane;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "Plane",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -235,3 +257,26 @@ fn test_regular_upgrade() {
 
     assert_eq!(contract.version(), 130)
 }
+
+#[test]
+fn test_update_not_migrated() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let plane = setup.plane;
+    let pool = Address::generate(&e);
+    let init_args = Vec::from_array(&e, [30_u128]);
+    let reserves = Vec::from_array(&e, [1000_u128, 1000_u128]);
+
+    // simulate upgrade from the previous version
+    e.as_contract(&plane.address, || {
+        upgrade::set_storage_version(&e, plane.version() - 1)
+    });
+    assert_eq!(
+        plane
+            .try_update(&pool, &symbol_short!("standard"), &init_args, &reserves)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2914))
+    );
+    plane.migrate(&setup.admin);
+    plane.update(&pool, &symbol_short!("standard"), &init_args, &reserves);
+}
@@ -4,6 +4,7 @@ use crate::testutils::{install_dummy_wasm, jump, Setup};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{symbol_short, Address, Symbol};
+use utils::test_utils::allow_wasm;
 
 // test admin transfer ownership
 #[test]
@@ -283,9 +284,9 @@ fn test_commit_upgrade_emergency_admin() {
 fn test_commit_upgrade_admin() {
     let setup = Setup::default();
     let plane = setup.plane;
-    assert!(plane
-        .try_commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env))
-        .is_ok());
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(&setup.env, &plane.address, &setup.admin, "Plane", &new_wasm);
+    assert!(plane.try_commit_upgrade(&setup.admin, &new_wasm).is_ok());
 }
 
 #[test]
@@ -293,7 +294,9 @@ fn test_apply_upgrade_third_party_user() {
     let setup = Setup::default();
     let plane = setup.plane;
     let user = Address::generate(&setup.env);
-    plane.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(&setup.env, &plane.address, &setup.admin, "Plane", &new_wasm);
+    plane.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(plane.try_apply_upgrade(&user).is_err());
 }
@@ -302,7 +305,9 @@ fn test_apply_upgrade_third_party_user() {
 fn test_apply_upgrade_emergency_admin() {
     let setup = Setup::default();
     let plane = setup.plane;
-    plane.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(&setup.env, &plane.address, &setup.admin, "Plane", &new_wasm);
+    plane.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(plane.try_apply_upgrade(&setup.emergency_admin).is_err());
 }
@@ -311,7 +316,9 @@ fn test_apply_upgrade_emergency_admin() {
 fn test_apply_upgrade_admin() {
     let setup = Setup::default();
     let plane = setup.plane;
-    plane.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(&setup.env, &plane.address, &setup.admin, "Plane", &new_wasm);
+    plane.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert_ne!(plane.version(), 130);
     assert!(plane.try_apply_upgrade(&setup.admin).is_ok());
@@ -4,20 +4,48 @@ use crate::storage::{
     set_fee_contract_wasm, set_router,
 };
 use access_control::access::{AccessControl, AccessControlTrait};
-use access_control::emergency::{get_emergency_mode, set_emergency_mode};
+use access_control::delays::{
+    apply_delay, commit_delay, get_delay, get_future_delay, revert_delay,
+};
+use access_control::emergency::{
+    enable_scoped_emergency_mode, get_emergency_mode, get_emergency_mode_state,
+    toggle_emergency_mode, EmergencyModeState, EmergencyScope,
+};
 use access_control::errors::AccessControlError;
 use access_control::events::Events as AccessControlEvents;
-use access_control::interface::TransferableContract;
+use access_control::interface::{
+    AdminSignersContract, ConfigurableDelaysContract, EmergencyModeContract, RoleTimelockContract,
+    TransferableContract,
+};
 use access_control::management::SingleAddressManagementTrait;
 use access_control::role::{Role, SymbolRepresentation};
-use access_control::transfer::TransferOwnershipTrait;
+use access_control::threshold::{
+    apply_admin_signers, commit_admin_signers, get_admin_cosigners, get_admin_signers,
+    get_future_admin_signers, revert_admin_signers, set_admin_cosigners,
+};
+use access_control::transfer::{
+    apply_role_members_change, apply_role_transfer_delay, commit_role_member_change,
+    commit_role_transfer_delay, get_future_role_transfer_delay, get_pending_role_members_change,
+    get_role_transfer_delay, revert_role_members_change, revert_role_transfer_delay,
+    TransferOwnershipTrait,
+};
 use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
     contract, contractimpl, panic_with_error, Address, Bytes, BytesN, Env, Symbol, Vec,
 };
+use upgrade::allowlist::{
+    apply_wasm_allowlist_change, commit_wasm_allowlist_change, get_allowed_wasms,
+    get_pending_allowlist_changes, is_wasm_allowed, require_wasm_allowed,
+    revert_wasm_allowlist_change, WasmAllowlistChange, WasmKind,
+};
 use upgrade::events::Events as UpgradeEvents;
-use upgrade::interface::UpgradeableContract;
-use upgrade::{apply_upgrade, commit_upgrade, revert_upgrade};
+use upgrade::history::UpgradeRecord;
+use upgrade::interface::{AdminStateContract, UpgradeableContract, WasmAllowlistContract};
+use upgrade::view::{get_admin_state, AdminState};
+use upgrade::{
+    apply_upgrade, commit_upgrade, get_storage_version, get_upgrade_history, migrate,
+    revert_upgrade, rollback_upgrade, set_storage_version,
+};
 
 #[contract]
 pub struct ProviderSwapFeeFactory;
@@ -42,6 +70,7 @@ impl ProviderSwapFeeFactory {
     ) {
         let access_control = AccessControl::new(&e);
         access_control.set_role_address(&Role::Admin, &admin);
+        set_storage_version(&e, Self::version());
         access_control.commit_transfer_ownership(&Role::EmergencyAdmin, &emergency_admin);
         access_control.apply_transfer_ownership(&Role::EmergencyAdmin);
 
@@ -59,6 +88,7 @@ impl ProviderSwapFeeFactory {
     pub fn set_swap_fee_contract_wasm(e: Env, admin: Address, swap_fee_contract_wasm: BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        require_wasm_allowed(&e, &WasmKind::ProviderSwapFee, &swap_fee_contract_wasm);
         set_fee_contract_wasm(&e, &swap_fee_contract_wasm);
         Events::new(&e).set_wasm(swap_fee_contract_wasm);
     }
@@ -130,7 +160,7 @@ impl UpgradeableContract for ProviderSwapFeeFactory {
     fn commit_upgrade(e: Env, admin: Address, new_wasm_hash: BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        commit_upgrade(&e, &new_wasm_hash);
+        commit_upgrade(&e, &WasmKind::ProviderSwapFeeFactory, &new_wasm_hash);
         UpgradeEvents::new(&e).commit_upgrade(Vec::from_array(&e, [new_wasm_hash.clone()]));
     }
 
@@ -146,7 +176,7 @@ impl UpgradeableContract for ProviderSwapFeeFactory {
     fn apply_upgrade(e: Env, admin: Address) -> BytesN<32> {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        let new_wasm_hash = apply_upgrade(&e);
+        let new_wasm_hash = apply_upgrade(&e, &admin);
         UpgradeEvents::new(&e).apply_upgrade(Vec::from_array(&e, [new_wasm_hash.clone()]));
         new_wasm_hash
     }
@@ -164,18 +194,62 @@ impl UpgradeableContract for ProviderSwapFeeFactory {
         UpgradeEvents::new(&e).revert_upgrade();
     }
 
+    // Rolls back the contract to the previously running wasm.
+    // The rollback is applied immediately as it's intended to fix the faulty release.
+    // The previous wasm is known only for the upgrades applied through `apply_upgrade`.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    //
+    // # Returns
+    //
+    // The wasm hash the contract has been rolled back to.
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32> {
+        emergency_admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
+        let wasm_hash = rollback_upgrade(&e, &emergency_admin);
+        UpgradeEvents::new(&e).rollback_upgrade(wasm_hash.clone());
+        wasm_hash
+    }
+
+    // Returns the latest applied upgrades and rollbacks.
+    //
+    // # Returns
+    //
+    // The list of records with wasm hash, timestamp and the admin applied it.
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord> {
+        get_upgrade_history(&e)
+    }
+
+    // Migrates the storage to the current contract version.
+    // Should be called once after every upgrade, state-changing methods are not available until then.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn migrate(e: Env, admin: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        let from_version = migrate(&e, Self::version());
+        UpgradeEvents::new(&e).migrate(from_version, Self::version());
+    }
+
+    // Returns the version of the storage schema.
+    fn get_storage_version(e: Env) -> u32 {
+        get_storage_version(&e)
+    }
+
     // set_emergency_mode
     // Sets or unsets emergency mode for instant upgrades.
+    // Emergency mode enabled this way unlocks all emergency powers and expires after 7 days.
     //
     // Arguments:
     //   - e: The Soroban environment.
     //   - emergency_admin: The emergency admin address (must be authorized).
     //   - value: Boolean indicating whether to enable (true) or disable (false) emergency mode.
     fn set_emergency_mode(e: Env, emergency_admin: Address, value: bool) {
-        emergency_admin.require_auth();
-        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
-        set_emergency_mode(&e, &value);
-        AccessControlEvents::new(&e).set_emergency_mode(value);
+        toggle_emergency_mode(&e, &emergency_admin, value);
     }
 
     // get_emergency_mode
@@ -267,3 +341,315 @@ impl TransferableContract for ProviderSwapFeeFactory {
         }
     }
 }
+
+// The `ConfigurableDelaysContract` trait provides the interface for configuring admin actions delays.
+#[contractimpl]
+impl ConfigurableDelaysContract for ProviderSwapFeeFactory {
+    // Commits new value for the delay: `AdminActions`, `Upgrade` or `WasmAllowlist`.
+    // The value is applied after the current delay passes.
+    fn commit_delay(e: Env, admin: Address, delay_name: Symbol, value: u64) {
+        commit_delay(&e, &admin, delay_name, value)
+    }
+
+    // Applies the committed delay value.
+    fn apply_delay(e: Env, admin: Address, delay_name: Symbol) {
+        apply_delay(&e, &admin, delay_name)
+    }
+
+    // Reverts the committed delay value.
+    fn revert_delay(e: Env, admin: Address, delay_name: Symbol) {
+        revert_delay(&e, &admin, delay_name)
+    }
+
+    // Returns the current delay value in seconds.
+    fn get_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_delay(&e, delay_name)
+    }
+
+    // Returns the committed delay value in seconds. Defaults to the current one if not committed.
+    fn get_future_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_future_delay(&e, delay_name)
+    }
+}
+
+// The `AdminSignersContract` trait provides M-of-N authorization for the admin role.
+#[contractimpl]
+impl AdminSignersContract for ProviderSwapFeeFactory {
+    // Commits the signers set backing the admin role. Empty set switches back to the single admin.
+    fn commit_admin_signers(e: Env, admin: Address, signers: Vec<Address>, threshold: u32) {
+        commit_admin_signers(&e, &admin, &signers, threshold)
+    }
+
+    // Applies the committed admin signers set once the admin transfer delay passes.
+    fn apply_admin_signers(e: Env, admin: Address) {
+        apply_admin_signers(&e, &admin)
+    }
+
+    // Reverts the committed admin signers set.
+    fn revert_admin_signers(e: Env, admin: Address) {
+        revert_admin_signers(&e, &admin)
+    }
+
+    // Returns the admin signers set and threshold. Empty set if not configured.
+    fn get_admin_signers(e: Env) -> (Vec<Address>, u32) {
+        get_admin_signers(&e)
+    }
+
+    // Returns the committed admin signers set and threshold if the change is pending.
+    fn get_future_admin_signers(e: Env) -> Option<(Vec<Address>, u32)> {
+        get_future_admin_signers(&e)
+    }
+
+    // Nominates the admin signers authorizing the admin actions of the signer together with it.
+    fn set_admin_cosigners(e: Env, signer: Address, cosigners: Vec<Address>) {
+        set_admin_cosigners(&e, &signer, &cosigners)
+    }
+
+    // Returns the admin signers nominated by the signer.
+    fn get_admin_cosigners(e: Env, signer: Address) -> Vec<Address> {
+        get_admin_cosigners(&e, &signer)
+    }
+}
+
+// The `RoleTimelockContract` trait provides delayed changes for the rest of the roles.
+#[contractimpl]
+impl RoleTimelockContract for ProviderSwapFeeFactory {
+    // Commits delay between commit and apply of the role transfer or members change.
+    // The value is applied after the current delay of the role passes.
+    // Once delay is set, role can be changed only through the commit-apply process.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    // * `delay` - Delay in seconds. Cannot be less than the minimum delay for the roles
+    //   transferred with mandatory delay like `Admin` and `EmergencyAdmin`.
+    fn commit_role_transfer_delay(e: Env, admin: Address, role_name: Symbol, delay: u64) {
+        commit_role_transfer_delay(&e, &admin, role_name, delay);
+    }
+
+    // Applies the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn apply_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        apply_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Reverts the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn revert_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Returns delay between commit and apply of the role transfer or members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_role_transfer_delay(&e, role_name)
+    }
+
+    // Returns the committed role transfer delay.
+    // The value will be defaulted to the current one if the change is not committed.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_future_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_future_role_transfer_delay(&e, role_name)
+    }
+
+    // Commits address addition to the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be added.
+    fn commit_add_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, true);
+    }
+
+    // Commits address removal from the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be removed.
+    fn commit_remove_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, false);
+    }
+
+    // Applies the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The new list of the role members.
+    fn apply_role_members_change(e: Env, admin: Address, role_name: Symbol) -> Vec<Address> {
+        apply_role_members_change(&e, &admin, role_name)
+    }
+
+    // Reverts the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    fn revert_role_members_change(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_members_change(&e, &admin, role_name);
+    }
+
+    // Returns the committed members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The (address, is_added) pair if change is committed.
+    fn get_pending_role_members_change(e: Env, role_name: Symbol) -> Option<(Address, bool)> {
+        get_pending_role_members_change(&e, role_name)
+    }
+}
+
+// The `AdminStateContract` trait provides the view of the roles and pending admin actions.
+#[contractimpl]
+impl AdminStateContract for ProviderSwapFeeFactory {
+    // Returns the roles state along with the delays, pending admin actions and emergency mode.
+    //
+    // # Returns
+    //
+    // The list of roles with current holders, pending transfers, signers and their deadlines,
+    // the configurable delays, the pending wasm hash with the upgrade deadline,
+    // the pending allowlist changes and contract actions and the emergency mode state.
+    fn get_admin_state(e: Env) -> AdminState {
+        get_admin_state(&e, &[])
+    }
+}
+
+// The `WasmAllowlistContract` trait provides the delayed management of the audited wasm hashes.
+#[contractimpl]
+impl WasmAllowlistContract for ProviderSwapFeeFactory {
+    // Commits allowlist change for the contract kind.
+    // Only allowlisted hashes can be committed for the kind, unconfigured allowlist rejects everything.
+    // Changes are tracked per (kind, hash), so the whole release can be committed at once.
+    // The change is applied after the wasm allowlist delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash to allow or disallow.
+    // * `is_allowed` - Whether the wasm hash should be allowed.
+    fn commit_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+        is_allowed: bool,
+    ) {
+        commit_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash, is_allowed);
+    }
+
+    // Applies the committed allowlist change for the contract kind and wasm hash.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash of the committed change.
+    fn apply_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    ) {
+        apply_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash);
+    }
+
+    // Reverts the committed allowlist change for the contract kind and wasm hash.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash of the committed change.
+    fn revert_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    ) {
+        revert_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash);
+    }
+
+    // Returns the allowed wasm hashes for the contract kind.
+    //
+    // # Arguments
+    //
+    // * `kind_name` - The name of the contract kind.
+    fn get_allowed_wasms(e: Env, kind_name: Symbol) -> Vec<BytesN<32>> {
+        get_allowed_wasms(&e, &WasmKind::from_symbol(&e, kind_name))
+    }
+
+    // Checks whether the wasm hash is allowed for the contract kind.
+    //
+    // # Arguments
+    //
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash to check.
+    fn is_wasm_allowed(e: Env, kind_name: Symbol, wasm_hash: BytesN<32>) -> bool {
+        is_wasm_allowed(&e, &WasmKind::from_symbol(&e, kind_name), &wasm_hash)
+    }
+
+    // Returns the committed allowlist changes along with their deadlines.
+    fn get_pending_wasm_allowlist_changes(e: Env) -> Vec<WasmAllowlistChange> {
+        get_pending_allowlist_changes(&e)
+    }
+}
+
+// The `EmergencyModeContract` trait provides the scoped emergency mode with automatic expiration.
+#[contractimpl]
+impl EmergencyModeContract for ProviderSwapFeeFactory {
+    // Enables the emergency mode for the limited time.
+    // Scope defines what is unlocked, currently the instant upgrade only.
+    // The emergency mode is disabled automatically once expired.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    // * `scope` - The powers unlocked by the emergency mode.
+    // * `duration` - The emergency mode duration in seconds.
+    fn enable_emergency_mode(
+        e: Env,
+        emergency_admin: Address,
+        scope: EmergencyScope,
+        duration: u64,
+    ) {
+        enable_scoped_emergency_mode(&e, &emergency_admin, &scope, duration);
+    }
+
+    // Returns the emergency mode state.
+    //
+    // # Returns
+    //
+    // The emergency mode flag, its scope, expiration timestamp and the remaining time in seconds.
+    fn get_emergency_mode_state(e: Env) -> EmergencyModeState {
+        get_emergency_mode_state(&e)
+    }
+}
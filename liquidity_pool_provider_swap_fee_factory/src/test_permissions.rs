@@ -4,7 +4,7 @@ use crate::testutils::{create_contract, install_swap_fee_collector_wasm, Setup};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{symbol_short, Address, Env, Symbol};
-use utils::test_utils::{install_dummy_wasm, jump};
+use utils::test_utils::{allow_wasm, install_dummy_wasm, jump};
 
 // test admin transfer ownership
 #[test]
@@ -312,9 +312,15 @@ fn test_commit_upgrade_emergency_admin() {
 fn test_commit_upgrade_admin() {
     let setup = Setup::default();
     let contract = setup.contract;
-    assert!(contract
-        .try_commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env))
-        .is_ok());
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "ProviderSwapFeeFactory",
+        &new_wasm,
+    );
+    assert!(contract.try_commit_upgrade(&setup.admin, &new_wasm).is_ok());
 }
 
 #[test]
@@ -322,7 +328,15 @@ fn test_apply_upgrade_third_party_user() {
     let setup = Setup::default();
     let contract = setup.contract;
     let user = Address::generate(&setup.env);
-    contract.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "ProviderSwapFeeFactory",
+        &new_wasm,
+    );
+    contract.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(contract.try_apply_upgrade(&user).is_err());
 }
@@ -331,7 +345,15 @@ fn test_apply_upgrade_third_party_user() {
 fn test_apply_upgrade_emergency_admin() {
     let setup = Setup::default();
     let contract = setup.contract;
-    contract.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "ProviderSwapFeeFactory",
+        &new_wasm,
+    );
+    contract.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(contract.try_apply_upgrade(&setup.emergency_admin).is_err());
 }
@@ -340,7 +362,15 @@ fn test_apply_upgrade_emergency_admin() {
 fn test_apply_upgrade_admin() {
     let setup = Setup::default();
     let contract = setup.contract;
-    contract.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "ProviderSwapFeeFactory",
+        &new_wasm,
+    );
+    contract.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(contract.try_apply_upgrade(&setup.admin).is_ok());
 }
@@ -5,6 +5,7 @@ use soroban_sdk::token::{
     StellarAssetClient as SorobanTokenAdminClient, TokenClient as SorobanTokenClient,
 };
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+use utils::test_utils::allow_wasm;
 
 pub(crate) struct TestConfig {}
 
@@ -61,8 +62,18 @@ impl Setup<'_> {
         let boost_feed = create_reward_boost_feed_contract(&e, &admin);
         let router = deploy_liqpool_router_contract(e.clone());
         router.init_admin(&admin);
+        let stableswap_pool_hash = install_stableswap_liq_pool_hash(&e);
+        allow_wasm(&e, &router.address, &admin, "StandardPool", &pool_hash);
+        allow_wasm(
+            &e,
+            &router.address,
+            &admin,
+            "StableSwapPool",
+            &stableswap_pool_hash,
+        );
+        allow_wasm(&e, &router.address, &admin, "Token", &token_hash);
         router.set_pool_hash(&admin, &pool_hash);
-        router.set_stableswap_pool_hash(&admin, &install_stableswap_liq_pool_hash(&e));
+        router.set_stableswap_pool_hash(&admin, &stableswap_pool_hash);
         router.set_token_hash(&admin, &token_hash);
         router.set_reward_token(&admin, &token_a.address);
         router.set_pools_plane(&admin, &plane);
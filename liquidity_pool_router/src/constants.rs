@@ -3,3 +3,4 @@ pub(crate) const CONSTANT_PRODUCT_FEE_AVAILABLE: [u32; 3] = [10, 30, 100];
 pub(crate) const STABLESWAP_MAX_POOLS: u32 = 3;
 pub(crate) const STABLESWAP_MAX_FEE: u32 = 100; // 1%
 pub(crate) const STABLESWAP_DEFAULT_A: u128 = 750;
+pub(crate) const MAX_ROUTE_HOPS: u32 = 3;
@@ -4,46 +4,89 @@ use crate::events::{Events, LiquidityPoolRouterEvents};
 use crate::liquidity_calculator::LiquidityCalculatorClient;
 use crate::pool_interface::{
     CombinedSwapInterface, LiquidityPoolInterfaceTrait, PoolPlaneInterface, PoolsManagementTrait,
-    RewardsInterfaceTrait,
+    RecipientDeadlineInterface, RewardsInterfaceTrait, RouteFinderInterface,
 };
 use crate::pool_utils::{
-    assert_tokens_sorted, deploy_stableswap_pool, deploy_standard_pool, get_stableswap_pool_salt,
-    get_standard_pool_salt, get_tokens_salt, get_total_liquidity, validate_tokens_contracts,
+    apply_pool_upgrade, assert_deadline, assert_tokens_sorted, commit_pool_upgrade,
+    deploy_stableswap_pool, deploy_standard_pool, deposit_to_pool, get_pool_upgrade_state,
+    get_pool_wasm_kind, get_stableswap_pool_salt, get_standard_pool_salt, get_tokens_salt,
+    get_total_liquidity, migrate_pool, revert_pool_upgrade, validate_tokens_contracts,
+    withdraw_from_pool,
 };
 use crate::rewards::get_rewards_manager;
-use crate::router_interface::AdminInterface;
+use crate::router_interface::{AdminInterface, KillSwitchInterface, PoolsUpgradeInterface};
 use crate::storage::{
     get_init_pool_payment_address, get_init_pool_payment_token,
     get_init_stable_pool_payment_amount, get_init_standard_pool_payment_amount,
-    get_liquidity_calculator, get_pool, get_pool_plane, get_pools_plain, get_reward_tokens,
-    get_reward_tokens_detailed, get_rewards_config, get_tokens_set, get_tokens_set_count, has_pool,
+    get_is_killed_claim, get_is_killed_deposit, get_is_killed_swap, get_liquidity_calculator,
+    get_pool, get_pool_plane, get_pools_by_type, get_pools_plain, get_pools_upgrade_committed,
+    get_pools_upgrade_target, get_reward_tokens, get_reward_tokens_detailed, get_rewards_config,
+    get_tokens_set, get_tokens_set_count, get_tokens_sets_indexed, has_pool, index_tokens_set,
     remove_pool, set_constant_product_pool_hash, set_init_pool_payment_address,
     set_init_pool_payment_token, set_init_stable_pool_payment_amount,
-    set_init_standard_pool_payment_amount, set_liquidity_calculator, set_pool_plane,
-    set_reward_tokens, set_reward_tokens_detailed, set_rewards_config, set_stableswap_pool_hash,
-    set_token_hash, GlobalRewardsConfig, LiquidityPoolRewardInfo,
+    set_init_standard_pool_payment_amount, set_is_killed_claim, set_is_killed_deposit,
+    set_is_killed_swap, set_liquidity_calculator, set_pool_plane, set_pool_upgrade_status,
+    set_pools_upgrade_committed, set_pools_upgrade_target, set_reward_tokens,
+    set_reward_tokens_detailed, set_rewards_config, set_stableswap_pool_hash, set_token_hash,
+    set_tokens_sets_indexed, GlobalRewardsConfig, LiquidityPoolRewardInfo, LiquidityPoolType,
+    PoolUpgradeStatus, PoolsUpgradeTarget, SwapHopEstimate,
+};
+use crate::swap_utils::{
+    estimate_chain, estimate_chain_strict_receive, estimate_chain_strict_receive_detailed,
+    execute_swap_chained, execute_swap_chained_strict_receive, find_best_route,
+    get_routes_token_out, swap_chain, swap_chain_strict_receive,
 };
 use access_control::access::{AccessControl, AccessControlTrait};
-use access_control::emergency::{get_emergency_mode, set_emergency_mode};
+use access_control::delays::{
+    apply_delay, commit_delay, get_delay, get_future_delay, revert_delay,
+};
+use access_control::emergency::{
+    enable_scoped_emergency_mode, get_emergency_mode, get_emergency_mode_state,
+    toggle_emergency_mode, EmergencyModeState, EmergencyScope,
+};
 use access_control::errors::AccessControlError;
 use access_control::events::Events as AccessControlEvents;
-use access_control::interface::TransferableContract;
+use access_control::interface::{
+    AdminSignersContract, ConfigurableDelaysContract, EmergencyModeContract, RoleRegistryContract,
+    RoleTimelockContract, TransferableContract,
+};
 use access_control::management::{MultipleAddressesManagementTrait, SingleAddressManagementTrait};
-use access_control::role::Role;
+use access_control::registry::RoleRegistryTrait;
 use access_control::role::SymbolRepresentation;
-use access_control::transfer::TransferOwnershipTrait;
-use access_control::utils::{require_operations_admin_or_owner, require_rewards_admin_or_owner};
+use access_control::role::{Role, RoleConfig};
+use access_control::threshold::{
+    apply_admin_signers, commit_admin_signers, get_admin_cosigners, get_admin_signers,
+    get_future_admin_signers, revert_admin_signers, set_admin_cosigners,
+};
+use access_control::transfer::{
+    apply_role_members_change, apply_role_transfer_delay, commit_role_member_change,
+    commit_role_transfer_delay, get_future_role_transfer_delay, get_pending_role_members_change,
+    get_role_transfer_delay, revert_role_members_change, revert_role_transfer_delay,
+    TransferOwnershipTrait,
+};
+use access_control::utils::{
+    require_operations_admin_or_owner, require_pause_admin_or_owner,
+    require_pause_or_emergency_pause_admin_or_owner, require_rewards_admin_or_owner,
+};
 use rewards::storage::{BoostFeedStorageTrait, BoostTokenStorageTrait, RewardTokenStorageTrait};
-use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
 use soroban_sdk::token::Client as SorobanTokenClient;
 use soroban_sdk::{
-    contract, contractimpl, panic_with_error, symbol_short, vec, Address, BytesN, Env, IntoVal,
-    Map, Symbol, Val, Vec, U256,
+    contract, contractimpl, panic_with_error, symbol_short, Address, BytesN, Env, IntoVal, Map,
+    Symbol, Val, Vec, U256,
+};
+use upgrade::allowlist::{
+    apply_wasm_allowlist_change, commit_wasm_allowlist_change, get_allowed_wasms,
+    get_pending_allowlist_changes, is_wasm_allowed, require_wasm_allowed,
+    revert_wasm_allowlist_change, WasmAllowlistChange, WasmKind,
 };
 use upgrade::events::Events as UpgradeEvents;
-use upgrade::interface::UpgradeableContract;
-use upgrade::{apply_upgrade, commit_upgrade, revert_upgrade};
-use utils::storage_errors::StorageError;
+use upgrade::history::UpgradeRecord;
+use upgrade::interface::{AdminStateContract, UpgradeableContract, WasmAllowlistContract};
+use upgrade::view::{get_admin_state, AdminState};
+use upgrade::{
+    apply_upgrade, commit_upgrade, get_storage_version, get_upgrade_history, migrate,
+    require_migrated, revert_upgrade, rollback_upgrade, set_storage_version,
+};
 
 #[contract]
 pub struct LiquidityPoolRouter;
@@ -174,24 +217,15 @@ impl LiquidityPoolInterfaceTrait for LiquidityPoolRouter {
         desired_amounts: Vec<u128>,
         min_shares: u128,
     ) -> (Vec<u128>, u128) {
+        require_migrated(&e, Self::version());
         user.require_auth();
-        assert_tokens_sorted(&e, &tokens);
 
-        let pool_id = get_pool(&e, &tokens, pool_index);
+        if get_is_killed_deposit(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::DepositKilled);
+        }
 
-        let (amounts, share_amount): (Vec<u128>, u128) = e.invoke_contract(
-            &pool_id,
-            &symbol_short!("deposit"),
-            Vec::from_array(
-                &e,
-                [
-                    user.clone().into_val(&e),
-                    desired_amounts.into_val(&e),
-                    min_shares.into_val(&e),
-                ],
-            ),
-        );
-        Events::new(&e).deposit(tokens, user, pool_id, amounts.clone(), share_amount);
+        let (_, amounts, share_amount) =
+            deposit_to_pool(&e, &user, &tokens, pool_index, &desired_amounts, min_shares);
         (amounts, share_amount)
     }
 
@@ -221,7 +255,12 @@ impl LiquidityPoolInterfaceTrait for LiquidityPoolRouter {
         in_amount: u128,
         out_min: u128,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
+
+        if get_is_killed_swap(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::SwapKilled);
+        }
         assert_tokens_sorted(&e, &tokens);
         let pool_id = get_pool(&e, &tokens, pool_index);
 
@@ -319,26 +358,10 @@ impl LiquidityPoolInterfaceTrait for LiquidityPoolRouter {
         share_amount: u128,
         min_amounts: Vec<u128>,
     ) -> Vec<u128> {
+        require_migrated(&e, Self::version());
         user.require_auth();
-        assert_tokens_sorted(&e, &tokens);
-
-        let pool_id = get_pool(&e, &tokens, pool_index);
-
-        let amounts: Vec<u128> = e.invoke_contract(
-            &pool_id,
-            &symbol_short!("withdraw"),
-            Vec::from_array(
-                &e,
-                [
-                    user.clone().into_val(&e),
-                    share_amount.into_val(&e),
-                    min_amounts.into_val(&e),
-                ],
-            ),
-        );
 
-        Events::new(&e).withdraw(tokens, user, pool_id, amounts.clone(), share_amount);
-        amounts
+        withdraw_from_pool(&e, &user, &tokens, pool_index, share_amount, &min_amounts)
     }
 
     // Returns the total liquidity of the pool.
@@ -417,7 +440,7 @@ impl UpgradeableContract for LiquidityPoolRouter {
     fn commit_upgrade(e: Env, admin: Address, new_wasm_hash: BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        commit_upgrade(&e, &new_wasm_hash);
+        commit_upgrade(&e, &WasmKind::Router, &new_wasm_hash);
         UpgradeEvents::new(&e).commit_upgrade(Vec::from_array(&e, [new_wasm_hash.clone()]));
     }
 
@@ -429,7 +452,7 @@ impl UpgradeableContract for LiquidityPoolRouter {
     fn apply_upgrade(e: Env, admin: Address) -> BytesN<32> {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        let new_wasm_hash = apply_upgrade(&e);
+        let new_wasm_hash = apply_upgrade(&e, &admin);
         UpgradeEvents::new(&e).apply_upgrade(Vec::from_array(&e, [new_wasm_hash.clone()]));
         new_wasm_hash
     }
@@ -449,8 +472,55 @@ impl UpgradeableContract for LiquidityPoolRouter {
         UpgradeEvents::new(&e).revert_upgrade();
     }
 
+    // Rolls back the contract to the previously running wasm.
+    // The rollback is applied immediately as it's intended to fix the faulty release.
+    // The previous wasm is known only for the upgrades applied through `apply_upgrade`.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    //
+    // # Returns
+    //
+    // The wasm hash the contract has been rolled back to.
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32> {
+        emergency_admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
+        let wasm_hash = rollback_upgrade(&e, &emergency_admin);
+        UpgradeEvents::new(&e).rollback_upgrade(wasm_hash.clone());
+        wasm_hash
+    }
+
+    // Returns the latest applied upgrades and rollbacks.
+    //
+    // # Returns
+    //
+    // The list of records with wasm hash, timestamp and the admin applied it.
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord> {
+        get_upgrade_history(&e)
+    }
+
+    // Migrates the storage to the current contract version.
+    // Should be called once after every upgrade, state-changing methods are not available until then.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn migrate(e: Env, admin: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        let from_version = migrate(&e, Self::version());
+        UpgradeEvents::new(&e).migrate(from_version, Self::version());
+    }
+
+    // Returns the version of the storage schema.
+    fn get_storage_version(e: Env) -> u32 {
+        get_storage_version(&e)
+    }
+
     // Sets the emergency mode.
     // When the emergency mode is set to true, the contract will allow instant upgrades without the delay.
+    // Emergency mode enabled this way unlocks all emergency powers and expires after 7 days.
     // This is useful in case of critical issues that need to be fixed immediately.
     // When the emergency mode is set to false, the contract will require the standard upgrade delay.
     // The emergency mode can only be set by the emergency admin.
@@ -460,10 +530,7 @@ impl UpgradeableContract for LiquidityPoolRouter {
     // * `emergency_admin` - The address of the emergency admin.
     // * `value` - The value to set the emergency mode to.
     fn set_emergency_mode(e: Env, emergency_admin: Address, value: bool) {
-        emergency_admin.require_auth();
-        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
-        set_emergency_mode(&e, &value);
-        AccessControlEvents::new(&e).set_emergency_mode(value);
+        toggle_emergency_mode(&e, &emergency_admin, value);
     }
 
     // Returns the emergency mode flag value.
@@ -486,6 +553,7 @@ impl AdminInterface for LiquidityPoolRouter {
             panic_with_error!(&e, AccessControlError::AdminAlreadySet);
         }
         access_control.set_role_address(&Role::Admin, &account);
+        set_storage_version(&e, Self::version());
     }
 
     // Sets the privileged addresses.
@@ -561,6 +629,7 @@ impl AdminInterface for LiquidityPoolRouter {
     fn set_token_hash(e: Env, admin: Address, new_hash: BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        require_wasm_allowed(&e, &WasmKind::Token, &new_hash);
         set_token_hash(&e, &new_hash);
     }
 
@@ -572,6 +641,7 @@ impl AdminInterface for LiquidityPoolRouter {
     fn set_pool_hash(e: Env, admin: Address, new_hash: BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        require_wasm_allowed(&e, &WasmKind::StandardPool, &new_hash);
         set_constant_product_pool_hash(&e, &new_hash);
     }
 
@@ -583,6 +653,7 @@ impl AdminInterface for LiquidityPoolRouter {
     fn set_stableswap_pool_hash(e: Env, admin: Address, new_hash: BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        require_wasm_allowed(&e, &WasmKind::StableSwapPool, &new_hash);
         set_stableswap_pool_hash(&e, &new_hash);
     }
 
@@ -740,6 +811,7 @@ impl RewardsInterfaceTrait for LiquidityPoolRouter {
         expired_at: u64,  // timestamp
         tokens_votes: Vec<(Vec<Address>, u32)>, // {[token1, token2]: voting_percentage}, voting percentage 0_0000000 .. 1_0000000
     ) {
+        require_migrated(&e, Self::version());
         user.require_auth();
         require_rewards_admin_or_owner(&e, &user);
 
@@ -780,6 +852,7 @@ impl RewardsInterfaceTrait for LiquidityPoolRouter {
     //
     // * `tokens` - A vector of token addresses for which to fill the liquidity.
     fn fill_liquidity(e: Env, tokens: Vec<Address>) {
+        require_migrated(&e, Self::version());
         assert_tokens_sorted(&e, &tokens);
         let tokens_salt = get_tokens_salt(&e, &tokens);
         let calculator = get_liquidity_calculator(&e);
@@ -827,6 +900,7 @@ impl RewardsInterfaceTrait for LiquidityPoolRouter {
     // * The tokens are not found in the current rewards configuration.
     // * The liquidity for the tokens has not been filled.
     fn config_pool_rewards(e: Env, tokens: Vec<Address>, pool_index: BytesN<32>) -> u128 {
+        require_migrated(&e, Self::version());
         assert_tokens_sorted(&e, &tokens);
         let pool_id = get_pool(&e, &tokens, pool_index.clone());
 
@@ -1063,6 +1137,7 @@ impl RewardsInterfaceTrait for LiquidityPoolRouter {
         tokens: Vec<Address>,
         pool_index: BytesN<32>,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
         require_rewards_admin_or_owner(&e, &user);
         assert_tokens_sorted(&e, &tokens);
@@ -1104,7 +1179,12 @@ impl RewardsInterfaceTrait for LiquidityPoolRouter {
     //
     // The amount of tokens rewarded to the user as a u128.
     fn claim(e: Env, user: Address, tokens: Vec<Address>, pool_index: BytesN<32>) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
+
+        if get_is_killed_claim(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::ClaimKilled);
+        }
         assert_tokens_sorted(&e, &tokens);
 
         let pool_id = get_pool(&e, &tokens, pool_index);
@@ -1149,6 +1229,7 @@ impl PoolsManagementTrait for LiquidityPoolRouter {
         tokens: Vec<Address>,
         fee_fraction: u32,
     ) -> (BytesN<32>, Address) {
+        require_migrated(&e, Self::version());
         user.require_auth();
         validate_tokens_contracts(&e, &tokens);
         assert_tokens_sorted(&e, &tokens);
@@ -1200,6 +1281,7 @@ impl PoolsManagementTrait for LiquidityPoolRouter {
         tokens: Vec<Address>,
         fee_fraction: u32,
     ) -> (BytesN<32>, Address) {
+        require_migrated(&e, Self::version());
         user.require_auth();
         validate_tokens_contracts(&e, &tokens);
         assert_tokens_sorted(&e, &tokens);
@@ -1261,6 +1343,7 @@ impl PoolsManagementTrait for LiquidityPoolRouter {
     //
     // A map of pool index hashes to pool addresses.
     fn remove_pool(e: Env, user: Address, tokens: Vec<Address>, pool_hash: BytesN<32>) {
+        require_migrated(&e, Self::version());
         user.require_auth();
         require_operations_admin_or_owner(&e, &user);
         assert_tokens_sorted(&e, &tokens);
@@ -1369,106 +1452,22 @@ impl CombinedSwapInterface for LiquidityPoolRouter {
         in_amount: u128,
         out_min: u128,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
-        let mut last_token_out: Option<Address> = None;
-        let mut last_swap_result = 0;
 
-        if swaps_chain.len() == 0 {
-            panic_with_error!(&e, LiquidityPoolRouterError::PathIsEmpty);
+        if get_is_killed_swap(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::SwapKilled);
         }
 
-        SorobanTokenClient::new(&e, &token_in).transfer(
+        execute_swap_chained(
+            &e,
             &user,
-            &e.current_contract_address(),
-            &(in_amount as i128),
-        );
-
-        for i in 0..swaps_chain.len() {
-            let (tokens, pool_index, token_out) = swaps_chain.get(i).unwrap();
-            assert_tokens_sorted(&e, &tokens);
-
-            let pool_id = get_pool(&e, &tokens, pool_index);
-
-            let mut out_min_local = 0;
-            let token_in_local;
-            let in_amount_local;
-            if i == 0 {
-                token_in_local = token_in.clone();
-                in_amount_local = in_amount;
-            } else {
-                token_in_local = match last_token_out {
-                    Some(v) => v,
-                    None => panic_with_error!(&e, StorageError::ValueNotInitialized),
-                };
-                in_amount_local = last_swap_result;
-            }
-
-            if i == swaps_chain.len() - 1 {
-                out_min_local = out_min;
-            }
-
-            e.authorize_as_current_contract(vec![
-                &e,
-                InvokerContractAuthEntry::Contract(SubContractInvocation {
-                    context: ContractContext {
-                        contract: token_in_local.clone(),
-                        fn_name: Symbol::new(&e, "transfer"),
-                        args: (
-                            e.current_contract_address(),
-                            pool_id.clone(),
-                            in_amount_local as i128,
-                        )
-                            .into_val(&e),
-                    },
-                    sub_invocations: vec![&e],
-                }),
-            ]);
-
-            last_swap_result = e.invoke_contract(
-                &pool_id,
-                &symbol_short!("swap"),
-                Vec::from_array(
-                    &e,
-                    [
-                        e.current_contract_address().into_val(&e),
-                        tokens
-                            .first_index_of(token_in_local.clone())
-                            .unwrap()
-                            .into_val(&e),
-                        tokens
-                            .first_index_of(token_out.clone())
-                            .unwrap()
-                            .into_val(&e),
-                        in_amount_local.into_val(&e),
-                        out_min_local.into_val(&e),
-                    ],
-                ),
-            );
-
-            Events::new(&e).swap(
-                tokens,
-                user.clone(),
-                pool_id,
-                token_in_local.clone(),
-                token_out.clone(),
-                in_amount_local,
-                last_swap_result,
-            );
-
-            last_token_out = Some(token_out);
-        }
-
-        let token_out_address = match last_token_out {
-            Some(v) => v,
-            None => panic_with_error!(&e, StorageError::ValueNotInitialized),
-        };
-        SorobanTokenClient::new(&e, &token_out_address).transfer(
-            &e.current_contract_address(),
+            &swaps_chain,
+            &token_in,
+            in_amount,
+            out_min,
             &user,
-            &(last_swap_result as i128),
-        );
-
-        last_swap_result
+        )
     }
 
     // Executes a chain of token swaps to exchange an input token for an output token.
@@ -1496,239 +1495,481 @@ impl CombinedSwapInterface for LiquidityPoolRouter {
         out_amount: u128, // fixed amount of output token to receive
         max_in: u128,     // maximum input token amount allowed
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
-        if swaps_chain.len() == 0 {
-            panic_with_error!(&e, LiquidityPoolRouterError::PathIsEmpty);
+        if get_is_killed_swap(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::SwapKilled);
         }
 
-        // -------------------------------
-        // Reverse pass: compute required inputs per hop
-        // -------------------------------
-        let mut required_amounts: Vec<u128> = Vec::new(&e);
-        let mut desired_out = out_amount;
-
-        let estimate_fn = Symbol::new(&e, "estimate_swap_strict_receive");
-        let swap_fn = Symbol::new(&e, "swap_strict_receive");
-
-        // Process swaps in reverse order
-        for i in (0..swaps_chain.len()).rev() {
-            let (tokens, pool_index, token_out) = swaps_chain.get(i).unwrap();
-            let pool_id = get_pool(&e, &tokens, pool_index);
-            let token_in_for_hop = if i == 0 {
-                token_in.clone()
-            } else {
-                // For a middle hop, the input is the output of the previous swap in the chain.
-                swaps_chain.get(i - 1).unwrap().2.clone()
-            };
-
-            // Calculate required input for this hop using pool pricing.
-            // Assumes the pool has a function like `calc_in_given_out`.
-            let required_in: u128 = e.invoke_contract(
-                &pool_id,
-                &estimate_fn,
-                Vec::from_array(
-                    &e,
-                    [
-                        tokens
-                            .first_index_of(token_in_for_hop.clone())
-                            .unwrap()
-                            .into_val(&e),
-                        tokens
-                            .first_index_of(token_out.clone())
-                            .unwrap()
-                            .into_val(&e),
-                        desired_out.into_val(&e),
-                    ],
-                ),
-            );
-            required_amounts.push_front(required_in);
-            // The output required from the previous hop is the input needed here.
-            desired_out = required_in;
+        execute_swap_chained_strict_receive(
+            &e,
+            &user,
+            &swaps_chain,
+            &token_in,
+            out_amount,
+            max_in,
+            &user,
+        )
+    }
+
+    // Splits the input amount between several routes and executes them in a single transaction.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user executing the swaps.
+    // * `routes` - The routes to be executed. Each route is represented by a tuple containing:
+    //   - The series of swaps, same as `swaps_chain` in `swap_chained`
+    //   - The amount of the input token to be swapped through the route
+    //   All routes should end with the same output token.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `out_min` - The minimum total amount of the output token to be received.
+    //
+    // # Returns
+    //
+    // The total amount of the output token received after all routes have been executed.
+    fn swap_split(
+        e: Env,
+        user: Address,
+        routes: Vec<(Vec<(Vec<Address>, BytesN<32>, Address)>, u128)>,
+        token_in: Address,
+        out_min: u128,
+    ) -> u128 {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+
+        if get_is_killed_swap(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::SwapKilled);
         }
-        let total_required_input = required_amounts.get_unchecked(0);
 
-        // Verify that the required input does not exceed the maximum provided.
-        if total_required_input > max_in {
-            panic_with_error!(&e, LiquidityPoolRouterError::InMaxNotSatisfied);
+        let token_out = get_routes_token_out(&e, &routes);
+
+        let mut in_amount = 0;
+        for (_, route_in_amount) in routes.iter() {
+            in_amount += route_in_amount;
         }
 
-        // -------------------------------
-        // Forward pass: execute the swaps
-        // -------------------------------
-        // Pull the maximum required input from the user.
         SorobanTokenClient::new(&e, &token_in).transfer(
             &user,
             &e.current_contract_address(),
-            &(max_in as i128),
+            &(in_amount as i128),
+        );
+
+        let mut out_amount = 0;
+        for (swaps_chain, route_in_amount) in routes.iter() {
+            out_amount += swap_chain(&e, &user, &swaps_chain, &token_in, route_in_amount, 0);
+        }
+
+        if out_amount < out_min {
+            panic_with_error!(&e, LiquidityPoolRouterError::OutMinNotSatisfied);
+        }
+
+        SorobanTokenClient::new(&e, &token_out).transfer(
+            &e.current_contract_address(),
+            &user,
+            &(out_amount as i128),
         );
-        // Return back the difference
-        if max_in > total_required_input {
+
+        out_amount
+    }
+
+    // Splits the output amount between several routes and executes them in a single transaction.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user executing the swaps.
+    // * `routes` - The routes to be executed. Each route is represented by a tuple containing:
+    //   - The series of swaps, same as `swaps_chain` in `swap_chained_strict_receive`
+    //   - The amount of the output token to be received through the route
+    //   All routes should end with the same output token.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `in_max` - The max total amount of the input token to spend.
+    //
+    // # Returns
+    //
+    // The total amount of the input token spent after all routes have been executed.
+    fn swap_split_strict_receive(
+        e: Env,
+        user: Address,
+        routes: Vec<(Vec<(Vec<Address>, BytesN<32>, Address)>, u128)>,
+        token_in: Address,
+        in_max: u128,
+    ) -> u128 {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+
+        if get_is_killed_swap(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::SwapKilled);
+        }
+
+        let token_out = get_routes_token_out(&e, &routes);
+
+        // Routes may share pools, so every route is estimated right before execution
+        //  against the reserves left by the previous routes.
+        let mut total_required_input = 0;
+        let mut total_out_amount = 0;
+        for (swaps_chain, route_out_amount) in routes.iter() {
+            let route_required_amounts =
+                estimate_chain_strict_receive(&e, &swaps_chain, &token_in, route_out_amount);
+            let route_required_input = route_required_amounts.get_unchecked(0);
+            total_required_input += route_required_input;
+            total_out_amount += route_out_amount;
+
+            if total_required_input > in_max {
+                panic_with_error!(&e, LiquidityPoolRouterError::InMaxNotSatisfied);
+            }
+
             SorobanTokenClient::new(&e, &token_in).transfer(
-                &e.current_contract_address(),
                 &user,
-                &((max_in - total_required_input) as i128),
+                &e.current_contract_address(),
+                &(route_required_input as i128),
             );
-        }
-
-        let mut current_in = total_required_input;
-        let mut last_token_out: Option<Address> = None;
-
-        // Execute each swap in sequence.
-        for i in 0..swaps_chain.len() {
-            let (tokens, pool_index, token_out) = swaps_chain.get(i).unwrap();
-            let pool_id = get_pool(&e, &tokens, pool_index);
-            let token_in_local = if i == 0 {
-                token_in.clone()
-            } else {
-                last_token_out.unwrap()
-            };
-
-            // Set the minimum acceptable output for this hop.
-            // For intermediate hops, this is the required amount computed for the next swap.
-            // For the final hop, it is the desired `out_amount`.
-            let out_local = if i == swaps_chain.len() - 1 {
-                out_amount
-            } else {
-                required_amounts.get_unchecked(i + 1)
-            };
-
-            // Authorize and perform the swap.
-            e.authorize_as_current_contract(vec![
+            swap_chain_strict_receive(
                 &e,
-                InvokerContractAuthEntry::Contract(SubContractInvocation {
-                    context: ContractContext {
-                        contract: token_in_local.clone(),
-                        fn_name: Symbol::new(&e, "transfer"),
-                        args: (
-                            e.current_contract_address(),
-                            pool_id.clone(),
-                            current_in as i128,
-                        )
-                            .into_val(&e),
-                    },
-                    sub_invocations: vec![&e],
-                }),
-            ]);
-
-            let in_local: u128 = e.invoke_contract(
-                &pool_id,
-                &swap_fn,
-                Vec::from_array(
-                    &e,
-                    [
-                        e.current_contract_address().into_val(&e),
-                        tokens
-                            .first_index_of(token_in_local.clone())
-                            .unwrap()
-                            .into_val(&e),
-                        tokens
-                            .first_index_of(token_out.clone())
-                            .unwrap()
-                            .into_val(&e),
-                        out_local.into_val(&e),
-                        current_in.into_val(&e),
-                    ],
-                ),
-            );
-
-            // Emit an event for the swap.
-            Events::new(&e).swap(
-                tokens,
-                user.clone(),
-                pool_id,
-                token_in_local.clone(),
-                token_out.clone(),
-                in_local,
-                out_local,
+                &user,
+                &swaps_chain,
+                &token_in,
+                route_out_amount,
+                &route_required_amounts,
             );
-
-            current_in = out_local;
-            last_token_out = Some(token_out);
         }
 
-        // Finally, transfer the received output tokens to the user.
-        let final_token = last_token_out.unwrap();
-        SorobanTokenClient::new(&e, &final_token).transfer(
+        SorobanTokenClient::new(&e, &token_out).transfer(
             &e.current_contract_address(),
             &user,
-            &(current_in as i128),
+            &(total_out_amount as i128),
         );
 
         total_required_input
     }
-}
 
-// The `TransferableContract` trait provides the interface for transferring ownership of the contract.
-#[contractimpl]
-impl TransferableContract for LiquidityPoolRouter {
-    // Commits an ownership transfer.
+    // Estimates a chain of token swaps to exchange an input token for an output token.
     //
     // # Arguments
     //
-    // * `admin` - The address of the admin.
-    // * `role_name` - The name of the role to transfer ownership of. The role must be one of the following:
-    //     * `Admin`
-    //     * `EmergencyAdmin`
-    // * `new_address` - New address for the role
-    fn commit_transfer_ownership(e: Env, admin: Address, role_name: Symbol, new_address: Address) {
-        admin.require_auth();
-        let access_control = AccessControl::new(&e);
-        access_control.assert_address_has_role(&admin, &Role::Admin);
+    // * `swaps_chain` - The series of swaps to be estimated, same as in `swap_chained`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `in_amount` - The amount of the input token to be swapped.
+    //
+    // # Returns
+    //
+    // The estimated amounts and fee for each swap in the chain.
+    fn estimate_swap_chained(
+        e: Env,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        in_amount: u128,
+    ) -> Vec<SwapHopEstimate> {
+        estimate_chain(&e, &swaps_chain, &token_in, in_amount)
+    }
 
-        let role = Role::from_symbol(&e, role_name);
-        access_control.commit_transfer_ownership(&role, &new_address);
-        AccessControlEvents::new(&e).commit_transfer_ownership(role, new_address);
+    // Estimates a chain of token swaps with strict receive.
+    //
+    // # Arguments
+    //
+    // * `swaps_chain` - The series of swaps to be estimated, same as in `swap_chained_strict_receive`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `out_amount` - The amount of the output token to be received.
+    //
+    // # Returns
+    //
+    // The estimated amounts and fee for each swap in the chain.
+    fn estimate_swap_chained_strict_receive(
+        e: Env,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        out_amount: u128,
+    ) -> Vec<SwapHopEstimate> {
+        estimate_chain_strict_receive_detailed(&e, &swaps_chain, &token_in, out_amount)
     }
+}
 
-    // Applies the committed ownership transfer.
+// The `RecipientDeadlineInterface` trait provides variants of the user operations with a deadline and a recipient.
+#[contractimpl]
+impl RecipientDeadlineInterface for LiquidityPoolRouter {
+    // Executes a chain of token swaps and sends the output token to the recipient.
     //
     // # Arguments
     //
-    // * `admin` - The address of the admin.
-    // * `role_name` - The name of the role to transfer ownership of. The role must be one of the following:
-    //     * `Admin`
-    //     * `EmergencyAdmin`
-    fn apply_transfer_ownership(e: Env, admin: Address, role_name: Symbol) {
-        admin.require_auth();
-        let access_control = AccessControl::new(&e);
-        access_control.assert_address_has_role(&admin, &Role::Admin);
+    // * `user` - The address of the user executing the swaps.
+    // * `swaps_chain` - The series of swaps to be executed, same as in `swap_chained`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `in_amount` - The amount of the input token to be swapped.
+    // * `out_min` - The minimum amount of the output token to be received.
+    // * `to` - The address receiving the output token.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // The amount of the output token received after all swaps have been executed.
+    fn swap_chained_to(
+        e: Env,
+        user: Address,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        in_amount: u128,
+        out_min: u128,
+        to: Address,
+        deadline: u64,
+    ) -> u128 {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+        assert_deadline(&e, deadline);
 
-        let role = Role::from_symbol(&e, role_name);
-        let new_address = access_control.apply_transfer_ownership(&role);
-        AccessControlEvents::new(&e).apply_transfer_ownership(role, new_address);
+        if get_is_killed_swap(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::SwapKilled);
+        }
+
+        execute_swap_chained(&e, &user, &swaps_chain, &token_in, in_amount, out_min, &to)
     }
 
-    // Reverts the committed ownership transfer.
+    // Executes a chain of token swaps with strict receive and sends the output token to the recipient.
+    // Unspent input is returned to the user.
     //
     // # Arguments
     //
-    // * `admin` - The address of the admin.
-    // * `role_name` - The name of the role to transfer ownership of. The role must be one of the following:
-    //     * `Admin`
-    //     * `EmergencyAdmin`
-    fn revert_transfer_ownership(e: Env, admin: Address, role_name: Symbol) {
-        admin.require_auth();
-        let access_control = AccessControl::new(&e);
-        access_control.assert_address_has_role(&admin, &Role::Admin);
+    // * `user` - The address of the user executing the swaps.
+    // * `swaps_chain` - The series of swaps to be executed, same as in `swap_chained_strict_receive`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `out_amount` - The amount of the output token to be received.
+    // * `in_max` - The max amount of the input token to spend.
+    // * `to` - The address receiving the output token.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // The amount of the input token spent after all swaps have been executed.
+    fn swap_chained_strict_receive_to(
+        e: Env,
+        user: Address,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        out_amount: u128,
+        in_max: u128,
+        to: Address,
+        deadline: u64,
+    ) -> u128 {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+        assert_deadline(&e, deadline);
 
-        let role = Role::from_symbol(&e, role_name);
-        access_control.revert_transfer_ownership(&role);
-        AccessControlEvents::new(&e).revert_transfer_ownership(role);
+        if get_is_killed_swap(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::SwapKilled);
+        }
+
+        execute_swap_chained_strict_receive(
+            &e,
+            &user,
+            &swaps_chain,
+            &token_in,
+            out_amount,
+            in_max,
+            &to,
+        )
     }
 
-    // Returns the future address for the role.
-    // The future address is the address that the ownership of the role will be transferred to.
-    // The future address is set using the `commit_transfer_ownership` function.
-    // The address will be defaulted to the current address if the transfer is not committed.
+    // Deposits tokens into the pool and sends the minted shares to the recipient.
     //
     // # Arguments
     //
-    // * `role_name` - The name of the role to get the future address for. The role must be one of the following:
-    //    * `Admin`
-    //    * `EmergencyAdmin`
-    fn get_future_address(e: Env, role_name: Symbol) -> Address {
-        let access_control = AccessControl::new(&e);
+    // * `user` - The address of the user depositing the tokens.
+    // * `tokens` - A vector of token addresses.
+    // * `pool_index` - The pool index hash.
+    // * `desired_amounts` - A vector of desired amounts of each token to deposit.
+    // * `min_shares` - The minimum amount of pool tokens to mint.
+    // * `to` - The address receiving the pool tokens.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // A tuple containing the amounts of each token deposited and the amount of pool tokens minted.
+    fn deposit_to(
+        e: Env,
+        user: Address,
+        tokens: Vec<Address>,
+        pool_index: BytesN<32>,
+        desired_amounts: Vec<u128>,
+        min_shares: u128,
+        to: Address,
+        deadline: u64,
+    ) -> (Vec<u128>, u128) {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+        assert_deadline(&e, deadline);
+
+        if get_is_killed_deposit(&e) {
+            panic_with_error!(&e, LiquidityPoolRouterError::DepositKilled);
+        }
+
+        let (pool_id, amounts, share_amount) =
+            deposit_to_pool(&e, &user, &tokens, pool_index, &desired_amounts, min_shares);
+
+        // pool always mints shares to the depositor
+        if to != user {
+            let share_token: Address =
+                e.invoke_contract(&pool_id, &Symbol::new(&e, "share_id"), Vec::new(&e));
+            SorobanTokenClient::new(&e, &share_token).transfer(&user, &to, &(share_amount as i128));
+        }
+        (amounts, share_amount)
+    }
+
+    // Withdraws tokens from the pool and sends them to the recipient.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user withdrawing the tokens.
+    // * `tokens` - A vector of token addresses.
+    // * `pool_index` - The pool index hash.
+    // * `share_amount` - The amount of pool tokens to burn.
+    // * `min_amounts` - A vector of minimum amounts of each token to be received.
+    // * `to` - The address receiving the withdrawn tokens.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // A vector of amounts of each token withdrawn.
+    fn withdraw_to(
+        e: Env,
+        user: Address,
+        tokens: Vec<Address>,
+        pool_index: BytesN<32>,
+        share_amount: u128,
+        min_amounts: Vec<u128>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<u128> {
+        require_migrated(&e, Self::version());
+        user.require_auth();
+        assert_deadline(&e, deadline);
+
+        let amounts =
+            withdraw_from_pool(&e, &user, &tokens, pool_index, share_amount, &min_amounts);
+
+        // pool always sends tokens to the share holder
+        if to != user {
+            for i in 0..tokens.len() {
+                let amount = amounts.get(i).unwrap();
+                if amount > 0 {
+                    SorobanTokenClient::new(&e, &tokens.get(i).unwrap()).transfer(
+                        &user,
+                        &to,
+                        &(amount as i128),
+                    );
+                }
+            }
+        }
+        amounts
+    }
+}
+
+#[contractimpl]
+impl RouteFinderInterface for LiquidityPoolRouter {
+    // Finds the chain of swaps with the best output using pools data stored in the plane.
+    // Quotes are calculated by the liquidity calculator, so the actual result may slightly differ.
+    //
+    // # Arguments
+    //
+    // * `token_in` - The address of the input token.
+    // * `token_out` - The address of the output token.
+    // * `in_amount` - The amount of the input token to be swapped.
+    // * `max_hops` - The maximum number of swaps in the chain.
+    //
+    // # Returns
+    //
+    // The chain of swaps which can be passed to `swap_chained` and the expected output amount.
+    fn find_best_route(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        in_amount: u128,
+        max_hops: u32,
+    ) -> (Vec<(Vec<Address>, BytesN<32>, Address)>, u128) {
+        find_best_route(&e, &token_in, &token_out, in_amount, max_hops)
+    }
+
+    // Indexes tokens sets created before the route finder was introduced by the tokens they contain.
+    // New tokens sets are indexed on creation once the index is complete.
+    //
+    // # Arguments
+    //
+    // * `limit` - The maximum number of tokens sets to be indexed.
+    //
+    // # Returns
+    //
+    // The number of tokens sets indexed so far.
+    fn fill_tokens_sets_index(e: Env, limit: u32) -> u128 {
+        let indexed = get_tokens_sets_indexed(&e);
+        let end = (indexed + limit as u128).min(get_tokens_set_count(&e));
+        for index in indexed..end {
+            index_tokens_set(&e, index, &get_tokens_set(&e, index));
+        }
+        set_tokens_sets_indexed(&e, &end);
+        end
+    }
+}
+
+// The `TransferableContract` trait provides the interface for transferring ownership of the contract.
+#[contractimpl]
+impl TransferableContract for LiquidityPoolRouter {
+    // Commits an ownership transfer.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role to transfer ownership of. Any single address role.
+    // * `new_address` - New address for the role
+    fn commit_transfer_ownership(e: Env, admin: Address, role_name: Symbol, new_address: Address) {
+        admin.require_auth();
+        let access_control = AccessControl::new(&e);
+        access_control.assert_address_has_role(&admin, &Role::Admin);
+
+        let role = Role::from_symbol(&e, role_name);
+        access_control.commit_transfer_ownership(&role, &new_address);
+        AccessControlEvents::new(&e).commit_transfer_ownership(role, new_address);
+    }
+
+    // Applies the committed ownership transfer.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role to transfer ownership of. Any single address role.
+    fn apply_transfer_ownership(e: Env, admin: Address, role_name: Symbol) {
+        admin.require_auth();
+        let access_control = AccessControl::new(&e);
+        access_control.assert_address_has_role(&admin, &Role::Admin);
+
+        let role = Role::from_symbol(&e, role_name);
+        let new_address = access_control.apply_transfer_ownership(&role);
+        AccessControlEvents::new(&e).apply_transfer_ownership(role, new_address);
+    }
+
+    // Reverts the committed ownership transfer.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role to transfer ownership of. Any single address role.
+    fn revert_transfer_ownership(e: Env, admin: Address, role_name: Symbol) {
+        admin.require_auth();
+        let access_control = AccessControl::new(&e);
+        access_control.assert_address_has_role(&admin, &Role::Admin);
+
+        let role = Role::from_symbol(&e, role_name);
+        access_control.revert_transfer_ownership(&role);
+        AccessControlEvents::new(&e).revert_transfer_ownership(role);
+    }
+
+    // Returns the future address for the role.
+    // The future address is the address that the ownership of the role will be transferred to.
+    // The future address is set using the `commit_transfer_ownership` function.
+    // The address will be defaulted to the current address if the transfer is not committed.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role to get the future address for. Any single address role.
+    fn get_future_address(e: Env, role_name: Symbol) -> Address {
+        let access_control = AccessControl::new(&e);
         let role = Role::from_symbol(&e, role_name);
         match access_control.get_transfer_ownership_deadline(&role) {
             0 => match access_control.get_role_safe(&role) {
@@ -1739,3 +1980,665 @@ impl TransferableContract for LiquidityPoolRouter {
         }
     }
 }
+
+// The `ConfigurableDelaysContract` trait provides the interface for configuring admin actions delays.
+#[contractimpl]
+impl ConfigurableDelaysContract for LiquidityPoolRouter {
+    // Commits new value for the delay: `AdminActions`, `Upgrade` or `WasmAllowlist`.
+    // The value is applied after the current delay passes.
+    fn commit_delay(e: Env, admin: Address, delay_name: Symbol, value: u64) {
+        commit_delay(&e, &admin, delay_name, value)
+    }
+
+    // Applies the committed delay value.
+    fn apply_delay(e: Env, admin: Address, delay_name: Symbol) {
+        apply_delay(&e, &admin, delay_name)
+    }
+
+    // Reverts the committed delay value.
+    fn revert_delay(e: Env, admin: Address, delay_name: Symbol) {
+        revert_delay(&e, &admin, delay_name)
+    }
+
+    // Returns the current delay value in seconds.
+    fn get_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_delay(&e, delay_name)
+    }
+
+    // Returns the committed delay value in seconds. Defaults to the current one if not committed.
+    fn get_future_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_future_delay(&e, delay_name)
+    }
+}
+
+// The `AdminSignersContract` trait provides M-of-N authorization for the admin role.
+#[contractimpl]
+impl AdminSignersContract for LiquidityPoolRouter {
+    // Commits the signers set backing the admin role. Empty set switches back to the single admin.
+    fn commit_admin_signers(e: Env, admin: Address, signers: Vec<Address>, threshold: u32) {
+        commit_admin_signers(&e, &admin, &signers, threshold)
+    }
+
+    // Applies the committed admin signers set once the admin transfer delay passes.
+    fn apply_admin_signers(e: Env, admin: Address) {
+        apply_admin_signers(&e, &admin)
+    }
+
+    // Reverts the committed admin signers set.
+    fn revert_admin_signers(e: Env, admin: Address) {
+        revert_admin_signers(&e, &admin)
+    }
+
+    // Returns the admin signers set and threshold. Empty set if not configured.
+    fn get_admin_signers(e: Env) -> (Vec<Address>, u32) {
+        get_admin_signers(&e)
+    }
+
+    // Returns the committed admin signers set and threshold if the change is pending.
+    fn get_future_admin_signers(e: Env) -> Option<(Vec<Address>, u32)> {
+        get_future_admin_signers(&e)
+    }
+
+    // Nominates the admin signers authorizing the admin actions of the signer together with it.
+    fn set_admin_cosigners(e: Env, signer: Address, cosigners: Vec<Address>) {
+        set_admin_cosigners(&e, &signer, &cosigners)
+    }
+
+    // Returns the admin signers nominated by the signer.
+    fn get_admin_cosigners(e: Env, signer: Address) -> Vec<Address> {
+        get_admin_cosigners(&e, &signer)
+    }
+}
+
+// The `AdminStateContract` trait provides the view of the roles and pending admin actions.
+#[contractimpl]
+impl AdminStateContract for LiquidityPoolRouter {
+    // Returns the roles state along with the delays, pending admin actions and emergency mode.
+    //
+    // # Returns
+    //
+    // The list of roles with current holders, pending transfers, signers and their deadlines,
+    // the configurable delays, the pending wasm hash with the upgrade deadline,
+    // the pending allowlist changes and contract actions and the emergency mode state.
+    fn get_admin_state(e: Env) -> AdminState {
+        get_admin_state(&e, &[])
+    }
+}
+
+// The `WasmAllowlistContract` trait provides the delayed management of the audited wasm hashes.
+#[contractimpl]
+impl WasmAllowlistContract for LiquidityPoolRouter {
+    // Commits allowlist change for the contract kind.
+    // Only allowlisted hashes can be committed for the kind, unconfigured allowlist rejects everything.
+    // Changes are tracked per (kind, hash), so the whole release can be committed at once.
+    // The change is applied after the wasm allowlist delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash to allow or disallow.
+    // * `is_allowed` - Whether the wasm hash should be allowed.
+    fn commit_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+        is_allowed: bool,
+    ) {
+        commit_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash, is_allowed);
+    }
+
+    // Applies the committed allowlist change for the contract kind and wasm hash.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash of the committed change.
+    fn apply_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    ) {
+        apply_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash);
+    }
+
+    // Reverts the committed allowlist change for the contract kind and wasm hash.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash of the committed change.
+    fn revert_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    ) {
+        revert_wasm_allowlist_change(&e, &admin, kind_name, &wasm_hash);
+    }
+
+    // Returns the allowed wasm hashes for the contract kind.
+    //
+    // # Arguments
+    //
+    // * `kind_name` - The name of the contract kind.
+    fn get_allowed_wasms(e: Env, kind_name: Symbol) -> Vec<BytesN<32>> {
+        get_allowed_wasms(&e, &WasmKind::from_symbol(&e, kind_name))
+    }
+
+    // Checks whether the wasm hash is allowed for the contract kind.
+    //
+    // # Arguments
+    //
+    // * `kind_name` - The name of the contract kind.
+    // * `wasm_hash` - The wasm hash to check.
+    fn is_wasm_allowed(e: Env, kind_name: Symbol, wasm_hash: BytesN<32>) -> bool {
+        is_wasm_allowed(&e, &WasmKind::from_symbol(&e, kind_name), &wasm_hash)
+    }
+
+    // Returns the committed allowlist changes along with their deadlines.
+    fn get_pending_wasm_allowlist_changes(e: Env) -> Vec<WasmAllowlistChange> {
+        get_pending_allowlist_changes(&e)
+    }
+}
+
+// The `EmergencyModeContract` trait provides the scoped emergency mode with automatic expiration.
+#[contractimpl]
+impl EmergencyModeContract for LiquidityPoolRouter {
+    // Enables the emergency mode for the limited time.
+    // Scope defines what is unlocked, currently the instant upgrade only.
+    // The emergency mode is disabled automatically once expired.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    // * `scope` - The powers unlocked by the emergency mode.
+    // * `duration` - The emergency mode duration in seconds.
+    fn enable_emergency_mode(
+        e: Env,
+        emergency_admin: Address,
+        scope: EmergencyScope,
+        duration: u64,
+    ) {
+        enable_scoped_emergency_mode(&e, &emergency_admin, &scope, duration);
+    }
+
+    // Returns the emergency mode state.
+    //
+    // # Returns
+    //
+    // The emergency mode flag, its scope, expiration timestamp and the remaining time in seconds.
+    fn get_emergency_mode_state(e: Env) -> EmergencyModeState {
+        get_emergency_mode_state(&e)
+    }
+}
+
+// The `RoleTimelockContract` trait provides delayed changes for the rest of the roles.
+#[contractimpl]
+impl RoleTimelockContract for LiquidityPoolRouter {
+    // Commits delay between commit and apply of the role transfer or members change.
+    // The value is applied after the current delay of the role passes.
+    // Once delay is set, role can be changed only through the commit-apply process.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    // * `delay` - Delay in seconds. Cannot be less than the minimum delay for the roles
+    //   transferred with mandatory delay like `Admin` and `EmergencyAdmin`.
+    fn commit_role_transfer_delay(e: Env, admin: Address, role_name: Symbol, delay: u64) {
+        commit_role_transfer_delay(&e, &admin, role_name, delay);
+    }
+
+    // Applies the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn apply_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        apply_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Reverts the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn revert_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Returns delay between commit and apply of the role transfer or members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_role_transfer_delay(&e, role_name)
+    }
+
+    // Returns the committed role transfer delay.
+    // The value will be defaulted to the current one if the change is not committed.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_future_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_future_role_transfer_delay(&e, role_name)
+    }
+
+    // Commits address addition to the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be added.
+    fn commit_add_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, true);
+    }
+
+    // Commits address removal from the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be removed.
+    fn commit_remove_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, false);
+    }
+
+    // Applies the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The new list of the role members.
+    fn apply_role_members_change(e: Env, admin: Address, role_name: Symbol) -> Vec<Address> {
+        apply_role_members_change(&e, &admin, role_name)
+    }
+
+    // Reverts the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    fn revert_role_members_change(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_members_change(&e, &admin, role_name);
+    }
+
+    // Returns the committed members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The (address, is_added) pair if change is committed.
+    fn get_pending_role_members_change(e: Env, role_name: Symbol) -> Option<(Address, bool)> {
+        get_pending_role_members_change(&e, role_name)
+    }
+}
+
+// The `RoleRegistryContract` trait provides declaration of the custom roles.
+#[contractimpl]
+impl RoleRegistryContract for LiquidityPoolRouter {
+    // Declares a custom role in addition to the built-in ones.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role. Cannot match a built-in role.
+    // * `config` - The role properties: single or many users, delayed or instant transfer.
+    fn register_role(e: Env, admin: Address, role_name: Symbol, config: RoleConfig) {
+        admin.require_auth();
+        let access_control = AccessControl::new(&e);
+        access_control.assert_address_has_role(&admin, &Role::Admin);
+
+        access_control.register_role(&role_name, &config);
+        AccessControlEvents::new(&e).register_role(role_name, config);
+    }
+
+    // Returns the names of the declared custom roles.
+    fn get_custom_roles(e: Env) -> Vec<Symbol> {
+        AccessControl::new(&e).get_custom_roles()
+    }
+
+    // Checks whether the address holds the role.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    // * `address` - The address to check.
+    fn has_role(e: Env, role_name: Symbol, address: Address) -> bool {
+        let role = Role::from_symbol(&e, role_name);
+        AccessControl::new(&e).address_has_role(&address, &role)
+    }
+}
+
+// The `PoolsUpgradeInterface` trait provides the interface for the coordinated pools upgrade.
+// Router admin is also the admin of every pool, so it's able to upgrade them in batches.
+#[contractimpl]
+impl PoolsUpgradeInterface for LiquidityPoolRouter {
+    // Commits upgrade of the pools of given type for the tokens sets in range.
+    // Range is the same as in `get_pools_for_tokens_range`, so the rollout can be split
+    // into multiple transactions and resumed from any point.
+    // Pools already committed or upgraded to the same wasm are skipped.
+    // Pools deployed before the fleet upgrade are committed on behalf of the admin.
+    // Pools with another upgrade pending or rejecting the commit are skipped and reported,
+    // so a single pool is not able to block the rollout.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `pool_type` - The type of the pools to upgrade.
+    // * `new_wasm_hash` - The new pool wasm hash.
+    // * `new_token_wasm_hash` - The new share token wasm hash.
+    // * `start` - The start index of the tokens sets range.
+    // * `end` - The end index of the tokens sets range (exclusive).
+    //
+    // # Returns
+    //
+    // The lists of pools committed and skipped within the call.
+    fn commit_pools_upgrade(
+        e: Env,
+        admin: Address,
+        pool_type: LiquidityPoolType,
+        new_wasm_hash: BytesN<32>,
+        new_token_wasm_hash: BytesN<32>,
+        start: u128,
+        end: u128,
+    ) -> (Vec<Address>, Vec<Address>) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        if pool_type != LiquidityPoolType::ConstantProduct
+            && pool_type != LiquidityPoolType::StableSwap
+        {
+            panic_with_error!(&e, LiquidityPoolRouterError::InvalidPoolType);
+        }
+
+        let target = PoolsUpgradeTarget {
+            pool_type,
+            wasm_hash: new_wasm_hash.clone(),
+            token_wasm_hash: new_token_wasm_hash.clone(),
+        };
+        let mut committed = get_pools_upgrade_committed(&e);
+        if committed > 0 && get_pools_upgrade_target(&e) != target {
+            panic_with_error!(&e, LiquidityPoolRouterError::PoolsUpgradeInProgress);
+        }
+        // pools rely on the router to check the hashes when committed on their behalf
+        require_wasm_allowed(&e, &get_pool_wasm_kind(pool_type), &new_wasm_hash);
+        require_wasm_allowed(&e, &WasmKind::Token, &new_token_wasm_hash);
+        set_pools_upgrade_target(&e, &target);
+
+        let mut pools = Vec::new(&e);
+        let mut skipped = Vec::new(&e);
+        for pool in get_pools_by_type(&e, start, end, pool_type) {
+            if get_pool_upgrade_state(&e, &pool, &target) != PoolUpgradeStatus::Pending {
+                continue;
+            }
+
+            if !commit_pool_upgrade(&e, &admin, &pool, &target) {
+                skipped.push_back(pool);
+                continue;
+            }
+            set_pool_upgrade_status(&e, &pool, &target, PoolUpgradeStatus::Committed);
+            committed += 1;
+            pools.push_back(pool);
+        }
+        set_pools_upgrade_committed(&e, &committed);
+
+        Events::new(&e).commit_pools_upgrade(
+            pool_type,
+            new_wasm_hash,
+            new_token_wasm_hash,
+            pools.clone(),
+            skipped.clone(),
+        );
+        (pools, skipped)
+    }
+
+    // Applies the committed pools upgrade for the tokens sets in range.
+    // Upgraded pools are migrated to the new storage version within the same call.
+    // Pools not committed or already upgraded are skipped.
+    // Pools rejecting the upgrade are skipped and reported, they stay committed.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `start` - The start index of the tokens sets range.
+    // * `end` - The end index of the tokens sets range (exclusive).
+    //
+    // # Returns
+    //
+    // The lists of pools upgraded and skipped within the call.
+    fn apply_pools_upgrade(
+        e: Env,
+        admin: Address,
+        start: u128,
+        end: u128,
+    ) -> (Vec<Address>, Vec<Address>) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        let target = get_pools_upgrade_target(&e);
+        // hashes may be removed from the allowlist after the commit, pools rely on the router check
+        require_wasm_allowed(&e, &get_pool_wasm_kind(target.pool_type), &target.wasm_hash);
+        require_wasm_allowed(&e, &WasmKind::Token, &target.token_wasm_hash);
+
+        let mut committed = get_pools_upgrade_committed(&e);
+        let mut pools = Vec::new(&e);
+        let mut skipped = Vec::new(&e);
+        for pool in get_pools_by_type(&e, start, end, target.pool_type) {
+            if get_pool_upgrade_state(&e, &pool, &target) != PoolUpgradeStatus::Committed {
+                continue;
+            }
+
+            if !apply_pool_upgrade(&e, &admin, &pool) {
+                skipped.push_back(pool);
+                continue;
+            }
+            migrate_pool(&e, &admin, &pool);
+            set_pool_upgrade_status(&e, &pool, &target, PoolUpgradeStatus::Applied);
+            committed -= 1;
+            pools.push_back(pool);
+        }
+        set_pools_upgrade_committed(&e, &committed);
+
+        Events::new(&e).apply_pools_upgrade(target.wasm_hash, pools.clone(), skipped.clone());
+        (pools, skipped)
+    }
+
+    // Reverts the committed pools upgrade for the tokens sets in range.
+    // Already upgraded pools are not affected.
+    // Pools rejecting the revert are skipped and reported, they stay committed.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `start` - The start index of the tokens sets range.
+    // * `end` - The end index of the tokens sets range (exclusive).
+    //
+    // # Returns
+    //
+    // The lists of pools reverted and skipped within the call.
+    fn revert_pools_upgrade(
+        e: Env,
+        admin: Address,
+        start: u128,
+        end: u128,
+    ) -> (Vec<Address>, Vec<Address>) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+
+        let target = get_pools_upgrade_target(&e);
+        let mut committed = get_pools_upgrade_committed(&e);
+        let mut pools = Vec::new(&e);
+        let mut skipped = Vec::new(&e);
+        for pool in get_pools_by_type(&e, start, end, target.pool_type) {
+            if get_pool_upgrade_state(&e, &pool, &target) != PoolUpgradeStatus::Committed {
+                continue;
+            }
+
+            if !revert_pool_upgrade(&e, &admin, &pool) {
+                skipped.push_back(pool);
+                continue;
+            }
+            set_pool_upgrade_status(&e, &pool, &target, PoolUpgradeStatus::Pending);
+            committed -= 1;
+            pools.push_back(pool);
+        }
+        set_pools_upgrade_committed(&e, &committed);
+
+        Events::new(&e).revert_pools_upgrade(target.wasm_hash, pools.clone(), skipped.clone());
+        (pools, skipped)
+    }
+
+    // Returns the target of the latest pools upgrade.
+    fn get_pools_upgrade_target(e: Env) -> PoolsUpgradeTarget {
+        get_pools_upgrade_target(&e)
+    }
+
+    // Returns the amount of pools committed, but not applied or reverted yet.
+    // New upgrade target can be committed only when there are no such pools.
+    fn get_pools_upgrade_committed(e: Env) -> u32 {
+        get_pools_upgrade_committed(&e)
+    }
+
+    // Returns the upgrade status of the target type pools for the tokens sets in range.
+    // `Applied` status means the pool is already running the target wasm according to its upgrade history.
+    //
+    // # Arguments
+    //
+    // * `start` - The start index of the tokens sets range.
+    // * `end` - The end index of the tokens sets range (exclusive).
+    //
+    // # Returns
+    //
+    // The list of pool addresses with their upgrade status.
+    fn get_pools_upgrade_status(
+        e: Env,
+        start: u128,
+        end: u128,
+    ) -> Vec<(Address, PoolUpgradeStatus)> {
+        let target = get_pools_upgrade_target(&e);
+        let mut result = Vec::new(&e);
+        for pool in get_pools_by_type(&e, start, end, target.pool_type) {
+            let status = get_pool_upgrade_state(&e, &pool, &target);
+            result.push_back((pool, status));
+        }
+        result
+    }
+}
+
+// The `KillSwitchInterface` trait provides the router level circuit breaker for all pools.
+// Pools can still be paused individually, router flags stop the operations made through the router.
+#[contractimpl]
+impl KillSwitchInterface for LiquidityPoolRouter {
+    // Stops the deposits through the router instantly.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn kill_deposit(e: Env, admin: Address) {
+        admin.require_auth();
+        require_pause_or_emergency_pause_admin_or_owner(&e, &admin);
+
+        set_is_killed_deposit(&e, &true);
+        Events::new(&e).kill_deposit();
+    }
+
+    // Stops the swaps through the router instantly, including the chained ones.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn kill_swap(e: Env, admin: Address) {
+        admin.require_auth();
+        require_pause_or_emergency_pause_admin_or_owner(&e, &admin);
+
+        set_is_killed_swap(&e, &true);
+        Events::new(&e).kill_swap();
+    }
+
+    // Stops the rewards claims through the router instantly.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn kill_claim(e: Env, admin: Address) {
+        admin.require_auth();
+        require_pause_or_emergency_pause_admin_or_owner(&e, &admin);
+
+        set_is_killed_claim(&e, &true);
+        Events::new(&e).kill_claim();
+    }
+
+    // Resumes the deposits through the router.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn unkill_deposit(e: Env, admin: Address) {
+        admin.require_auth();
+        require_pause_admin_or_owner(&e, &admin);
+
+        set_is_killed_deposit(&e, &false);
+        Events::new(&e).unkill_deposit();
+    }
+
+    // Resumes the swaps through the router.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn unkill_swap(e: Env, admin: Address) {
+        admin.require_auth();
+        require_pause_admin_or_owner(&e, &admin);
+
+        set_is_killed_swap(&e, &false);
+        Events::new(&e).unkill_swap();
+    }
+
+    // Resumes the rewards claims through the router.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn unkill_claim(e: Env, admin: Address) {
+        admin.require_auth();
+        require_pause_admin_or_owner(&e, &admin);
+
+        set_is_killed_claim(&e, &false);
+        Events::new(&e).unkill_claim();
+    }
+
+    // Get deposit killswitch status.
+    fn get_is_killed_deposit(e: Env) -> bool {
+        get_is_killed_deposit(&e)
+    }
+
+    // Get swap killswitch status.
+    fn get_is_killed_swap(e: Env) -> bool {
+        get_is_killed_swap(&e)
+    }
+
+    // Get claim killswitch status.
+    fn get_is_killed_claim(e: Env) -> bool {
+        get_is_killed_claim(&e)
+    }
+}
@@ -19,7 +19,17 @@ pub enum LiquidityPoolRouterError {
     RewardsAlreadyConfigured = 314,
     DuplicatesNotAllowed = 315,
     InvalidPoolType = 316,
+    PoolsUpgradeInProgress = 317, // previous pools upgrade has committed pools. apply or revert them first
+    DepositKilled = 318,
+    SwapKilled = 319,
+    ClaimKilled = 320,
+    RoutesTokenOutMismatch = 321, // all routes of the split swap should end with the same token
+    BadMaxHops = 322,
+    RouteNotFound = 323,
+    DeadlineExpired = 324,
+    TokensSetsNotIndexed = 325, // tokens sets index is incomplete. run `fill_tokens_sets_index` first
 
     TokensNotSorted = 2002,
+    OutMinNotSatisfied = 2006,
     InMaxNotSatisfied = 2020,
 }
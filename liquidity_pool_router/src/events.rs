@@ -1,3 +1,4 @@
+use crate::storage::LiquidityPoolType;
 use soroban_sdk::{Address, BytesN, Env, Symbol, Val, Vec};
 
 #[derive(Clone)]
@@ -70,6 +71,36 @@ pub(crate) trait LiquidityPoolRouterEvents {
         reward_token: Address,
         reward_amount: u128,
     );
+
+    fn commit_pools_upgrade(
+        &self,
+        pool_type: LiquidityPoolType,
+        new_wasm_hash: BytesN<32>,
+        new_token_wasm_hash: BytesN<32>,
+        pools: Vec<Address>,
+        skipped: Vec<Address>,
+    );
+
+    fn apply_pools_upgrade(
+        &self,
+        new_wasm_hash: BytesN<32>,
+        pools: Vec<Address>,
+        skipped: Vec<Address>,
+    );
+
+    fn revert_pools_upgrade(
+        &self,
+        new_wasm_hash: BytesN<32>,
+        pools: Vec<Address>,
+        skipped: Vec<Address>,
+    );
+
+    fn kill_deposit(&self);
+    fn kill_swap(&self);
+    fn kill_claim(&self);
+    fn unkill_deposit(&self);
+    fn unkill_swap(&self);
+    fn unkill_claim(&self);
 }
 
 impl LiquidityPoolRouterEvents for Events {
@@ -157,4 +188,78 @@ impl LiquidityPoolRouterEvents for Events {
             (pool_address, reward_token, reward_amount),
         );
     }
+
+    fn commit_pools_upgrade(
+        &self,
+        pool_type: LiquidityPoolType,
+        new_wasm_hash: BytesN<32>,
+        new_token_wasm_hash: BytesN<32>,
+        pools: Vec<Address>,
+        skipped: Vec<Address>,
+    ) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "commit_pools_upgrade"), pool_type),
+            (new_wasm_hash, new_token_wasm_hash, pools, skipped),
+        );
+    }
+
+    fn apply_pools_upgrade(
+        &self,
+        new_wasm_hash: BytesN<32>,
+        pools: Vec<Address>,
+        skipped: Vec<Address>,
+    ) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "apply_pools_upgrade"),),
+            (new_wasm_hash, pools, skipped),
+        );
+    }
+
+    fn revert_pools_upgrade(
+        &self,
+        new_wasm_hash: BytesN<32>,
+        pools: Vec<Address>,
+        skipped: Vec<Address>,
+    ) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "revert_pools_upgrade"),),
+            (new_wasm_hash, pools, skipped),
+        );
+    }
+
+    fn kill_deposit(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "kill_deposit"),), ())
+    }
+
+    fn kill_swap(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "kill_swap"),), ())
+    }
+
+    fn kill_claim(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "kill_claim"),), ())
+    }
+
+    fn unkill_deposit(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "unkill_deposit"),), ())
+    }
+
+    fn unkill_swap(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "unkill_swap"),), ())
+    }
+
+    fn unkill_claim(&self) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "unkill_claim"),), ())
+    }
 }
@@ -10,6 +10,7 @@ mod pool_utils;
 mod rewards;
 mod router_interface;
 mod storage;
+mod swap_utils;
 mod test;
 mod test_permissions;
 mod testutils;
@@ -1,3 +1,4 @@
+use crate::storage::SwapHopEstimate;
 use soroban_sdk::{Address, BytesN, Env, Map, Symbol, Val, Vec, U256};
 
 pub trait LiquidityPoolInterfaceTrait {
@@ -302,4 +303,225 @@ pub trait CombinedSwapInterface {
         out_amount: u128,
         in_max: u128,
     ) -> u128;
+
+    // Splits the input amount between several routes and executes them in a single transaction.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user executing the swaps.
+    // * `routes` - The routes to be executed. Each route is represented by a tuple containing:
+    //   - The series of swaps, same as `swaps_chain` in `swap_chained`
+    //   - The amount of the input token to be swapped through the route
+    //   All routes should end with the same output token.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `out_min` - The minimum total amount of the output token to be received.
+    //
+    // # Returns
+    //
+    // The total amount of the output token received after all routes have been executed.
+    fn swap_split(
+        e: Env,
+        user: Address,
+        routes: Vec<(Vec<(Vec<Address>, BytesN<32>, Address)>, u128)>,
+        token_in: Address,
+        out_min: u128,
+    ) -> u128;
+
+    // Splits the output amount between several routes and executes them in a single transaction.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user executing the swaps.
+    // * `routes` - The routes to be executed. Each route is represented by a tuple containing:
+    //   - The series of swaps, same as `swaps_chain` in `swap_chained_strict_receive`
+    //   - The amount of the output token to be received through the route
+    //   All routes should end with the same output token.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `in_max` - The max total amount of the input token to spend.
+    //
+    // # Returns
+    //
+    // The total amount of the input token spent after all routes have been executed.
+    fn swap_split_strict_receive(
+        e: Env,
+        user: Address,
+        routes: Vec<(Vec<(Vec<Address>, BytesN<32>, Address)>, u128)>,
+        token_in: Address,
+        in_max: u128,
+    ) -> u128;
+
+    // Estimates a chain of token swaps to exchange an input token for an output token.
+    //
+    // # Arguments
+    //
+    // * `swaps_chain` - The series of swaps to be estimated, same as in `swap_chained`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `in_amount` - The amount of the input token to be swapped.
+    //
+    // # Returns
+    //
+    // The estimated amounts and fee for each swap in the chain.
+    fn estimate_swap_chained(
+        e: Env,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        in_amount: u128,
+    ) -> Vec<SwapHopEstimate>;
+
+    // Estimates a chain of token swaps with strict receive.
+    //
+    // # Arguments
+    //
+    // * `swaps_chain` - The series of swaps to be estimated, same as in `swap_chained_strict_receive`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `out_amount` - The amount of the output token to be received.
+    //
+    // # Returns
+    //
+    // The estimated amounts and fee for each swap in the chain.
+    fn estimate_swap_chained_strict_receive(
+        e: Env,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        out_amount: u128,
+    ) -> Vec<SwapHopEstimate>;
+}
+
+pub trait RouteFinderInterface {
+    // Finds the chain of swaps with the best output using pools data stored in the plane.
+    //
+    // # Arguments
+    //
+    // * `token_in` - The address of the input token.
+    // * `token_out` - The address of the output token.
+    // * `in_amount` - The amount of the input token to be swapped.
+    // * `max_hops` - The maximum number of swaps in the chain.
+    //
+    // # Returns
+    //
+    // The chain of swaps which can be passed to `swap_chained` and the expected output amount.
+    fn find_best_route(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        in_amount: u128,
+        max_hops: u32,
+    ) -> (Vec<(Vec<Address>, BytesN<32>, Address)>, u128);
+
+    // Indexes tokens sets created before the route finder was introduced by the tokens they contain.
+    //
+    // # Arguments
+    //
+    // * `limit` - The maximum number of tokens sets to be indexed.
+    //
+    // # Returns
+    //
+    // The number of tokens sets indexed so far.
+    fn fill_tokens_sets_index(e: Env, limit: u32) -> u128;
+}
+
+pub trait RecipientDeadlineInterface {
+    // Executes a chain of token swaps and sends the output token to the recipient.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user executing the swaps.
+    // * `swaps_chain` - The series of swaps to be executed, same as in `swap_chained`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `in_amount` - The amount of the input token to be swapped.
+    // * `out_min` - The minimum amount of the output token to be received.
+    // * `to` - The address receiving the output token.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // The amount of the output token received after all swaps have been executed.
+    fn swap_chained_to(
+        e: Env,
+        user: Address,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        in_amount: u128,
+        out_min: u128,
+        to: Address,
+        deadline: u64,
+    ) -> u128;
+
+    // Executes a chain of token swaps with strict receive and sends the output token to the recipient.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user executing the swaps.
+    // * `swaps_chain` - The series of swaps to be executed, same as in `swap_chained_strict_receive`.
+    // * `token_in` - The address of the input token to be swapped.
+    // * `out_amount` - The amount of the output token to be received.
+    // * `in_max` - The max amount of the input token to spend.
+    // * `to` - The address receiving the output token.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // The amount of the input token spent after all swaps have been executed.
+    fn swap_chained_strict_receive_to(
+        e: Env,
+        user: Address,
+        swaps_chain: Vec<(Vec<Address>, BytesN<32>, Address)>,
+        token_in: Address,
+        out_amount: u128,
+        in_max: u128,
+        to: Address,
+        deadline: u64,
+    ) -> u128;
+
+    // Deposits tokens into the pool and sends the minted shares to the recipient.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user depositing the tokens.
+    // * `tokens` - A vector of token addresses.
+    // * `pool_index` - The pool index hash.
+    // * `desired_amounts` - A vector of desired amounts of each token to deposit.
+    // * `min_shares` - The minimum amount of pool tokens to mint.
+    // * `to` - The address receiving the pool tokens.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // A tuple containing the amounts of each token deposited and the amount of pool tokens minted.
+    fn deposit_to(
+        e: Env,
+        user: Address,
+        tokens: Vec<Address>,
+        pool_index: BytesN<32>,
+        desired_amounts: Vec<u128>,
+        min_shares: u128,
+        to: Address,
+        deadline: u64,
+    ) -> (Vec<u128>, u128);
+
+    // Withdraws tokens from the pool and sends them to the recipient.
+    //
+    // # Arguments
+    //
+    // * `user` - The address of the user withdrawing the tokens.
+    // * `tokens` - A vector of token addresses.
+    // * `pool_index` - The pool index hash.
+    // * `share_amount` - The amount of pool tokens to burn.
+    // * `min_amounts` - A vector of minimum amounts of each token to be received.
+    // * `to` - The address receiving the withdrawn tokens.
+    // * `deadline` - The ledger timestamp after which the call is rejected.
+    //
+    // # Returns
+    //
+    // A vector of amounts of each token withdrawn.
+    fn withdraw_to(
+        e: Env,
+        user: Address,
+        tokens: Vec<Address>,
+        pool_index: BytesN<32>,
+        share_amount: u128,
+        min_amounts: Vec<u128>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<u128>;
 }
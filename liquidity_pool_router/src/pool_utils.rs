@@ -3,8 +3,9 @@ use crate::events::{Events, LiquidityPoolRouterEvents};
 use crate::liquidity_calculator::LiquidityCalculatorClient;
 use crate::rewards::get_rewards_manager;
 use crate::storage::{
-    add_pool, add_tokens_set, get_constant_product_pool_hash, get_pool_next_counter,
-    get_pool_plane, get_pools_plain, get_stableswap_pool_hash, get_token_hash, LiquidityPoolType,
+    add_pool, add_tokens_set, get_constant_product_pool_hash, get_pool, get_pool_next_counter,
+    get_pool_plane, get_pool_upgrade_status, get_pools_plain, get_stableswap_pool_hash,
+    get_token_hash, LiquidityPoolType, PoolUpgradeStatus, PoolsUpgradeTarget,
 };
 use access_control::access::AccessControl;
 use access_control::management::{MultipleAddressesManagementTrait, SingleAddressManagementTrait};
@@ -12,9 +13,12 @@ use access_control::role::Role;
 use rewards::storage::{BoostFeedStorageTrait, BoostTokenStorageTrait, RewardTokenStorageTrait};
 use soroban_sdk::token::Client as SorobanTokenClient;
 use soroban_sdk::{
-    panic_with_error, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol,
-    Val, Vec, U256,
+    panic_with_error, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Error, IntoVal, Map,
+    Symbol, TryFromVal, Val, Vec, U256,
 };
+use upgrade::allowlist::WasmKind;
+use upgrade::history::UpgradeRecord;
+use upgrade::view::AdminState;
 
 pub fn get_standard_pool_salt(e: &Env, fee_fraction: &u32) -> BytesN<32> {
     let mut salt = Bytes::new(e);
@@ -266,6 +270,211 @@ pub fn assert_tokens_sorted(e: &Env, tokens: &Vec<Address>) {
     }
 }
 
+pub fn assert_deadline(e: &Env, deadline: u64) {
+    if e.ledger().timestamp() > deadline {
+        panic_with_error!(e, LiquidityPoolRouterError::DeadlineExpired);
+    }
+}
+
+// Deposits tokens into the pool on behalf of the user. Shares are minted to the user.
+pub fn deposit_to_pool(
+    e: &Env,
+    user: &Address,
+    tokens: &Vec<Address>,
+    pool_index: BytesN<32>,
+    desired_amounts: &Vec<u128>,
+    min_shares: u128,
+) -> (Address, Vec<u128>, u128) {
+    assert_tokens_sorted(e, tokens);
+
+    let pool_id = get_pool(e, tokens, pool_index);
+
+    let (amounts, share_amount): (Vec<u128>, u128) = e.invoke_contract(
+        &pool_id,
+        &symbol_short!("deposit"),
+        Vec::from_array(
+            e,
+            [
+                user.clone().into_val(e),
+                desired_amounts.into_val(e),
+                min_shares.into_val(e),
+            ],
+        ),
+    );
+    Events::new(e).deposit(
+        tokens.clone(),
+        user.clone(),
+        pool_id.clone(),
+        amounts.clone(),
+        share_amount,
+    );
+    (pool_id, amounts, share_amount)
+}
+
+// Withdraws tokens from the pool on behalf of the user. Tokens are sent to the user.
+pub fn withdraw_from_pool(
+    e: &Env,
+    user: &Address,
+    tokens: &Vec<Address>,
+    pool_index: BytesN<32>,
+    share_amount: u128,
+    min_amounts: &Vec<u128>,
+) -> Vec<u128> {
+    assert_tokens_sorted(e, tokens);
+
+    let pool_id = get_pool(e, tokens, pool_index);
+
+    let amounts: Vec<u128> = e.invoke_contract(
+        &pool_id,
+        &symbol_short!("withdraw"),
+        Vec::from_array(
+            e,
+            [
+                user.clone().into_val(e),
+                share_amount.into_val(e),
+                min_amounts.into_val(e),
+            ],
+        ),
+    );
+
+    Events::new(e).withdraw(
+        tokens.clone(),
+        user.clone(),
+        pool_id,
+        amounts.clone(),
+        share_amount,
+    );
+    amounts
+}
+
+// Allowlist kind of the pool code
+pub fn get_pool_wasm_kind(pool_type: LiquidityPoolType) -> WasmKind {
+    match pool_type {
+        LiquidityPoolType::StableSwap => WasmKind::StableSwapPool,
+        _ => WasmKind::StandardPool,
+    }
+}
+
+// Pool call which is not able to abort the whole fleet upgrade batch.
+// Returns None if the pool rejects the call or does not support it
+fn try_invoke_pool<T: TryFromVal<Env, Val>>(
+    e: &Env,
+    pool: &Address,
+    func: &str,
+    args: Vec<Val>,
+) -> Option<T> {
+    match e.try_invoke_contract::<T, Error>(pool, &Symbol::new(e, func), args) {
+        Ok(Ok(value)) => Some(value),
+        _ => None,
+    }
+}
+
+// Pools deployed before the upgrade history was introduced have no storage version
+// and accept the upgrade only from their admin
+fn is_legacy_pool(e: &Env, pool: &Address) -> bool {
+    try_invoke_pool::<u32>(e, pool, "get_storage_version", Vec::new(e)).is_none()
+}
+
+// Another upgrade committed on the pool directly. Legacy pools do not expose it,
+// for them the commit itself fails and the pool is skipped
+fn has_pending_upgrade(e: &Env, pool: &Address) -> bool {
+    match try_invoke_pool::<AdminState>(e, pool, "get_admin_state", Vec::new(e)) {
+        Some(state) => state.upgrade_deadline != 0,
+        None => false,
+    }
+}
+
+// Fleet upgrade status of the pool. Applied status is derived from the wasm the pool is running,
+//  so the pools rolled back on their own are pending again.
+// Pools without upgrade history rely on the status recorded by the router
+pub fn get_pool_upgrade_state(
+    e: &Env,
+    pool: &Address,
+    target: &PoolsUpgradeTarget,
+) -> PoolUpgradeStatus {
+    let status = get_pool_upgrade_status(e, pool, target);
+    if status == PoolUpgradeStatus::Committed {
+        return PoolUpgradeStatus::Committed;
+    }
+
+    let history: Vec<UpgradeRecord> =
+        try_invoke_pool(e, pool, "get_upgrade_history", Vec::new(e)).unwrap_or(Vec::new(e));
+    match history.last() {
+        Some(record) if record.wasm_hash == target.wasm_hash => PoolUpgradeStatus::Applied,
+        Some(_) => PoolUpgradeStatus::Pending,
+        None => status,
+    }
+}
+
+// Commits the target upgrade on the pool. Returns false if the pool cannot take part in the
+// fleet upgrade: another upgrade is pending on it or the commit is rejected
+pub fn commit_pool_upgrade(
+    e: &Env,
+    admin: &Address,
+    pool: &Address,
+    target: &PoolsUpgradeTarget,
+) -> bool {
+    if has_pending_upgrade(e, pool) {
+        return false;
+    }
+
+    // router is trusted to commit the checked hashes only by the pools aware of the fleet upgrade
+    let caller = match is_legacy_pool(e, pool) {
+        true => admin.clone(),
+        false => e.current_contract_address(),
+    };
+    try_invoke_pool::<Val>(
+        e,
+        pool,
+        "commit_upgrade",
+        Vec::from_array(
+            e,
+            [
+                caller.into_val(e),
+                target.wasm_hash.into_val(e),
+                target.token_wasm_hash.into_val(e),
+            ],
+        ),
+    )
+    .is_some()
+}
+
+// Applies the committed upgrade on the pool. Returns false if the pool rejects it
+pub fn apply_pool_upgrade(e: &Env, admin: &Address, pool: &Address) -> bool {
+    try_invoke_pool::<(BytesN<32>, BytesN<32>)>(
+        e,
+        pool,
+        "apply_upgrade",
+        Vec::from_array(e, [admin.into_val(e)]),
+    )
+    .is_some()
+}
+
+// Reverts the committed upgrade on the pool. Returns false if the pool rejects it
+pub fn revert_pool_upgrade(e: &Env, admin: &Address, pool: &Address) -> bool {
+    try_invoke_pool::<Val>(
+        e,
+        pool,
+        "revert_upgrade",
+        Vec::from_array(e, [admin.into_val(e)]),
+    )
+    .is_some()
+}
+
+// Migrates the pool storage after the upgrade if the new wasm requires it.
+pub fn migrate_pool(e: &Env, admin: &Address, pool: &Address) {
+    let version: u32 = e.invoke_contract(pool, &Symbol::new(e, "version"), Vec::new(e));
+    let storage_version: u32 =
+        e.invoke_contract(pool, &Symbol::new(e, "get_storage_version"), Vec::new(e));
+    if storage_version < version {
+        e.invoke_contract::<Val>(
+            pool,
+            &Symbol::new(e, "migrate"),
+            Vec::from_array(e, [admin.into_val(e)]),
+        );
+    }
+}
+
 pub fn get_tokens_salt(e: &Env, tokens: &Vec<Address>) -> BytesN<32> {
     let mut salt = Bytes::new(e);
     for token in tokens.iter() {
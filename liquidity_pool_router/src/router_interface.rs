@@ -1,3 +1,4 @@
+use crate::storage::{LiquidityPoolType, PoolUpgradeStatus, PoolsUpgradeTarget};
 use soroban_sdk::{Address, BytesN, Env, Map, Symbol, Vec};
 
 pub trait AdminInterface {
@@ -53,3 +54,64 @@ pub trait AdminInterface {
         reward_boost_feed: Address,
     );
 }
+
+pub trait PoolsUpgradeInterface {
+    // Commit upgrade of the pools of given type for the tokens sets in range [start, end).
+    // Pools already committed or upgraded to the same wasm are skipped.
+    // Pools which cannot take part are skipped and returned separately
+    fn commit_pools_upgrade(
+        e: Env,
+        admin: Address,
+        pool_type: LiquidityPoolType,
+        new_wasm_hash: BytesN<32>,
+        new_token_wasm_hash: BytesN<32>,
+        start: u128,
+        end: u128,
+    ) -> (Vec<Address>, Vec<Address>);
+
+    // Apply committed pools upgrade for the tokens sets in range [start, end)
+    fn apply_pools_upgrade(
+        e: Env,
+        admin: Address,
+        start: u128,
+        end: u128,
+    ) -> (Vec<Address>, Vec<Address>);
+
+    // Revert committed pools upgrade for the tokens sets in range [start, end)
+    fn revert_pools_upgrade(
+        e: Env,
+        admin: Address,
+        start: u128,
+        end: u128,
+    ) -> (Vec<Address>, Vec<Address>);
+
+    // Get current pools upgrade target
+    fn get_pools_upgrade_target(e: Env) -> PoolsUpgradeTarget;
+
+    // Get amount of pools committed, but not applied or reverted yet
+    fn get_pools_upgrade_committed(e: Env) -> u32;
+
+    // Get upgrade status of the target type pools for the tokens sets in range [start, end)
+    fn get_pools_upgrade_status(
+        e: Env,
+        start: u128,
+        end: u128,
+    ) -> Vec<(Address, PoolUpgradeStatus)>;
+}
+
+pub trait KillSwitchInterface {
+    // Stop operations through the router for all pools instantly
+    fn kill_deposit(e: Env, admin: Address);
+    fn kill_swap(e: Env, admin: Address);
+    fn kill_claim(e: Env, admin: Address);
+
+    // Resume operations through the router
+    fn unkill_deposit(e: Env, admin: Address);
+    fn unkill_swap(e: Env, admin: Address);
+    fn unkill_claim(e: Env, admin: Address);
+
+    // Get killswitch status
+    fn get_is_killed_deposit(e: Env) -> bool;
+    fn get_is_killed_swap(e: Env) -> bool;
+    fn get_is_killed_claim(e: Env) -> bool;
+}
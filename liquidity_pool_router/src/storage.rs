@@ -45,12 +45,43 @@ pub struct LiquidityPoolRewardInfo {
     pub total_liquidity: U256,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PoolUpgradeStatus {
+    Pending = 0,
+    Committed = 1,
+    Applied = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolsUpgradeTarget {
+    pub pool_type: LiquidityPoolType,
+    pub wasm_hash: BytesN<32>,
+    pub token_wasm_hash: BytesN<32>,
+}
+
+// Estimated result of the single swap in the chain
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapHopEstimate {
+    pub pool: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub in_amount: u128,
+    pub out_amount: u128,
+    pub fee: u128, // fee charged by the pool in the output token
+}
+
 #[derive(Clone)]
 #[contracttype]
 enum DataKey {
     TokensSet(u128),
     TokensSetCounter,
     TokensSetPools(BytesN<32>),
+    TokenTokensSets(Address),
+    TokensSetsIndexed,
     TokenHash,
     InitPoolPaymentToken,
     InitStandardPoolPaymentAmount,
@@ -61,6 +92,14 @@ enum DataKey {
     PoolCounter,
     PoolPlane,
     LiquidityCalculator,
+    PoolsUpgradeTarget,
+    PoolsUpgradeCommitted,
+    PoolUpgradeStatus(Address),
+
+    // router level killswitches for all pools
+    IsKilledSwap,
+    IsKilledDeposit,
+    IsKilledClaim,
 
     // Temporary storage
     RewardsConfig,                          // Global reward config
@@ -125,12 +164,47 @@ generate_instance_storage_getter_and_setter_with_default!(
     u128,
     0
 );
+generate_instance_storage_getter_and_setter_with_default!(
+    tokens_sets_indexed,
+    DataKey::TokensSetsIndexed,
+    u128,
+    0
+);
 generate_instance_storage_getter_and_setter!(pool_plane, DataKey::PoolPlane, Address);
 generate_instance_storage_getter_and_setter!(
     liquidity_calculator,
     DataKey::LiquidityCalculator,
     Address
 );
+generate_instance_storage_getter_and_setter!(
+    pools_upgrade_target,
+    DataKey::PoolsUpgradeTarget,
+    PoolsUpgradeTarget
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    is_killed_swap,
+    DataKey::IsKilledSwap,
+    bool,
+    false
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    is_killed_deposit,
+    DataKey::IsKilledDeposit,
+    bool,
+    false
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    is_killed_claim,
+    DataKey::IsKilledClaim,
+    bool,
+    false
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    pools_upgrade_committed,
+    DataKey::PoolsUpgradeCommitted,
+    u32,
+    0
+);
 
 pub fn get_rewards_config(e: &Env) -> GlobalRewardsConfig {
     match e.storage().temporary().get(&DataKey::RewardsConfig) {
@@ -279,6 +353,35 @@ pub fn add_tokens_set(e: &Env, tokens: &Vec<Address>) {
     let tokens_set_count = get_tokens_set_count(e);
     put_tokens_set(e, tokens_set_count, &tokens);
     set_tokens_set_count(e, &(tokens_set_count + 1));
+
+    // sets created before the index was introduced are indexed by `fill_tokens_sets_index`
+    if get_tokens_sets_indexed(e) == tokens_set_count {
+        index_tokens_set(e, tokens_set_count, tokens);
+        set_tokens_sets_indexed(e, &(tokens_set_count + 1));
+    }
+}
+
+// remember tokens set index for every token it consists of
+pub fn index_tokens_set(e: &Env, index: u128, tokens: &Vec<Address>) {
+    for token in tokens.iter() {
+        let mut token_sets = get_token_tokens_sets(e, &token);
+        token_sets.push_back(index);
+        let key = DataKey::TokenTokensSets(token);
+        e.storage().persistent().set(&key, &token_sets);
+        bump_persistent(e, &key);
+    }
+}
+
+// indexes of the tokens sets containing the token
+pub fn get_token_tokens_sets(e: &Env, token: &Address) -> Vec<u128> {
+    let key = DataKey::TokenTokensSets(token.clone());
+    match e.storage().persistent().get(&key) {
+        Some(v) => {
+            bump_persistent(e, &key);
+            v
+        }
+        None => Vec::new(e),
+    }
 }
 
 pub fn remove_pool(e: &Env, salt: BytesN<32>, pool_index: BytesN<32>) {
@@ -309,3 +412,59 @@ pub fn put_tokens_set(e: &Env, index: u128, tokens: &Vec<Address>) {
     e.storage().persistent().set(&key, tokens);
     bump_persistent(e, &key);
 }
+
+// pools of the given type for the tokens sets in range, same order as `get_pools_for_tokens_range`
+pub fn get_pools_by_type(
+    e: &Env,
+    start: u128,
+    end: u128,
+    pool_type: LiquidityPoolType,
+) -> Vec<Address> {
+    let mut result = Vec::new(e);
+    for index in start..end {
+        let salt = get_tokens_salt(e, &get_tokens_set(e, index));
+        for (_key, value) in get_pools(e, salt) {
+            if value.pool_type == pool_type {
+                result.push_back(value.address);
+            }
+        }
+    }
+    result
+}
+
+// fleet upgrade progress of the pool. status is tracked per target wasm
+// so the pools processed by the previous rollout are pending again
+pub fn get_pool_upgrade_status(
+    e: &Env,
+    pool: &Address,
+    target: &PoolsUpgradeTarget,
+) -> PoolUpgradeStatus {
+    let key = DataKey::PoolUpgradeStatus(pool.clone());
+    match e
+        .storage()
+        .persistent()
+        .get::<DataKey, (BytesN<32>, PoolUpgradeStatus)>(&key)
+    {
+        Some((wasm_hash, status)) => {
+            bump_persistent(e, &key);
+            match wasm_hash == target.wasm_hash {
+                true => status,
+                false => PoolUpgradeStatus::Pending,
+            }
+        }
+        None => PoolUpgradeStatus::Pending,
+    }
+}
+
+pub fn set_pool_upgrade_status(
+    e: &Env,
+    pool: &Address,
+    target: &PoolsUpgradeTarget,
+    status: PoolUpgradeStatus,
+) {
+    let key = DataKey::PoolUpgradeStatus(pool.clone());
+    e.storage()
+        .persistent()
+        .set(&key, &(target.wasm_hash.clone(), status));
+    bump_persistent(e, &key);
+}
@@ -0,0 +1,641 @@
+use crate::constants::MAX_ROUTE_HOPS;
+use crate::errors::LiquidityPoolRouterError;
+use crate::events::{Events, LiquidityPoolRouterEvents};
+use crate::liquidity_calculator::LiquidityCalculatorClient;
+use crate::pool_utils::{assert_tokens_sorted, get_tokens_salt};
+use crate::storage::{
+    get_liquidity_calculator, get_pool, get_pools_plain, get_token_tokens_sets, get_tokens_set,
+    get_tokens_set_count, get_tokens_sets_indexed, SwapHopEstimate,
+};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::token::Client as SorobanTokenClient;
+use soroban_sdk::{
+    panic_with_error, symbol_short, vec, Address, BytesN, Env, IntoVal, Map, Symbol, Vec,
+};
+
+// Authorizes the pool to pull the input tokens from the router.
+fn authorize_pool_transfer(e: &Env, token: &Address, pool_id: &Address, amount: u128) {
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token.clone(),
+                fn_name: Symbol::new(e, "transfer"),
+                args: (
+                    e.current_contract_address(),
+                    pool_id.clone(),
+                    amount as i128,
+                )
+                    .into_val(e),
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+}
+
+// Returns the output token of the chain. Panics if the chain is empty.
+pub fn get_chain_token_out(
+    e: &Env,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+) -> Address {
+    match swaps_chain.last() {
+        Some((_, _, token_out)) => token_out,
+        None => panic_with_error!(e, LiquidityPoolRouterError::PathIsEmpty),
+    }
+}
+
+// Returns the output token shared by all routes. Panics if routes are empty or end with different tokens.
+pub fn get_routes_token_out(
+    e: &Env,
+    routes: &Vec<(Vec<(Vec<Address>, BytesN<32>, Address)>, u128)>,
+) -> Address {
+    let mut token_out: Option<Address> = None;
+    for (swaps_chain, _) in routes.iter() {
+        let route_token_out = get_chain_token_out(e, &swaps_chain);
+        match token_out {
+            Some(ref v) if *v != route_token_out => {
+                panic_with_error!(e, LiquidityPoolRouterError::RoutesTokenOutMismatch)
+            }
+            _ => token_out = Some(route_token_out),
+        }
+    }
+    match token_out {
+        Some(v) => v,
+        None => panic_with_error!(e, LiquidityPoolRouterError::PathIsEmpty),
+    }
+}
+
+// Executes a chain of swaps using tokens already held by the router.
+//
+// # Arguments
+//
+// * `user` - The address of the user the swaps are executed for.
+// * `swaps_chain` - The series of swaps to be executed.
+// * `token_in` - The address of the input token.
+// * `in_amount` - The amount of the input token to be swapped.
+// * `out_min` - The minimum amount of the output token to be received from the last hop.
+//
+// # Returns
+//
+// The amount of the output token received by the router after all swaps have been executed.
+pub fn swap_chain(
+    e: &Env,
+    user: &Address,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+    token_in: &Address,
+    in_amount: u128,
+    out_min: u128,
+) -> u128 {
+    if swaps_chain.len() == 0 {
+        panic_with_error!(e, LiquidityPoolRouterError::PathIsEmpty);
+    }
+
+    let mut token_in_local = token_in.clone();
+    let mut in_amount_local = in_amount;
+
+    for i in 0..swaps_chain.len() {
+        let (tokens, pool_index, token_out) = swaps_chain.get(i).unwrap();
+        assert_tokens_sorted(e, &tokens);
+
+        let pool_id = get_pool(e, &tokens, pool_index);
+
+        let mut out_min_local = 0;
+        if i == swaps_chain.len() - 1 {
+            out_min_local = out_min;
+        }
+
+        authorize_pool_transfer(e, &token_in_local, &pool_id, in_amount_local);
+
+        let out_amount: u128 = e.invoke_contract(
+            &pool_id,
+            &symbol_short!("swap"),
+            Vec::from_array(
+                e,
+                [
+                    e.current_contract_address().into_val(e),
+                    tokens
+                        .first_index_of(token_in_local.clone())
+                        .unwrap()
+                        .into_val(e),
+                    tokens
+                        .first_index_of(token_out.clone())
+                        .unwrap()
+                        .into_val(e),
+                    in_amount_local.into_val(e),
+                    out_min_local.into_val(e),
+                ],
+            ),
+        );
+
+        Events::new(e).swap(
+            tokens,
+            user.clone(),
+            pool_id,
+            token_in_local.clone(),
+            token_out.clone(),
+            in_amount_local,
+            out_amount,
+        );
+
+        token_in_local = token_out;
+        in_amount_local = out_amount;
+    }
+
+    in_amount_local
+}
+
+// Pulls the input tokens from the user, executes the chain and sends the output to the recipient.
+//
+// # Arguments
+//
+// * `user` - The address of the user executing the swaps.
+// * `swaps_chain` - The series of swaps to be executed.
+// * `token_in` - The address of the input token.
+// * `in_amount` - The amount of the input token to be swapped.
+// * `out_min` - The minimum amount of the output token to be received.
+// * `to` - The address receiving the output token.
+//
+// # Returns
+//
+// The amount of the output token received after all swaps have been executed.
+pub fn execute_swap_chained(
+    e: &Env,
+    user: &Address,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+    token_in: &Address,
+    in_amount: u128,
+    out_min: u128,
+    to: &Address,
+) -> u128 {
+    let token_out = get_chain_token_out(e, swaps_chain);
+
+    SorobanTokenClient::new(e, token_in).transfer(
+        user,
+        &e.current_contract_address(),
+        &(in_amount as i128),
+    );
+
+    let out_amount = swap_chain(e, user, swaps_chain, token_in, in_amount, out_min);
+
+    SorobanTokenClient::new(e, &token_out).transfer(
+        &e.current_contract_address(),
+        to,
+        &(out_amount as i128),
+    );
+
+    out_amount
+}
+
+// Calculates input amounts required by each hop of the chain to receive the exact output amount.
+//
+// # Arguments
+//
+// * `swaps_chain` - The series of swaps to be executed.
+// * `token_in` - The address of the input token.
+// * `out_amount` - The amount of the output token to be received from the last hop.
+//
+// # Returns
+//
+// The vector of the required input amounts per hop. First item is the total input of the chain.
+pub fn estimate_chain_strict_receive(
+    e: &Env,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+    token_in: &Address,
+    out_amount: u128,
+) -> Vec<u128> {
+    if swaps_chain.len() == 0 {
+        panic_with_error!(e, LiquidityPoolRouterError::PathIsEmpty);
+    }
+
+    let mut required_amounts: Vec<u128> = Vec::new(e);
+    let mut desired_out = out_amount;
+
+    let estimate_fn = Symbol::new(e, "estimate_swap_strict_receive");
+
+    // Process swaps in reverse order
+    for i in (0..swaps_chain.len()).rev() {
+        let (tokens, pool_index, token_out) = swaps_chain.get(i).unwrap();
+        let pool_id = get_pool(e, &tokens, pool_index);
+        let token_in_for_hop = if i == 0 {
+            token_in.clone()
+        } else {
+            // For a middle hop, the input is the output of the previous swap in the chain.
+            swaps_chain.get(i - 1).unwrap().2.clone()
+        };
+
+        let required_in: u128 = e.invoke_contract(
+            &pool_id,
+            &estimate_fn,
+            Vec::from_array(
+                e,
+                [
+                    tokens
+                        .first_index_of(token_in_for_hop.clone())
+                        .unwrap()
+                        .into_val(e),
+                    tokens
+                        .first_index_of(token_out.clone())
+                        .unwrap()
+                        .into_val(e),
+                    desired_out.into_val(e),
+                ],
+            ),
+        );
+        required_amounts.push_front(required_in);
+        // The output required from the previous hop is the input needed here.
+        desired_out = required_in;
+    }
+
+    required_amounts
+}
+
+// Executes a chain of swaps with strict receive using tokens already held by the router.
+//
+// # Arguments
+//
+// * `user` - The address of the user the swaps are executed for.
+// * `swaps_chain` - The series of swaps to be executed.
+// * `token_in` - The address of the input token.
+// * `out_amount` - The amount of the output token to be received from the last hop.
+// * `required_amounts` - Input amounts per hop, as returned by `estimate_chain_strict_receive`.
+pub fn swap_chain_strict_receive(
+    e: &Env,
+    user: &Address,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+    token_in: &Address,
+    out_amount: u128,
+    required_amounts: &Vec<u128>,
+) {
+    let swap_fn = Symbol::new(e, "swap_strict_receive");
+
+    let mut current_in = required_amounts.get_unchecked(0);
+    let mut token_in_local = token_in.clone();
+
+    // Execute each swap in sequence.
+    for i in 0..swaps_chain.len() {
+        let (tokens, pool_index, token_out) = swaps_chain.get(i).unwrap();
+        let pool_id = get_pool(e, &tokens, pool_index);
+
+        // Set the minimum acceptable output for this hop.
+        // For intermediate hops, this is the required amount computed for the next swap.
+        // For the final hop, it is the desired `out_amount`.
+        let out_local = if i == swaps_chain.len() - 1 {
+            out_amount
+        } else {
+            required_amounts.get_unchecked(i + 1)
+        };
+
+        authorize_pool_transfer(e, &token_in_local, &pool_id, current_in);
+
+        let in_local: u128 = e.invoke_contract(
+            &pool_id,
+            &swap_fn,
+            Vec::from_array(
+                e,
+                [
+                    e.current_contract_address().into_val(e),
+                    tokens
+                        .first_index_of(token_in_local.clone())
+                        .unwrap()
+                        .into_val(e),
+                    tokens
+                        .first_index_of(token_out.clone())
+                        .unwrap()
+                        .into_val(e),
+                    out_local.into_val(e),
+                    current_in.into_val(e),
+                ],
+            ),
+        );
+
+        // Emit an event for the swap.
+        Events::new(e).swap(
+            tokens,
+            user.clone(),
+            pool_id,
+            token_in_local.clone(),
+            token_out.clone(),
+            in_local,
+            out_local,
+        );
+
+        current_in = out_local;
+        token_in_local = token_out;
+    }
+}
+
+// Pulls the required input tokens from the user, executes the chain with strict receive
+//  and sends the output to the recipient.
+//
+// # Arguments
+//
+// * `user` - The address of the user executing the swaps.
+// * `swaps_chain` - The series of swaps to be executed.
+// * `token_in` - The address of the input token.
+// * `out_amount` - The amount of the output token to be received.
+// * `max_in` - The max amount of the input token to spend.
+// * `to` - The address receiving the output token.
+//
+// # Returns
+//
+// The amount of the input token spent after all swaps have been executed.
+pub fn execute_swap_chained_strict_receive(
+    e: &Env,
+    user: &Address,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+    token_in: &Address,
+    out_amount: u128,
+    max_in: u128,
+    to: &Address,
+) -> u128 {
+    let token_out = get_chain_token_out(e, swaps_chain);
+
+    // Reverse pass: compute required inputs per hop
+    let required_amounts = estimate_chain_strict_receive(e, swaps_chain, token_in, out_amount);
+    let total_required_input = required_amounts.get_unchecked(0);
+
+    // Verify that the required input does not exceed the maximum provided.
+    if total_required_input > max_in {
+        panic_with_error!(e, LiquidityPoolRouterError::InMaxNotSatisfied);
+    }
+
+    // Pull the maximum required input from the user.
+    SorobanTokenClient::new(e, token_in).transfer(
+        user,
+        &e.current_contract_address(),
+        &(max_in as i128),
+    );
+    // Return back the difference
+    if max_in > total_required_input {
+        SorobanTokenClient::new(e, token_in).transfer(
+            &e.current_contract_address(),
+            user,
+            &((max_in - total_required_input) as i128),
+        );
+    }
+
+    // Forward pass: execute the swaps
+    swap_chain_strict_receive(
+        e,
+        user,
+        swaps_chain,
+        token_in,
+        out_amount,
+        &required_amounts,
+    );
+
+    // Finally, transfer the received output tokens to the recipient.
+    SorobanTokenClient::new(e, &token_out).transfer(
+        &e.current_contract_address(),
+        to,
+        &(out_amount as i128),
+    );
+
+    total_required_input
+}
+
+// Estimates a chain of swaps hop by hop, same as `swap_chain` executes it.
+//
+// # Arguments
+//
+// * `swaps_chain` - The series of swaps to be estimated.
+// * `token_in` - The address of the input token.
+// * `in_amount` - The amount of the input token to be swapped.
+//
+// # Returns
+//
+// The estimated result of each swap in the chain.
+pub fn estimate_chain(
+    e: &Env,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+    token_in: &Address,
+    in_amount: u128,
+) -> Vec<SwapHopEstimate> {
+    if swaps_chain.len() == 0 {
+        panic_with_error!(e, LiquidityPoolRouterError::PathIsEmpty);
+    }
+
+    let mut result = Vec::new(e);
+    let mut token_in_local = token_in.clone();
+    let mut in_amount_local = in_amount;
+
+    for (tokens, pool_index, token_out) in swaps_chain.iter() {
+        assert_tokens_sorted(e, &tokens);
+        let pool_id = get_pool(e, &tokens, pool_index);
+
+        let (out_amount, fee): (u128, u128) = e.invoke_contract(
+            &pool_id,
+            &Symbol::new(e, "estimate_swap_with_fee"),
+            Vec::from_array(
+                e,
+                [
+                    tokens
+                        .first_index_of(token_in_local.clone())
+                        .unwrap()
+                        .into_val(e),
+                    tokens
+                        .first_index_of(token_out.clone())
+                        .unwrap()
+                        .into_val(e),
+                    in_amount_local.into_val(e),
+                ],
+            ),
+        );
+
+        result.push_back(SwapHopEstimate {
+            pool: pool_id,
+            token_in: token_in_local,
+            token_out: token_out.clone(),
+            in_amount: in_amount_local,
+            out_amount,
+            fee,
+        });
+
+        token_in_local = token_out;
+        in_amount_local = out_amount;
+    }
+
+    result
+}
+
+// Estimates a chain of swaps with strict receive hop by hop, same as `swap_chain_strict_receive` executes it.
+//
+// # Arguments
+//
+// * `swaps_chain` - The series of swaps to be estimated.
+// * `token_in` - The address of the input token.
+// * `out_amount` - The amount of the output token to be received from the last hop.
+//
+// # Returns
+//
+// The estimated result of each swap in the chain.
+pub fn estimate_chain_strict_receive_detailed(
+    e: &Env,
+    swaps_chain: &Vec<(Vec<Address>, BytesN<32>, Address)>,
+    token_in: &Address,
+    out_amount: u128,
+) -> Vec<SwapHopEstimate> {
+    if swaps_chain.len() == 0 {
+        panic_with_error!(e, LiquidityPoolRouterError::PathIsEmpty);
+    }
+
+    let mut result = Vec::new(e);
+    let mut desired_out = out_amount;
+
+    let estimate_fn = Symbol::new(e, "estimate_swap_strict_receive_with_fee");
+
+    // Process swaps in reverse order
+    for i in (0..swaps_chain.len()).rev() {
+        let (tokens, pool_index, token_out) = swaps_chain.get(i).unwrap();
+        let pool_id = get_pool(e, &tokens, pool_index);
+        let token_in_for_hop = if i == 0 {
+            token_in.clone()
+        } else {
+            swaps_chain.get(i - 1).unwrap().2.clone()
+        };
+
+        let (required_in, fee): (u128, u128) = e.invoke_contract(
+            &pool_id,
+            &estimate_fn,
+            Vec::from_array(
+                e,
+                [
+                    tokens
+                        .first_index_of(token_in_for_hop.clone())
+                        .unwrap()
+                        .into_val(e),
+                    tokens
+                        .first_index_of(token_out.clone())
+                        .unwrap()
+                        .into_val(e),
+                    desired_out.into_val(e),
+                ],
+            ),
+        );
+        result.push_front(SwapHopEstimate {
+            pool: pool_id,
+            token_in: token_in_for_hop,
+            token_out,
+            in_amount: required_in,
+            out_amount: desired_out,
+            fee,
+        });
+        // The output required from the previous hop is the input needed here.
+        desired_out = required_in;
+    }
+
+    result
+}
+
+// Pools of all the tokens sets containing the token.
+fn get_token_pools(e: &Env, token: &Address) -> Vec<(Vec<Address>, BytesN<32>, Address)> {
+    let mut pools = Vec::new(e);
+    for index in get_token_tokens_sets(e, token) {
+        let tokens = get_tokens_set(e, index);
+        for (pool_index, pool_id) in get_pools_plain(e, get_tokens_salt(e, &tokens)) {
+            pools.push_back((tokens.clone(), pool_index, pool_id));
+        }
+    }
+    pools
+}
+
+// Searches registered pools for the chain of swaps giving the biggest output.
+// Each hop keeps the best known amount for every token, so the search is limited by
+//  the number of hops rather than the number of possible paths.
+//
+// # Arguments
+//
+// * `token_in` - The address of the input token.
+// * `token_out` - The address of the output token.
+// * `in_amount` - The amount of the input token to be swapped.
+// * `max_hops` - The maximum number of swaps in the chain.
+//
+// # Returns
+//
+// The chain of swaps in the `swap_chained` format and the expected output amount.
+pub fn find_best_route(
+    e: &Env,
+    token_in: &Address,
+    token_out: &Address,
+    in_amount: u128,
+    max_hops: u32,
+) -> (Vec<(Vec<Address>, BytesN<32>, Address)>, u128) {
+    if max_hops == 0 || max_hops > MAX_ROUTE_HOPS {
+        panic_with_error!(e, LiquidityPoolRouterError::BadMaxHops);
+    }
+
+    if get_tokens_sets_indexed(e) < get_tokens_set_count(e) {
+        panic_with_error!(e, LiquidityPoolRouterError::TokensSetsNotIndexed);
+    }
+
+    // pools are loaded lazily only for the tokens reached by the search
+    let mut token_pools: Map<Address, Vec<(Vec<Address>, BytesN<32>, Address)>> = Map::new(e);
+    let calculator = LiquidityCalculatorClient::new(e, &get_liquidity_calculator(e));
+    let mut best: Map<Address, (u128, Vec<(Vec<Address>, BytesN<32>, Address)>)> = Map::new(e);
+    best.set(token_in.clone(), (in_amount, Vec::new(e)));
+    let mut frontier = Vec::from_array(e, [token_in.clone()]);
+
+    for _hop in 0..max_hops {
+        // quote every pool reachable from the tokens improved on the previous hop
+        let mut quotes = Vec::new(e);
+        let mut candidates = Vec::new(e);
+        for token in frontier.iter() {
+            let (amount, path) = best.get(token.clone()).unwrap();
+            let pools = match token_pools.get(token.clone()) {
+                Some(v) => v,
+                None => {
+                    let pools = get_token_pools(e, &token);
+                    token_pools.set(token.clone(), pools.clone());
+                    pools
+                }
+            };
+            for (tokens, pool_index, pool_id) in pools.iter() {
+                let in_idx = tokens.first_index_of(&token).unwrap();
+                for out_idx in 0..tokens.len() {
+                    let hop_token_out = tokens.get(out_idx).unwrap();
+                    // never come back to the tokens or pools already visited by the path
+                    if out_idx == in_idx
+                        || hop_token_out == *token_in
+                        || path.iter().any(|(visited_tokens, visited_index, visited)| {
+                            visited == hop_token_out
+                                || (visited_tokens == tokens && visited_index == pool_index)
+                        })
+                    {
+                        continue;
+                    }
+                    quotes.push_back((pool_id.clone(), tokens.clone(), in_idx, out_idx, amount));
+                    let mut candidate_path = path.clone();
+                    candidate_path.push_back((tokens.clone(), pool_index.clone(), hop_token_out));
+                    candidates.push_back(candidate_path);
+                }
+            }
+        }
+        if quotes.len() == 0 {
+            break;
+        }
+
+        let amounts = calculator.estimate_swaps(&quotes);
+        frontier = Vec::new(e);
+        for i in 0..candidates.len() {
+            let out_amount = amounts.get(i).unwrap();
+            let path = candidates.get(i).unwrap();
+            let hop_token_out = path.last().unwrap().2;
+            let is_better = match best.get(hop_token_out.clone()) {
+                Some((amount, _)) => out_amount > amount,
+                None => out_amount > 0,
+            };
+            if !is_better {
+                continue;
+            }
+            best.set(hop_token_out.clone(), (out_amount, path));
+            if !frontier.contains(&hop_token_out) {
+                frontier.push_back(hop_token_out);
+            }
+        }
+    }
+
+    match best.get(token_out.clone()) {
+        Some((out_amount, path)) if path.len() > 0 => (path, out_amount),
+        _ => panic_with_error!(e, LiquidityPoolRouterError::RouteNotFound),
+    }
+}
@@ -2,18 +2,23 @@
 extern crate std;
 
 use crate::constants::{CONSTANT_PRODUCT_FEE_AVAILABLE, STABLESWAP_MAX_POOLS};
+use crate::storage::{
+    set_tokens_sets_indexed, LiquidityPoolType, PoolUpgradeStatus, PoolsUpgradeTarget,
+    SwapHopEstimate,
+};
 use crate::testutils;
 use crate::testutils::{create_plane_contract, test_token, Setup};
-use access_control::constants::ADMIN_ACTIONS_DELAY;
+use access_control::constants::{ADMIN_ACTIONS_DELAY, WASM_ALLOWLIST_DELAY};
+use access_control::emergency::EmergencyScope;
 use soroban_sdk::testutils::{
     AuthorizedFunction, AuthorizedInvocation, Events, MockAuth, MockAuthInvoke,
 };
 use soroban_sdk::{
-    symbol_short, testutils::Address as _, vec, Address, FromVal, IntoVal, Map, Symbol, Val, Vec,
-    U256,
+    symbol_short, testutils::Address as _, vec, Address, Error, FromVal, IntoVal, Map, Symbol, Val,
+    Vec, U256,
 };
 use utils::test_utils::{
-    assert_approx_eq_abs, assert_approx_eq_abs_u256, install_dummy_wasm, jump,
+    allow_wasm, assert_approx_eq_abs, assert_approx_eq_abs_u256, install_dummy_wasm, jump,
 };
 
 #[test]
@@ -2586,6 +2591,581 @@ fn test_chained_swap_min_not_met() {
     );
 }
 
+#[test]
+fn test_split_swap() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let admin = setup.admin;
+    let [token1, token2, token3, _] = setup.tokens;
+    setup.reward_token.mint(&admin, &10_0000000);
+
+    let tokens1 = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let tokens2 = Vec::from_array(&e, [token2.address.clone(), token3.address.clone()]);
+    let (pool_index1, _) = router.init_standard_pool(&admin, &tokens1, &10);
+    let (pool_index2, _) = router.init_standard_pool(&admin, &tokens1, &30);
+    let (pool_index3, _) = router.init_standard_pool(&admin, &tokens2, &30);
+    token1.mint(&admin, &20000);
+    token2.mint(&admin, &30000);
+    token3.mint(&admin, &10000);
+    for (tokens, pool_index) in [
+        (tokens1.clone(), pool_index1.clone()),
+        (tokens1.clone(), pool_index2.clone()),
+        (tokens2.clone(), pool_index3.clone()),
+    ] {
+        router.deposit(
+            &admin,
+            &tokens,
+            &pool_index,
+            &Vec::from_array(&e, [10000, 10000]),
+            &0,
+        );
+    }
+
+    let swapper = Address::generate(&e);
+    token1.mint(&swapper, &1000);
+
+    let route1 = vec![
+        &e,
+        (tokens1.clone(), pool_index1.clone(), token2.address.clone()),
+    ];
+    let route2 = vec![
+        &e,
+        (tokens1.clone(), pool_index2.clone(), token2.address.clone()),
+    ];
+    let route3 = vec![
+        &e,
+        (tokens1.clone(), pool_index1.clone(), token2.address.clone()),
+        (tokens2.clone(), pool_index3.clone(), token3.address.clone()),
+    ];
+
+    // all routes should end with the same token
+    assert_eq!(
+        router
+            .try_swap_split(
+                &swapper,
+                &vec![&e, (route1.clone(), 100), (route3.clone(), 100)],
+                &token1.address,
+                &0,
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(321))
+    );
+    assert_eq!(
+        router
+            .try_swap_split(&swapper, &vec![&e], &token1.address, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(307))
+    );
+
+    let expected_out = router.estimate_swap(
+        &tokens1,
+        &token1.address,
+        &token2.address,
+        &pool_index1,
+        &300,
+    ) + router.estimate_swap(
+        &tokens1,
+        &token1.address,
+        &token2.address,
+        &pool_index2,
+        &100,
+    );
+    // overall out_min is checked against the aggregated output
+    assert_eq!(
+        router
+            .try_swap_split(
+                &swapper,
+                &vec![&e, (route1.clone(), 300), (route2.clone(), 100)],
+                &token1.address,
+                &(expected_out + 1),
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2006))
+    );
+    assert_eq!(
+        router.swap_split(
+            &swapper,
+            &vec![&e, (route1.clone(), 300), (route2.clone(), 100)],
+            &token1.address,
+            &expected_out,
+        ),
+        expected_out
+    );
+    assert_eq!(token1.balance(&swapper), 600);
+    assert_eq!(token2.balance(&swapper), expected_out as i128);
+    assert_eq!(token1.balance(&router.address), 0);
+    assert_eq!(token2.balance(&router.address), 0);
+
+    // strict receive
+    let swapper2 = Address::generate(&e);
+    token1.mint(&swapper2, &1000);
+    assert_eq!(
+        router
+            .try_swap_split_strict_receive(
+                &swapper2,
+                &vec![&e, (route1.clone(), 200), (route2.clone(), 100)],
+                &token1.address,
+                &300,
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2020))
+    );
+    let spent = router.swap_split_strict_receive(
+        &swapper2,
+        &vec![&e, (route1.clone(), 200), (route2.clone(), 100)],
+        &token1.address,
+        &1000,
+    );
+    assert!(spent > 300);
+    assert_eq!(token1.balance(&swapper2), 1000 - spent as i128);
+    assert_eq!(token2.balance(&swapper2), 300);
+    assert_eq!(token1.balance(&router.address), 0);
+    assert_eq!(token2.balance(&router.address), 0);
+
+    // routes going through the same pool are estimated right before execution
+    let swapper3 = Address::generate(&e);
+    token1.mint(&swapper3, &1000);
+    let spent = router.swap_split_strict_receive(
+        &swapper3,
+        &vec![&e, (route1.clone(), 100), (route1.clone(), 100)],
+        &token1.address,
+        &1000,
+    );
+    assert!(spent > 200);
+    assert_eq!(token1.balance(&swapper3), 1000 - spent as i128);
+    assert_eq!(token2.balance(&swapper3), 200);
+    assert_eq!(token1.balance(&router.address), 0);
+    assert_eq!(token2.balance(&router.address), 0);
+}
+
+#[test]
+fn test_find_best_route() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let admin = setup.admin;
+    let [token1, token2, token3, token4] = setup.tokens;
+    setup.reward_token.mint(&admin, &10_0000000);
+
+    let tokens12 = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let tokens23 = Vec::from_array(&e, [token2.address.clone(), token3.address.clone()]);
+    let tokens13 = Vec::from_array(&e, [token1.address.clone(), token3.address.clone()]);
+    let (pool_index12, _) = router.init_standard_pool(&admin, &tokens12, &30);
+    let (pool_index23, _) = router.init_standard_pool(&admin, &tokens23, &30);
+    let (pool_index13, _) = router.init_standard_pool(&admin, &tokens13, &30);
+    token1.mint(&admin, &101000);
+    token2.mint(&admin, &200000);
+    token3.mint(&admin, &101000);
+    for (tokens, pool_index, amount) in [
+        (tokens12.clone(), pool_index12.clone(), 100000),
+        (tokens23.clone(), pool_index23.clone(), 100000),
+        (tokens13.clone(), pool_index13.clone(), 1000),
+    ] {
+        router.deposit(
+            &admin,
+            &tokens,
+            &pool_index,
+            &Vec::from_array(&e, [amount, amount]),
+            &0,
+        );
+    }
+
+    // direct pool is the only option with a single hop
+    let direct_route = vec![
+        &e,
+        (
+            tokens13.clone(),
+            pool_index13.clone(),
+            token3.address.clone(),
+        ),
+    ];
+    assert_eq!(
+        router.find_best_route(&token1.address, &token3.address, &500, &1),
+        (
+            direct_route.clone(),
+            router.estimate_swap(
+                &tokens13,
+                &token1.address,
+                &token3.address,
+                &pool_index13,
+                &500
+            )
+        )
+    );
+
+    // deep pools through the intermediate token give better price
+    let (route, expected_out) = router.find_best_route(&token1.address, &token3.address, &500, &3);
+    assert_eq!(
+        route,
+        vec![
+            &e,
+            (
+                tokens12.clone(),
+                pool_index12.clone(),
+                token2.address.clone()
+            ),
+            (
+                tokens23.clone(),
+                pool_index23.clone(),
+                token3.address.clone()
+            ),
+        ]
+    );
+
+    let swapper = Address::generate(&e);
+    token1.mint(&swapper, &500);
+    assert_eq!(
+        router.swap_chained(&swapper, &route, &token1.address, &500, &expected_out),
+        expected_out
+    );
+
+    assert_eq!(
+        router
+            .try_find_best_route(&token1.address, &token3.address, &500, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(322))
+    );
+    assert_eq!(
+        router
+            .try_find_best_route(&token1.address, &token3.address, &500, &4)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(322))
+    );
+    assert_eq!(
+        router
+            .try_find_best_route(&token1.address, &token4.address, &500, &3)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(323))
+    );
+}
+
+#[test]
+fn test_find_best_route_tokens_sets_index() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let admin = setup.admin;
+    let [token1, token2, token3, _] = setup.tokens;
+    setup.reward_token.mint(&admin, &10_0000000);
+
+    // simulate tokens sets created before the index was introduced
+    e.as_contract(&router.address, || set_tokens_sets_indexed(&e, &u128::MAX));
+    let tokens12 = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let tokens23 = Vec::from_array(&e, [token2.address.clone(), token3.address.clone()]);
+    let (pool_index12, _) = router.init_standard_pool(&admin, &tokens12, &30);
+    let (pool_index23, _) = router.init_standard_pool(&admin, &tokens23, &30);
+    token1.mint(&admin, &10000);
+    token2.mint(&admin, &20000);
+    token3.mint(&admin, &10000);
+    for (tokens, pool_index) in [
+        (tokens12.clone(), pool_index12.clone()),
+        (tokens23.clone(), pool_index23.clone()),
+    ] {
+        router.deposit(
+            &admin,
+            &tokens,
+            &pool_index,
+            &Vec::from_array(&e, [10000, 10000]),
+            &0,
+        );
+    }
+    e.as_contract(&router.address, || set_tokens_sets_indexed(&e, &0));
+
+    assert_eq!(
+        router
+            .try_find_best_route(&token1.address, &token3.address, &100, &2)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(325))
+    );
+    assert_eq!(router.fill_tokens_sets_index(&1), 1);
+    assert_eq!(
+        router
+            .try_find_best_route(&token1.address, &token3.address, &100, &2)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(325))
+    );
+    assert_eq!(router.fill_tokens_sets_index(&10), 2);
+    assert_eq!(
+        router.find_best_route(&token1.address, &token3.address, &100, &2),
+        (
+            vec![
+                &e,
+                (
+                    tokens12.clone(),
+                    pool_index12.clone(),
+                    token2.address.clone()
+                ),
+                (
+                    tokens23.clone(),
+                    pool_index23.clone(),
+                    token3.address.clone()
+                ),
+            ],
+            96
+        )
+    );
+
+    // new tokens sets are indexed on creation once the index is complete
+    let tokens13 = Vec::from_array(&e, [token1.address.clone(), token3.address.clone()]);
+    router.init_standard_pool(&admin, &tokens13, &30);
+    assert_eq!(router.get_tokens_sets_count(), 3);
+    assert_eq!(router.fill_tokens_sets_index(&10), 3);
+}
+
+#[test]
+fn test_estimate_swap_chained() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let admin = setup.admin;
+    let [token1, token2, token3, _] = setup.tokens;
+    setup.reward_token.mint(&admin, &10_0000000);
+
+    let tokens1 = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let tokens2 = Vec::from_array(&e, [token2.address.clone(), token3.address.clone()]);
+    let (pool_index1, pool_address1) = router.init_standard_pool(&admin, &tokens1, &30);
+    let (pool_index2, pool_address2) = router.init_standard_pool(&admin, &tokens2, &30);
+    token1.mint(&admin, &10000);
+    token2.mint(&admin, &20000);
+    token3.mint(&admin, &10000);
+    router.deposit(
+        &admin,
+        &tokens1,
+        &pool_index1,
+        &Vec::from_array(&e, [10000, 10000]),
+        &0,
+    );
+    router.deposit(
+        &admin,
+        &tokens2,
+        &pool_index2,
+        &Vec::from_array(&e, [10000, 10000]),
+        &0,
+    );
+
+    let swaps_chain = vec![
+        &e,
+        (tokens1.clone(), pool_index1.clone(), token2.address.clone()),
+        (tokens2.clone(), pool_index2.clone(), token3.address.clone()),
+    ];
+    assert_eq!(
+        router.estimate_swap_chained(&swaps_chain, &token1.address, &100),
+        vec![
+            &e,
+            SwapHopEstimate {
+                pool: pool_address1.clone(),
+                token_in: token1.address.clone(),
+                token_out: token2.address.clone(),
+                in_amount: 100,
+                out_amount: 98,
+                fee: 1,
+            },
+            SwapHopEstimate {
+                pool: pool_address2.clone(),
+                token_in: token2.address.clone(),
+                token_out: token3.address.clone(),
+                in_amount: 98,
+                out_amount: 96,
+                fee: 1,
+            },
+        ]
+    );
+    assert_eq!(
+        router.estimate_swap_chained_strict_receive(&swaps_chain, &token1.address, &96),
+        vec![
+            &e,
+            SwapHopEstimate {
+                pool: pool_address1.clone(),
+                token_in: token1.address.clone(),
+                token_out: token2.address.clone(),
+                in_amount: 100,
+                out_amount: 98,
+                fee: 1,
+            },
+            SwapHopEstimate {
+                pool: pool_address2.clone(),
+                token_in: token2.address.clone(),
+                token_out: token3.address.clone(),
+                in_amount: 98,
+                out_amount: 96,
+                fee: 1,
+            },
+        ]
+    );
+
+    // estimate matches the actual swap
+    let swapper = Address::generate(&e);
+    token1.mint(&swapper, &100);
+    assert_eq!(
+        router.swap_chained(&swapper, &swaps_chain, &token1.address, &100, &0),
+        96
+    );
+    assert_eq!(
+        router
+            .try_estimate_swap_chained(&vec![&e], &token1.address, &100)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(307))
+    );
+}
+
+#[test]
+fn test_recipient_and_deadline() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let admin = setup.admin;
+    let [token1, token2, token3, _] = setup.tokens;
+    setup.reward_token.mint(&admin, &10_0000000);
+
+    let tokens1 = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let tokens2 = Vec::from_array(&e, [token2.address.clone(), token3.address.clone()]);
+    let (pool_index1, _) = router.init_standard_pool(&admin, &tokens1, &30);
+    let (pool_index2, _) = router.init_standard_pool(&admin, &tokens2, &30);
+    let token_share = test_token::Client::new(&e, &router.share_id(&tokens1, &pool_index1));
+
+    let user = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    token1.mint(&user, &20000);
+    token2.mint(&user, &20000);
+    token3.mint(&user, &10000);
+
+    jump(&e, 100);
+    let deadline = e.ledger().timestamp();
+
+    // deposit shares go to the recipient
+    router.deposit_to(
+        &user,
+        &tokens1,
+        &pool_index1,
+        &Vec::from_array(&e, [10000, 10000]),
+        &0,
+        &recipient,
+        &deadline,
+    );
+    router.deposit(
+        &user,
+        &tokens2,
+        &pool_index2,
+        &Vec::from_array(&e, [10000, 10000]),
+        &0,
+    );
+    assert_eq!(token_share.balance(&user), 0);
+    assert_eq!(token_share.balance(&recipient), 10000);
+
+    // swap output goes to the recipient
+    let swaps_chain = vec![
+        &e,
+        (tokens1.clone(), pool_index1.clone(), token2.address.clone()),
+        (tokens2.clone(), pool_index2.clone(), token3.address.clone()),
+    ];
+    assert_eq!(
+        router.swap_chained_to(
+            &user,
+            &swaps_chain,
+            &token1.address,
+            &100,
+            &96,
+            &recipient,
+            &deadline,
+        ),
+        96
+    );
+    assert_eq!(token1.balance(&user), 9900);
+    assert_eq!(token3.balance(&user), 0);
+    assert_eq!(token3.balance(&recipient), 96);
+
+    // unspent input goes back to the user
+    let spent = router.swap_chained_strict_receive_to(
+        &user,
+        &swaps_chain,
+        &token1.address,
+        &96,
+        &1000,
+        &recipient,
+        &deadline,
+    );
+    assert_eq!(token1.balance(&user), 9900 - spent as i128);
+    assert_eq!(token3.balance(&recipient), 192);
+
+    // withdrawn tokens go to the recipient
+    let amounts = router.withdraw_to(
+        &recipient,
+        &tokens1,
+        &pool_index1,
+        &1000,
+        &Vec::from_array(&e, [0, 0]),
+        &user,
+        &deadline,
+    );
+    assert_eq!(token_share.balance(&recipient), 9000);
+    assert_eq!(token1.balance(&recipient), 0);
+    assert_eq!(token2.balance(&recipient), 0);
+    assert_eq!(
+        token1.balance(&user),
+        9900 - spent as i128 + amounts.get(0).unwrap() as i128
+    );
+
+    // expired calls are rejected
+    jump(&e, 1);
+    assert_eq!(
+        router
+            .try_swap_chained_to(
+                &user,
+                &swaps_chain,
+                &token1.address,
+                &100,
+                &0,
+                &recipient,
+                &deadline,
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(324))
+    );
+    assert_eq!(
+        router
+            .try_swap_chained_strict_receive_to(
+                &user,
+                &swaps_chain,
+                &token1.address,
+                &96,
+                &1000,
+                &recipient,
+                &deadline,
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(324))
+    );
+    assert_eq!(
+        router
+            .try_deposit_to(
+                &user,
+                &tokens1,
+                &pool_index1,
+                &Vec::from_array(&e, [100, 100]),
+                &0,
+                &recipient,
+                &deadline,
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(324))
+    );
+    assert_eq!(
+        router
+            .try_withdraw_to(
+                &recipient,
+                &tokens1,
+                &pool_index1,
+                &1000,
+                &Vec::from_array(&e, [0, 0]),
+                &recipient,
+                &deadline,
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(324))
+    );
+}
+
 #[test]
 fn test_create_pool_payment() {
     let setup = Setup::default();
@@ -2831,6 +3411,13 @@ fn test_upgrade_events() {
     let setup = Setup::default();
     let contract = setup.router;
     let new_wasm_hash = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "Router",
+        &new_wasm_hash,
+    );
 
     contract.commit_upgrade(&setup.admin, &new_wasm_hash);
     assert_eq!(
@@ -2910,6 +3497,13 @@ fn test_emergency_upgrade() {
     let setup = Setup::default();
     let contract = setup.router;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -2926,6 +3520,13 @@ fn test_regular_upgrade() {
     let setup = Setup::default();
     let contract = setup.router;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
 
     assert_eq!(contract.get_emergency_mode(), false);
     assert_ne!(contract.version(), 130);
@@ -2937,3 +3538,470 @@ fn test_regular_upgrade() {
 
     assert_eq!(contract.version(), 130)
 }
+
+#[test]
+fn test_admin_state() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let e = setup.env;
+    let admin_new = Address::generate(&e);
+    let new_wasm = install_dummy_wasm(&e);
+    allow_wasm(&e, &router.address, &setup.admin, "Router", &new_wasm);
+
+    let state = router.get_admin_state();
+    assert_eq!(state.roles.len(), 6);
+    let admin_state = state.roles.get(0).unwrap();
+    assert_eq!(admin_state.name, symbol_short!("Admin"));
+    assert_eq!(admin_state.addresses, vec![&e, setup.admin.clone()]);
+    assert_eq!(admin_state.future_address, None);
+    assert_eq!(admin_state.deadline, 0);
+    assert_eq!(
+        state.roles.get(5).unwrap().addresses,
+        vec![&e, setup.emergency_pause_admin.clone()]
+    );
+    assert_eq!(admin_state.signers, None);
+    assert_eq!(admin_state.future_signers, None);
+    assert_eq!(admin_state.transfer_delay.value, ADMIN_ACTIONS_DELAY);
+    assert_eq!(state.delays.len(), 3);
+    assert_eq!(state.delays.get(0).unwrap().future_value, None);
+    assert_eq!(state.future_wasm, None);
+    assert_eq!(state.upgrade_deadline, 0);
+    assert_eq!(state.pending_allowlist_changes.len(), 0);
+    assert_eq!(state.pending_actions.len(), 0);
+    assert!(!state.emergency_mode);
+    assert!(!state.emergency_mode_state.enabled);
+
+    let signers = vec![&e, setup.admin.clone(), admin_new.clone()];
+    let next_wasm = install_dummy_wasm(&e);
+    router.commit_transfer_ownership(&setup.admin, &symbol_short!("Admin"), &admin_new);
+    router.commit_admin_signers(&setup.admin, &signers, &2);
+    router.commit_role_transfer_delay(&setup.admin, &Symbol::new(&e, "RewardsAdmin"), &86400);
+    router.commit_delay(&setup.admin, &Symbol::new(&e, "Upgrade"), &86400);
+    router.commit_wasm_allowlist_change(&setup.admin, &symbol_short!("Router"), &next_wasm, &true);
+    router.commit_upgrade(&setup.admin, &new_wasm);
+    router.enable_emergency_mode(&setup.emergency_admin, &EmergencyScope::Upgrade, &86400);
+
+    let state = router.get_admin_state();
+    let admin_state = state.roles.get(0).unwrap();
+    assert_eq!(admin_state.addresses, vec![&e, setup.admin.clone()]);
+    assert_eq!(admin_state.future_address, Some(admin_new));
+    assert_eq!(
+        admin_state.deadline,
+        e.ledger().timestamp() + ADMIN_ACTIONS_DELAY
+    );
+    assert_eq!(admin_state.future_signers.unwrap().signers, signers);
+    assert_eq!(
+        admin_state.signers_deadline,
+        e.ledger().timestamp() + ADMIN_ACTIONS_DELAY
+    );
+    let rewards_admin_state = state.roles.get(2).unwrap();
+    assert_eq!(rewards_admin_state.transfer_delay.value, 0);
+    assert_eq!(rewards_admin_state.transfer_delay.future_value, Some(86400));
+    let upgrade_delay = state.delays.get(1).unwrap();
+    assert_eq!(upgrade_delay.name, Symbol::new(&e, "Upgrade"));
+    assert_eq!(upgrade_delay.future_value, Some(86400));
+    assert_eq!(state.future_wasm, Some(new_wasm));
+    assert_eq!(
+        state.upgrade_deadline,
+        e.ledger().timestamp() + ADMIN_ACTIONS_DELAY
+    );
+    let allowlist_change = state.pending_allowlist_changes.get(0).unwrap();
+    assert_eq!(allowlist_change.wasm_hash, next_wasm);
+    assert_eq!(
+        allowlist_change.deadline,
+        e.ledger().timestamp() + WASM_ALLOWLIST_DELAY
+    );
+    assert!(state.emergency_mode);
+    assert_eq!(state.emergency_mode_state.scope, EmergencyScope::Upgrade);
+    assert_eq!(
+        state.emergency_mode_state.expires_at,
+        e.ledger().timestamp() + 86400
+    );
+}
+
+#[test]
+fn test_migrate() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let [token1, token2, _, _] = setup.tokens;
+    let tokens = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let user1 = Address::generate(&e);
+    setup.reward_token.mint(&user1, &10_0000000);
+
+    // freshly initialized contract doesn't need migration
+    assert_eq!(router.get_storage_version(), router.version());
+    assert_eq!(
+        router.try_migrate(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2912))
+    );
+
+    // simulate upgrade from the previous version
+    e.as_contract(&router.address, || {
+        upgrade::set_storage_version(&e, router.version() - 1)
+    });
+    assert_eq!(
+        router
+            .try_init_standard_pool(&user1, &tokens, &30)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2914))
+    );
+    assert!(router.try_migrate(&setup.emergency_admin).is_err());
+    router.migrate(&setup.admin);
+    assert_eq!(router.get_storage_version(), router.version());
+    router.init_standard_pool(&user1, &tokens, &30);
+
+    // storage cannot be migrated back
+    e.as_contract(&router.address, || {
+        upgrade::set_storage_version(&e, router.version() + 1)
+    });
+    assert_eq!(
+        router.try_migrate(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2913))
+    );
+}
+
+#[test]
+fn test_pools_upgrade() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let [token1, token2, token3, token4] = setup.tokens;
+    let user1 = Address::generate(&e);
+    setup.reward_token.mint(&user1, &10_0000000);
+
+    let tokens1 = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let tokens2 = Vec::from_array(&e, [token3.address.clone(), token4.address.clone()]);
+    let (_, pool1) = router.init_standard_pool(&user1, &tokens1, &30);
+    let (_, pool2) = router.init_standard_pool(&user1, &tokens2, &30);
+    // stableswap pools are not affected by the standard pools upgrade
+    router.init_stableswap_pool(&user1, &tokens1, &10);
+
+    let new_wasm = testutils::install_liq_pool_hash(&e);
+    let new_token_wasm = testutils::install_token_wasm(&e);
+    let standard = LiquidityPoolType::ConstantProduct;
+
+    assert_eq!(
+        router
+            .try_commit_pools_upgrade(
+                &setup.admin,
+                &LiquidityPoolType::Custom,
+                &new_wasm,
+                &new_token_wasm,
+                &0,
+                &2
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(316))
+    );
+
+    // first part of the rollout
+    assert_eq!(
+        router.commit_pools_upgrade(&setup.admin, &standard, &new_wasm, &new_token_wasm, &0, &1),
+        (vec![&e, pool1.clone()], Vec::new(&e))
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 1);
+    assert_eq!(
+        router.get_pools_upgrade_status(&0, &2),
+        vec![
+            &e,
+            (pool1.clone(), PoolUpgradeStatus::Committed),
+            (pool2.clone(), PoolUpgradeStatus::Pending),
+        ]
+    );
+
+    // another target cannot be committed until the current one is finished
+    assert_eq!(
+        router
+            .try_commit_pools_upgrade(
+                &setup.admin,
+                &standard,
+                &new_token_wasm,
+                &new_token_wasm,
+                &0,
+                &2
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(317))
+    );
+
+    // resume rollout, already committed pools are skipped
+    assert_eq!(
+        router.commit_pools_upgrade(&setup.admin, &standard, &new_wasm, &new_token_wasm, &0, &2),
+        (vec![&e, pool2.clone()], Vec::new(&e))
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 2);
+
+    assert_eq!(
+        router.revert_pools_upgrade(&setup.admin, &1, &2),
+        (vec![&e, pool2.clone()], Vec::new(&e))
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 1);
+
+    // pool rejecting the upgrade is reported and stays committed
+    assert_eq!(
+        router.apply_pools_upgrade(&setup.admin, &0, &2),
+        (Vec::new(&e), vec![&e, pool1.clone()])
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 1);
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
+    // simulate the new wasm requiring storage migration
+    let pool1_client = testutils::standard_pool::Client::new(&e, &pool1);
+    e.as_contract(&pool1, || {
+        upgrade::set_storage_version(&e, pool1_client.version() - 1)
+    });
+    assert_eq!(
+        router.apply_pools_upgrade(&setup.admin, &0, &2),
+        (vec![&e, pool1.clone()], Vec::new(&e))
+    );
+    // upgraded pools are migrated right away
+    assert_eq!(pool1_client.get_storage_version(), pool1_client.version());
+    assert_eq!(
+        pool1_client.get_upgrade_history().last().unwrap().wasm_hash,
+        new_wasm
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 0);
+    assert_eq!(
+        router.get_pools_upgrade_status(&0, &2),
+        vec![
+            &e,
+            (pool1.clone(), PoolUpgradeStatus::Applied),
+            (pool2.clone(), PoolUpgradeStatus::Pending),
+        ]
+    );
+    assert_eq!(
+        router.apply_pools_upgrade(&setup.admin, &0, &2),
+        (Vec::new(&e), Vec::new(&e))
+    );
+    assert_eq!(
+        router.get_pools_upgrade_target(),
+        PoolsUpgradeTarget {
+            pool_type: standard,
+            wasm_hash: new_wasm,
+            token_wasm_hash: new_token_wasm.clone(),
+        }
+    );
+
+    // rollout finished, new target is allowed
+    allow_wasm(
+        &e,
+        &router.address,
+        &setup.admin,
+        "StandardPool",
+        &new_token_wasm,
+    );
+    router.commit_pools_upgrade(
+        &setup.admin,
+        &standard,
+        &new_token_wasm,
+        &new_token_wasm,
+        &1,
+        &2,
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 1);
+}
+
+#[test]
+fn test_pools_upgrade_disallowed_after_commit() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let [token1, token2, _, _] = setup.tokens;
+    let user1 = Address::generate(&e);
+    setup.reward_token.mint(&user1, &10_0000000);
+
+    let tokens = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let (_, pool) = router.init_standard_pool(&user1, &tokens, &30);
+
+    let new_wasm = testutils::install_liq_pool_hash(&e);
+    let new_token_wasm = testutils::install_token_wasm(&e);
+    let standard = LiquidityPoolType::ConstantProduct;
+    router.commit_pools_upgrade(&setup.admin, &standard, &new_wasm, &new_token_wasm, &0, &1);
+
+    // pool relies on the router allowlist, so the removed hash is not applied
+    let pool_kind = Symbol::new(&e, "StandardPool");
+    router.commit_wasm_allowlist_change(&setup.admin, &pool_kind, &new_wasm, &false);
+    jump(&e, WASM_ALLOWLIST_DELAY);
+    router.apply_wasm_allowlist_change(&setup.admin, &pool_kind, &new_wasm);
+    assert_eq!(
+        router
+            .try_apply_pools_upgrade(&setup.admin, &0, &1)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2915))
+    );
+
+    assert_eq!(
+        router.revert_pools_upgrade(&setup.admin, &0, &1),
+        (vec![&e, pool], Vec::new(&e))
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 0);
+}
+
+#[test]
+fn test_pools_upgrade_skips_pool_with_pending_upgrade() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let [token1, token2, token3, token4] = setup.tokens;
+    let user1 = Address::generate(&e);
+    setup.reward_token.mint(&user1, &10_0000000);
+
+    let tokens1 = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let tokens2 = Vec::from_array(&e, [token3.address.clone(), token4.address.clone()]);
+    let (_, pool1) = router.init_standard_pool(&user1, &tokens1, &30);
+    let (_, pool2) = router.init_standard_pool(&user1, &tokens2, &30);
+
+    let new_wasm = testutils::install_liq_pool_hash(&e);
+    let new_token_wasm = testutils::install_token_wasm(&e);
+    let standard = LiquidityPoolType::ConstantProduct;
+
+    // upgrade committed on the pool directly does not block the rollout
+    testutils::standard_pool::Client::new(&e, &pool1).commit_upgrade(
+        &setup.admin,
+        &new_wasm,
+        &new_token_wasm,
+    );
+    assert_eq!(
+        router.commit_pools_upgrade(&setup.admin, &standard, &new_wasm, &new_token_wasm, &0, &2),
+        (vec![&e, pool2.clone()], vec![&e, pool1.clone()])
+    );
+    assert_eq!(router.get_pools_upgrade_committed(), 1);
+    assert_eq!(
+        router.get_pools_upgrade_status(&0, &2),
+        vec![
+            &e,
+            (pool1.clone(), PoolUpgradeStatus::Pending),
+            (pool2.clone(), PoolUpgradeStatus::Committed),
+        ]
+    );
+}
+
+#[test]
+fn test_pools_upgrade_legacy_pool() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let [token1, token2, _, _] = setup.tokens;
+    let user1 = Address::generate(&e);
+    setup.reward_token.mint(&user1, &10_0000000);
+
+    // pool deployed before the fleet upgrade accepts the upgrade only from its admin
+    let legacy_wasm = testutils::install_legacy_liq_pool_hash(&e);
+    allow_wasm(
+        &e,
+        &router.address,
+        &setup.admin,
+        "StandardPool",
+        &legacy_wasm,
+    );
+    router.set_pool_hash(&setup.admin, &legacy_wasm);
+    let tokens = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let (_, pool) = router.init_standard_pool(&user1, &tokens, &30);
+
+    let new_wasm = testutils::install_liq_pool_hash(&e);
+    let new_token_wasm = testutils::install_token_wasm(&e);
+    let standard = LiquidityPoolType::ConstantProduct;
+    assert_eq!(
+        router.commit_pools_upgrade(&setup.admin, &standard, &new_wasm, &new_token_wasm, &0, &1),
+        (vec![&e, pool.clone()], Vec::new(&e))
+    );
+
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
+    assert_eq!(
+        router.apply_pools_upgrade(&setup.admin, &0, &1),
+        (vec![&e, pool.clone()], Vec::new(&e))
+    );
+    // legacy storage is migrated to the new wasm right away
+    let pool_client = testutils::standard_pool::Client::new(&e, &pool);
+    assert_eq!(pool_client.get_storage_version(), pool_client.version());
+    assert_eq!(
+        router.get_pools_upgrade_status(&0, &1),
+        vec![&e, (pool.clone(), PoolUpgradeStatus::Applied)]
+    );
+
+    // running wasm is recorded by the migration, so the next upgrade can be rolled back
+    pool_client.commit_upgrade(&setup.admin, &new_wasm, &new_token_wasm);
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
+    pool_client.apply_upgrade(&setup.admin);
+    assert_eq!(
+        pool_client.rollback_upgrade(&setup.emergency_admin),
+        new_wasm
+    );
+}
+
+#[test]
+fn test_kill_switches() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let router = setup.router;
+    let [token1, token2, _, _] = setup.tokens;
+    let tokens = Vec::from_array(&e, [token1.address.clone(), token2.address.clone()]);
+    let user1 = Address::generate(&e);
+    setup.reward_token.mint(&user1, &10_0000000);
+    token1.mint(&user1, &10000);
+    token2.mint(&user1, &10000);
+
+    let (pool_hash, _pool_address) = router.init_standard_pool(&user1, &tokens, &30);
+    let amounts = Vec::from_array(&e, [1000, 1000]);
+    router.deposit(&user1, &tokens, &pool_hash, &amounts, &0);
+
+    router.kill_deposit(&setup.pause_admin);
+    router.kill_swap(&setup.pause_admin);
+    router.kill_claim(&setup.pause_admin);
+    assert!(router.get_is_killed_deposit());
+    assert!(router.get_is_killed_swap());
+    assert!(router.get_is_killed_claim());
+
+    assert_eq!(
+        router
+            .try_deposit(&user1, &tokens, &pool_hash, &amounts, &0)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(318))
+    );
+    assert_eq!(
+        router
+            .try_swap(
+                &user1,
+                &tokens,
+                &token1.address,
+                &token2.address,
+                &pool_hash,
+                &100,
+                &0
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(319))
+    );
+    assert_eq!(
+        router
+            .try_swap_chained(
+                &user1,
+                &vec![
+                    &e,
+                    (tokens.clone(), pool_hash.clone(), token2.address.clone())
+                ],
+                &token1.address,
+                &100,
+                &0
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(319))
+    );
+    assert_eq!(
+        router.try_claim(&user1, &tokens, &pool_hash).unwrap_err(),
+        Ok(Error::from_contract_error(320))
+    );
+
+    // users are always able to exit
+    router.withdraw(
+        &user1,
+        &tokens,
+        &pool_hash,
+        &100,
+        &Vec::from_array(&e, [0, 0]),
+    );
+
+    router.unkill_deposit(&setup.pause_admin);
+    assert!(!router.get_is_killed_deposit());
+    router.deposit(&user1, &tokens, &pool_hash, &amounts, &0);
+}
@@ -3,10 +3,11 @@
 use crate::testutils::{
     install_liq_pool_hash, install_stableswap_liq_pool_hash, install_token_wasm, Setup,
 };
-use access_control::constants::ADMIN_ACTIONS_DELAY;
+use access_control::constants::{ADMIN_ACTIONS_DELAY, MIN_DELAY, WASM_ALLOWLIST_DELAY};
+use access_control::role::RoleConfig;
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{symbol_short, Address, Symbol, Vec};
-use utils::test_utils::{install_dummy_wasm, jump};
+use soroban_sdk::{symbol_short, Address, Error, Symbol, Vec};
+use utils::test_utils::{allow_wasm, install_dummy_wasm, jump};
 
 // test admin transfer ownership
 #[test]
@@ -402,6 +403,13 @@ fn test_commit_upgrade() {
     let setup = Setup::default();
     let router = setup.router;
     let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
     let user = Address::generate(&setup.env);
 
     for (addr, is_ok) in [
@@ -425,7 +433,15 @@ fn test_apply_upgrade_third_party_user() {
     let user = Address::generate(&setup.env);
     // Upgrade router code with token wasm as it has no dependencies
     // after upgrade router cannot be reused
-    router.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(router.try_apply_upgrade(&user).is_err());
 }
@@ -436,7 +452,15 @@ fn test_apply_upgrade_emergency_admin() {
     let router = setup.router;
     // Upgrade router code with token wasm as it has no dependencies
     // after upgrade router cannot be reused
-    router.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(router.try_apply_upgrade(&setup.emergency_admin).is_err());
 }
@@ -448,7 +472,15 @@ fn test_apply_upgrade_admin() {
     assert_ne!(router.version(), 130);
     // Upgrade router code with token wasm as it has no dependencies
     // after upgrade router cannot be reused
-    router.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(router.try_apply_upgrade(&setup.admin).is_ok());
     assert_eq!(router.version(), 130);
@@ -460,7 +492,15 @@ fn test_apply_upgrade_rewards_admin() {
     let router = setup.router;
     // Upgrade router code with token wasm as it has no dependencies
     // after upgrade router cannot be reused
-    router.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(router.try_apply_upgrade(&setup.rewards_admin).is_err());
 }
@@ -471,7 +511,15 @@ fn test_apply_upgrade_operations_admin() {
     let router = setup.router;
     // Upgrade router code with token wasm as it has no dependencies
     // after upgrade router cannot be reused
-    router.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(router.try_apply_upgrade(&setup.operations_admin).is_err());
 }
@@ -482,7 +530,15 @@ fn test_apply_upgrade_pause_admin() {
     let router = setup.router;
     // Upgrade router code with token wasm as it has no dependencies
     // after upgrade router cannot be reused
-    router.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(router.try_apply_upgrade(&setup.pause_admin).is_err());
 }
@@ -493,7 +549,15 @@ fn test_apply_upgrade_emergency_pause_admin() {
     let router = setup.router;
     // Upgrade router code with token wasm as it has no dependencies
     // after upgrade router cannot be reused
-    router.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(router
         .try_apply_upgrade(&setup.emergency_pause_admin)
@@ -644,3 +708,626 @@ fn test_remove_pool() {
         );
     }
 }
+
+#[test]
+fn test_admin_signers_threshold() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let signer1 = Address::generate(&setup.env);
+    let signer2 = Address::generate(&setup.env);
+    let signer3 = Address::generate(&setup.env);
+    let pool_hash = install_liq_pool_hash(&setup.env);
+    let signers = Vec::from_array(
+        &setup.env,
+        [signer1.clone(), signer2.clone(), signer3.clone()],
+    );
+
+    // signers set can be configured only by the admin and is applied after the delay
+    assert!(router
+        .try_commit_admin_signers(&signer1, &signers, &2)
+        .is_err());
+    router.commit_admin_signers(&setup.admin, &signers, &2);
+    assert_eq!(
+        router.get_future_admin_signers(),
+        Some((signers.clone(), 2))
+    );
+    assert_eq!(router.get_admin_signers(), (Vec::new(&setup.env), 0));
+    assert_eq!(
+        router.try_apply_admin_signers(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    jump(&setup.env, ADMIN_ACTIONS_DELAY);
+    router.apply_admin_signers(&setup.admin);
+    assert_eq!(router.get_admin_signers(), (signers.clone(), 2));
+    assert_eq!(router.get_future_admin_signers(), None);
+
+    // signer holds the role, but can't act alone until the threshold is reached
+    assert!(router.has_role(&Symbol::new(&setup.env, "Admin"), &signer1));
+    assert!(!router.has_role(&Symbol::new(&setup.env, "Admin"), &setup.admin));
+    assert!(router.try_set_pool_hash(&setup.admin, &pool_hash).is_err());
+    assert!(router.try_set_pool_hash(&signer1, &pool_hash).is_err());
+
+    // regular admin methods are authorized by the signer together with the nominated cosigners
+    for (signer, cosigners) in [
+        (
+            setup.admin.clone(),
+            Vec::from_array(&setup.env, [signer2.clone()]),
+        ),
+        (
+            signer1.clone(),
+            Vec::from_array(&setup.env, [signer1.clone()]),
+        ),
+        (
+            signer1.clone(),
+            Vec::from_array(&setup.env, [setup.admin.clone()]),
+        ),
+        (
+            signer1.clone(),
+            Vec::from_array(&setup.env, [signer3.clone(), signer3.clone()]),
+        ),
+    ] {
+        assert!(router.try_set_admin_cosigners(&signer, &cosigners).is_err());
+    }
+    router.set_admin_cosigners(&signer1, &Vec::from_array(&setup.env, [signer3.clone()]));
+    assert_eq!(
+        router.get_admin_cosigners(&signer1),
+        Vec::from_array(&setup.env, [signer3.clone()])
+    );
+    router.set_pool_hash(&signer1, &pool_hash);
+    assert!(setup
+        .env
+        .auths()
+        .iter()
+        .any(|(address, _)| address == &signer3));
+    assert!(router.try_set_pool_hash(&signer2, &pool_hash).is_err());
+
+    // switch back to the single admin, nominations are dropped with the signers set
+    router.commit_admin_signers(&signer1, &Vec::new(&setup.env), &0);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY);
+    router.apply_admin_signers(&signer1);
+    assert_eq!(router.get_admin_signers(), (Vec::new(&setup.env), 0));
+    assert_eq!(router.get_admin_cosigners(&signer1), Vec::new(&setup.env));
+    assert!(router.try_set_pool_hash(&setup.admin, &pool_hash).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #106)")]
+fn test_admin_signers_bad_threshold() {
+    let setup = Setup::default();
+    let signer = Address::generate(&setup.env);
+    setup
+        .router
+        .commit_admin_signers(&setup.admin, &Vec::from_array(&setup.env, [signer]), &2);
+}
+
+#[test]
+fn test_rewards_admin_delayed_transfer() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let role_name = Symbol::new(&setup.env, "RewardsAdmin");
+    let rewards_admin_new = Address::generate(&setup.env);
+    let delay = 3 * 86400;
+
+    // zero deadline stands for no pending change, so move off the genesis timestamp
+    jump(&setup.env, 1);
+    assert_eq!(router.get_role_transfer_delay(&role_name), 0);
+    assert!(router
+        .try_commit_role_transfer_delay(&setup.rewards_admin, &role_name, &delay)
+        .is_err());
+    // role is not delayed yet, so the delay can be applied right away
+    router.commit_role_transfer_delay(&setup.admin, &role_name, &delay);
+    assert_eq!(router.get_future_role_transfer_delay(&role_name), delay);
+    assert!(router
+        .try_apply_role_transfer_delay(&setup.rewards_admin, &role_name)
+        .is_err());
+    router.apply_role_transfer_delay(&setup.admin, &role_name);
+    assert_eq!(router.get_role_transfer_delay(&role_name), delay);
+
+    // delay cannot be dropped before the current one passes
+    router.commit_role_transfer_delay(&setup.admin, &role_name, &0);
+    assert_eq!(
+        router
+            .try_apply_role_transfer_delay(&setup.admin, &role_name)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    router.revert_role_transfer_delay(&setup.admin, &role_name);
+    assert_eq!(router.get_future_role_transfer_delay(&role_name), delay);
+
+    // address cannot be replaced instantly anymore
+    assert!(router
+        .try_set_privileged_addrs(
+            &setup.admin,
+            &rewards_admin_new,
+            &setup.operations_admin,
+            &setup.pause_admin,
+            &Vec::from_array(&setup.env, [setup.emergency_pause_admin.clone()]),
+        )
+        .is_err());
+
+    router.commit_transfer_ownership(&setup.admin, &role_name, &rewards_admin_new);
+    assert_eq!(router.get_future_address(&role_name), rewards_admin_new);
+    router.revert_transfer_ownership(&setup.admin, &role_name);
+    assert!(router
+        .try_apply_transfer_ownership(&setup.admin, &role_name)
+        .is_err());
+
+    router.commit_transfer_ownership(&setup.admin, &role_name, &rewards_admin_new);
+    jump(&setup.env, delay - 1);
+    assert!(router
+        .try_apply_transfer_ownership(&setup.admin, &role_name)
+        .is_err());
+    jump(&setup.env, 1);
+    router.apply_transfer_ownership(&setup.admin, &role_name);
+
+    assert_eq!(
+        router.get_privileged_addrs().get(role_name).unwrap(),
+        Vec::from_array(&setup.env, [rewards_admin_new])
+    );
+}
+
+#[test]
+fn test_admins_transfer_delay_floor() {
+    let setup = Setup::default();
+    let router = setup.router;
+
+    for role_name in [
+        Symbol::new(&setup.env, "Admin"),
+        Symbol::new(&setup.env, "EmergencyAdmin"),
+    ] {
+        assert_eq!(
+            router.get_role_transfer_delay(&role_name),
+            ADMIN_ACTIONS_DELAY
+        );
+        for delay in [0, MIN_DELAY - 1] {
+            assert_eq!(
+                router
+                    .try_commit_role_transfer_delay(&setup.admin, &role_name, &delay)
+                    .unwrap_err(),
+                Ok(Error::from_contract_error(107))
+            );
+        }
+        router.commit_role_transfer_delay(&setup.admin, &role_name, &MIN_DELAY);
+    }
+
+    jump(&setup.env, ADMIN_ACTIONS_DELAY);
+    for role_name in [
+        Symbol::new(&setup.env, "Admin"),
+        Symbol::new(&setup.env, "EmergencyAdmin"),
+    ] {
+        router.apply_role_transfer_delay(&setup.admin, &role_name);
+        assert_eq!(router.get_role_transfer_delay(&role_name), MIN_DELAY);
+    }
+}
+
+#[test]
+fn test_emergency_pause_admins_delayed_change() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let role_name = Symbol::new(&setup.env, "EmergencyPauseAdmin");
+    let emergency_pause_admin_new = Address::generate(&setup.env);
+    let delay = 86400;
+
+    // zero deadline stands for no pending change, so move off the genesis timestamp
+    jump(&setup.env, 1);
+    router.commit_role_transfer_delay(&setup.admin, &role_name, &delay);
+    router.apply_role_transfer_delay(&setup.admin, &role_name);
+
+    // members list cannot be replaced instantly anymore
+    assert!(router
+        .try_set_privileged_addrs(
+            &setup.admin,
+            &setup.rewards_admin,
+            &setup.operations_admin,
+            &setup.pause_admin,
+            &Vec::from_array(&setup.env, [emergency_pause_admin_new.clone()]),
+        )
+        .is_err());
+
+    for (addr, is_ok) in [
+        (emergency_pause_admin_new.clone(), false),
+        (setup.emergency_admin.clone(), false),
+        (setup.rewards_admin.clone(), false),
+        (setup.operations_admin.clone(), false),
+        (setup.pause_admin.clone(), false),
+        (setup.emergency_pause_admin.clone(), false),
+        (setup.admin.clone(), true),
+    ] {
+        assert_eq!(
+            router
+                .try_commit_add_role_member(&addr, &role_name, &emergency_pause_admin_new)
+                .is_ok(),
+            is_ok
+        );
+    }
+    assert_eq!(
+        router.get_pending_role_members_change(&role_name),
+        Some((emergency_pause_admin_new.clone(), true))
+    );
+    assert!(router
+        .try_apply_role_members_change(&setup.admin, &role_name)
+        .is_err());
+    jump(&setup.env, delay);
+    assert_eq!(
+        router.apply_role_members_change(&setup.admin, &role_name),
+        Vec::from_array(
+            &setup.env,
+            [
+                setup.emergency_pause_admin.clone(),
+                emergency_pause_admin_new.clone()
+            ]
+        )
+    );
+    assert_eq!(router.get_pending_role_members_change(&role_name), None);
+
+    router.commit_remove_role_member(&setup.admin, &role_name, &setup.emergency_pause_admin);
+    router.revert_role_members_change(&setup.admin, &role_name);
+    router.commit_remove_role_member(&setup.admin, &role_name, &setup.emergency_pause_admin);
+    jump(&setup.env, delay);
+    assert_eq!(
+        router.apply_role_members_change(&setup.admin, &role_name),
+        Vec::from_array(&setup.env, [emergency_pause_admin_new])
+    );
+}
+
+#[test]
+fn test_commit_delay() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let user = Address::generate(&setup.env);
+    let delay_name = Symbol::new(&setup.env, "Upgrade");
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.emergency_admin, false),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, false),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+        (setup.admin.clone(), true),
+    ] {
+        assert_eq!(
+            router
+                .try_commit_delay(&addr, &delay_name, &(7 * 86400))
+                .is_ok(),
+            is_ok
+        );
+    }
+    router.revert_delay(&setup.admin, &delay_name);
+}
+
+#[test]
+fn test_upgrade_delay() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let delay_name = Symbol::new(&setup.env, "Upgrade");
+    let new_delay = 7 * 86400;
+
+    router.commit_delay(&setup.admin, &delay_name, &new_delay);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY);
+    router.apply_delay(&setup.admin, &delay_name);
+    assert_eq!(router.get_delay(&delay_name), new_delay);
+
+    // admin actions delay is not affected
+    assert_eq!(
+        router.get_delay(&Symbol::new(&setup.env, "AdminActions")),
+        ADMIN_ACTIONS_DELAY
+    );
+
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
+    jump(&setup.env, new_delay - 1);
+    assert!(router.try_apply_upgrade(&setup.admin).is_err());
+    jump(&setup.env, 1);
+    assert!(router.try_apply_upgrade(&setup.admin).is_ok());
+}
+
+#[test]
+fn test_rollback_upgrade() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let user = Address::generate(&setup.env);
+
+    for addr in [
+        user,
+        setup.admin.clone(),
+        setup.rewards_admin,
+        setup.operations_admin,
+        setup.pause_admin,
+        setup.emergency_pause_admin,
+    ] {
+        assert_eq!(
+            router.try_rollback_upgrade(&addr).unwrap_err(),
+            Ok(Error::from_contract_error(102))
+        );
+    }
+    // nothing to rollback to as contract has not been upgraded yet
+    assert_eq!(
+        router
+            .try_rollback_upgrade(&setup.emergency_admin)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2911))
+    );
+    assert_eq!(router.get_upgrade_history().len(), 0);
+
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &router.address,
+        &setup.admin,
+        "Router",
+        &new_wasm,
+    );
+    router.commit_upgrade(&setup.admin, &new_wasm);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    router.apply_upgrade(&setup.admin);
+
+    // router code has been replaced, check stored history directly
+    let history = setup
+        .env
+        .as_contract(&router.address, || upgrade::get_upgrade_history(&setup.env));
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.wasm_hash, new_wasm);
+    assert_eq!(record.admin, setup.admin);
+    assert_eq!(record.timestamp, setup.env.ledger().timestamp());
+}
+
+#[test]
+fn test_wasm_allowlist() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let user = Address::generate(&setup.env);
+    let router_kind = Symbol::new(&setup.env, "Router");
+    let audited_wasm = install_dummy_wasm(&setup.env);
+    let unaudited_wasm = install_liq_pool_hash(&setup.env);
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.admin.clone(), true),
+        (setup.emergency_admin, false),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, false),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+    ] {
+        assert_eq!(
+            router
+                .try_commit_wasm_allowlist_change(&addr, &router_kind, &audited_wasm, &true)
+                .is_ok(),
+            is_ok
+        );
+    }
+    router.revert_wasm_allowlist_change(&setup.admin, &router_kind, &audited_wasm);
+    assert_eq!(
+        router.get_pending_wasm_allowlist_changes(),
+        Vec::new(&setup.env)
+    );
+    assert_eq!(
+        router
+            .try_revert_wasm_allowlist_change(&setup.admin, &router_kind, &audited_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2907))
+    );
+    assert_eq!(
+        router
+            .try_get_allowed_wasms(&Symbol::new(&setup.env, "Unknown"))
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2916))
+    );
+
+    // the whole release is committed at once, one pending change per (kind, hash)
+    let token_kind = Symbol::new(&setup.env, "Token");
+    router.commit_wasm_allowlist_change(&setup.admin, &router_kind, &audited_wasm, &true);
+    router.commit_wasm_allowlist_change(&setup.admin, &token_kind, &audited_wasm, &true);
+    assert_eq!(
+        router
+            .try_commit_wasm_allowlist_change(&setup.admin, &router_kind, &audited_wasm, &false)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2906))
+    );
+    let changes = router.get_pending_wasm_allowlist_changes();
+    assert_eq!(changes.len(), 2);
+    for change in changes.iter() {
+        assert_eq!(
+            change.deadline,
+            setup.env.ledger().timestamp() + WASM_ALLOWLIST_DELAY
+        );
+    }
+    jump(&setup.env, WASM_ALLOWLIST_DELAY - 1);
+    assert_eq!(
+        router
+            .try_apply_wasm_allowlist_change(&setup.admin, &router_kind, &audited_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    jump(&setup.env, 1);
+    router.apply_wasm_allowlist_change(&setup.admin, &router_kind, &audited_wasm);
+    router.apply_wasm_allowlist_change(&setup.admin, &token_kind, &audited_wasm);
+    assert_eq!(
+        router.get_pending_wasm_allowlist_changes(),
+        Vec::new(&setup.env)
+    );
+    assert!(router.is_wasm_allowed(&token_kind, &audited_wasm));
+    assert_eq!(
+        router.get_allowed_wasms(&router_kind),
+        Vec::from_array(&setup.env, [audited_wasm.clone()])
+    );
+
+    // only explicitly allowed hashes are accepted for the kind
+    assert!(router.is_wasm_allowed(&router_kind, &audited_wasm));
+    assert!(!router.is_wasm_allowed(&router_kind, &unaudited_wasm));
+    assert_eq!(
+        router
+            .try_commit_upgrade(&setup.admin, &unaudited_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2915))
+    );
+    assert_eq!(
+        router
+            .try_set_pool_hash(&setup.admin, &audited_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2915))
+    );
+    router.commit_upgrade(&setup.admin, &audited_wasm);
+
+    // unconfigured allowlist rejects everything
+    let plane_kind = Symbol::new(&setup.env, "Plane");
+    assert_eq!(router.get_allowed_wasms(&plane_kind), Vec::new(&setup.env));
+    assert!(!router.is_wasm_allowed(&plane_kind, &audited_wasm));
+}
+
+// router kill switches
+#[test]
+fn test_kill_switches() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let user = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user.clone(), false),
+        (setup.admin.clone(), true),
+        (setup.emergency_admin.clone(), false),
+        (setup.rewards_admin.clone(), false),
+        (setup.operations_admin.clone(), false),
+        (setup.pause_admin.clone(), true),
+        (setup.emergency_pause_admin.clone(), true),
+    ] {
+        assert_eq!(router.try_kill_deposit(&addr).is_ok(), is_ok);
+        assert_eq!(router.try_kill_swap(&addr).is_ok(), is_ok);
+        assert_eq!(router.try_kill_claim(&addr).is_ok(), is_ok);
+    }
+}
+
+#[test]
+fn test_unkill_switches() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let user = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user.clone(), false),
+        (setup.admin.clone(), true),
+        (setup.emergency_admin.clone(), false),
+        (setup.rewards_admin.clone(), false),
+        (setup.operations_admin.clone(), false),
+        (setup.pause_admin.clone(), true),
+        (setup.emergency_pause_admin.clone(), false),
+    ] {
+        assert_eq!(router.try_unkill_deposit(&addr).is_ok(), is_ok);
+        assert_eq!(router.try_unkill_swap(&addr).is_ok(), is_ok);
+        assert_eq!(router.try_unkill_claim(&addr).is_ok(), is_ok);
+    }
+}
+
+#[test]
+fn test_register_role() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let role_name = Symbol::new(&setup.env, "FeeOperator");
+    let config = RoleConfig {
+        has_many_users: false,
+        is_transfer_delayed: false,
+    };
+
+    for addr in [
+        Address::generate(&setup.env),
+        setup.emergency_admin.clone(),
+        setup.rewards_admin.clone(),
+        setup.operations_admin.clone(),
+        setup.pause_admin.clone(),
+        setup.emergency_pause_admin.clone(),
+    ] {
+        assert_eq!(
+            router
+                .try_register_role(&addr, &role_name, &config)
+                .unwrap_err(),
+            Ok(Error::from_contract_error(102))
+        );
+    }
+    router.register_role(&setup.admin, &role_name, &config);
+    assert_eq!(
+        router.get_custom_roles(),
+        Vec::from_array(&setup.env, [role_name.clone()])
+    );
+
+    // role is declared once and built-in roles cannot be overridden
+    assert_eq!(
+        router
+            .try_register_role(&setup.admin, &role_name, &config)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(105))
+    );
+    assert_eq!(
+        router
+            .try_register_role(&setup.admin, &symbol_short!("Admin"), &config)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(104))
+    );
+}
+
+#[test]
+fn test_custom_roles_granted() {
+    let setup = Setup::default();
+    let router = setup.router;
+    let operator = Address::generate(&setup.env);
+    let operator_new = Address::generate(&setup.env);
+    let updater = Address::generate(&setup.env);
+
+    // unknown role is rejected
+    let operator_role = Symbol::new(&setup.env, "FeeOperator");
+    assert_eq!(
+        router.try_has_role(&operator_role, &operator).unwrap_err(),
+        Ok(Error::from_contract_error(104))
+    );
+
+    // single address role with delayed transfer
+    router.register_role(
+        &setup.admin,
+        &operator_role,
+        &RoleConfig {
+            has_many_users: false,
+            is_transfer_delayed: true,
+        },
+    );
+    assert!(!router.has_role(&operator_role, &operator));
+    router.commit_transfer_ownership(&setup.admin, &operator_role, &operator);
+    router.apply_transfer_ownership(&setup.admin, &operator_role);
+    assert!(router.has_role(&operator_role, &operator));
+
+    router.commit_transfer_ownership(&setup.admin, &operator_role, &operator_new);
+    assert!(router
+        .try_apply_transfer_ownership(&setup.admin, &operator_role)
+        .is_err());
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    router.apply_transfer_ownership(&setup.admin, &operator_role);
+    assert!(!router.has_role(&operator_role, &operator));
+    assert!(router.has_role(&operator_role, &operator_new));
+
+    // multiple addresses role
+    let updater_role = Symbol::new(&setup.env, "OracleUpdater");
+    router.register_role(
+        &setup.admin,
+        &updater_role,
+        &RoleConfig {
+            has_many_users: true,
+            is_transfer_delayed: false,
+        },
+    );
+    router.commit_add_role_member(&setup.admin, &updater_role, &updater);
+    router.apply_role_members_change(&setup.admin, &updater_role);
+    assert!(router.has_role(&updater_role, &updater));
+    assert!(!router.has_role(&updater_role, &operator_new));
+
+    // custom role doesn't grant any built-in powers
+    assert!(router
+        .try_commit_transfer_ownership(&operator_new, &operator_role, &operator)
+        .is_err());
+}
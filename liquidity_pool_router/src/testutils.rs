@@ -4,6 +4,7 @@ extern crate std;
 use crate::LiquidityPoolRouterClient;
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+use utils::test_utils::allow_wasm;
 
 pub(crate) mod test_token {
     use soroban_sdk::contractimport;
@@ -40,6 +41,16 @@ pub fn install_liq_pool_hash(e: &Env) -> BytesN<32> {
     e.deployer().upload_contract_wasm(standard_pool::WASM)
 }
 
+// Pool code deployed before the fleet upgrade, without upgrade history and storage version
+pub mod legacy_standard_pool {
+    soroban_sdk::contractimport!(file = "../contracts/soroban_liquidity_pool_contract.wasm");
+}
+
+pub fn install_legacy_liq_pool_hash(e: &Env) -> BytesN<32> {
+    e.deployer()
+        .upload_contract_wasm(legacy_standard_pool::WASM)
+}
+
 pub mod stableswap_pool {
     soroban_sdk::contractimport!(
         file = "../target/wasm32-unknown-unknown/release/soroban_liquidity_pool_stableswap_contract.wasm"
@@ -58,7 +69,10 @@ mod pool_plane {
 }
 
 pub fn create_plane_contract<'a>(e: &Env) -> pool_plane::Client<'a> {
-    pool_plane::Client::new(e, &e.register(pool_plane::WASM, ()))
+    let plane = pool_plane::Client::new(e, &e.register(pool_plane::WASM, ()));
+    // storage version is set on init, plane refuses updates until then
+    plane.init_admin(&Address::generate(e));
+    plane
 }
 
 mod liquidity_calculator {
@@ -163,8 +177,18 @@ impl Default for Setup<'_> {
             &pause_admin,
             &Vec::from_array(&env, [emergency_pause_admin.clone()]),
         );
+        let stableswap_pool_hash = install_stableswap_liq_pool_hash(&env);
+        allow_wasm(&env, &router.address, &admin, "StandardPool", &pool_hash);
+        allow_wasm(
+            &env,
+            &router.address,
+            &admin,
+            "StableSwapPool",
+            &stableswap_pool_hash,
+        );
+        allow_wasm(&env, &router.address, &admin, "Token", &token_hash);
         router.set_pool_hash(&admin, &pool_hash);
-        router.set_stableswap_pool_hash(&admin, &install_stableswap_liq_pool_hash(&env));
+        router.set_stableswap_pool_hash(&admin, &stableswap_pool_hash);
         router.set_token_hash(&admin, &token_hash);
         router.set_reward_token(&admin, &reward_token.address);
         router.configure_init_pool_payment(
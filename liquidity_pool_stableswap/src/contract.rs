@@ -1,16 +1,24 @@
-use crate::pool_constants::{FEE_DENOMINATOR, MAX_A, MAX_A_CHANGE, MIN_RAMP_TIME};
+use crate::pool_constants::{
+    FEE_DENOMINATOR, MAX_A, MAX_A_CHANGE, MAX_MA_HALF_LIFE, MIN_MA_HALF_LIFE, PRICE_PRECISION,
+};
 use crate::pool_interface::{
-    AdminInterfaceTrait, LiquidityPoolInterfaceTrait, LiquidityPoolTrait, ManagedLiquidityPool,
-    RewardsTrait, UpgradeableContract, UpgradeableLPTokenTrait,
+    AdminInterfaceTrait, FlashLoanTrait, LiquidityPoolInterfaceTrait, LiquidityPoolTrait,
+    ManagedLiquidityPool, PriceOracleTrait, RewardsTrait, UpgradeableContract,
+    UpgradeableLPTokenTrait,
 };
 use crate::storage::{
-    get_admin_actions_deadline, get_decimals, get_fee, get_future_a, get_future_a_time,
-    get_future_fee, get_initial_a, get_initial_a_time, get_is_killed_claim, get_is_killed_deposit,
-    get_is_killed_swap, get_plane, get_precision, get_precision_mul, get_reserves, get_router,
-    get_token_future_wasm, get_tokens, has_plane, put_admin_actions_deadline, put_decimals,
-    put_fee, put_future_a, put_future_a_time, put_future_fee, put_initial_a, put_initial_a_time,
-    put_reserves, put_tokens, set_is_killed_claim, set_is_killed_deposit, set_is_killed_swap,
-    set_plane, set_router, set_token_future_wasm,
+    get_admin_actions_deadline, get_admin_balances, get_admin_fee, get_admin_fee_deadline,
+    get_decimals, get_fee, get_future_a, get_future_a_time, get_future_admin_fee, get_future_fee,
+    get_future_ma_half_life, get_initial_a, get_initial_a_time, get_is_killed_claim,
+    get_is_killed_deposit, get_is_killed_swap, get_last_prices, get_ma_half_life,
+    get_ma_half_life_deadline, get_ma_last_time, get_ma_prices, get_plane, get_precision,
+    get_precision_mul, get_reserves, get_router, get_token_future_wasm, get_tokens, has_plane,
+    put_admin_actions_deadline, put_admin_balances, put_admin_fee, put_decimals, put_fee,
+    put_future_a, put_future_a_time, put_future_admin_fee, put_future_fee, put_initial_a,
+    put_initial_a_time, put_last_prices, put_ma_prices, put_reserves, put_tokens,
+    set_admin_fee_deadline, set_future_ma_half_life, set_is_killed_claim, set_is_killed_deposit,
+    set_is_killed_swap, set_ma_half_life, set_ma_half_life_deadline, set_ma_last_time, set_plane,
+    set_router, set_token_future_wasm,
 };
 use crate::token::create_contract;
 use token_share::{
@@ -21,19 +29,37 @@ use token_share::{
 use crate::errors::LiquidityPoolError;
 use crate::events::Events;
 use crate::normalize::{read_decimals, xp};
+use crate::oracle::get_moving_average;
 use crate::plane::update_plane;
 use crate::plane_interface::Plane;
 use crate::rewards::get_rewards_manager;
 use access_control::access::{AccessControl, AccessControlTrait};
-use access_control::constants::ADMIN_ACTIONS_DELAY;
-use access_control::emergency::{get_emergency_mode, set_emergency_mode};
+use access_control::delays::{
+    apply_delay, commit_delay, get_delay, get_future_delay, revert_delay, Delay, DelaysTrait,
+};
+use access_control::emergency::{
+    enable_scoped_emergency_mode, get_emergency_mode, get_emergency_mode_state,
+    toggle_emergency_mode, EmergencyModeState, EmergencyScope,
+};
 use access_control::errors::AccessControlError;
 use access_control::events::Events as AccessControlEvents;
-use access_control::interface::TransferableContract;
+use access_control::interface::{
+    AdminSignersContract, ConfigurableDelaysContract, EmergencyModeContract, RoleTimelockContract,
+    TransferableContract,
+};
 use access_control::management::{MultipleAddressesManagementTrait, SingleAddressManagementTrait};
 use access_control::role::Role;
 use access_control::role::SymbolRepresentation;
-use access_control::transfer::TransferOwnershipTrait;
+use access_control::threshold::{
+    apply_admin_signers, commit_admin_signers, get_admin_cosigners, get_admin_signers,
+    get_future_admin_signers, revert_admin_signers, set_admin_cosigners,
+};
+use access_control::transfer::{
+    apply_role_members_change, apply_role_transfer_delay, commit_role_member_change,
+    commit_role_transfer_delay, get_future_role_transfer_delay, get_pending_role_members_change,
+    get_role_transfer_delay, revert_role_members_change, revert_role_transfer_delay,
+    TransferOwnershipTrait,
+};
 use access_control::utils::{
     require_operations_admin_or_owner, require_pause_admin_or_owner,
     require_pause_or_emergency_pause_admin_or_owner, require_rewards_admin_or_owner,
@@ -47,11 +73,19 @@ use rewards::storage::{
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::token::Client as SorobanTokenClient;
 use soroban_sdk::{
-    contract, contractimpl, contractmeta, panic_with_error, symbol_short, Address, BytesN, Env,
-    IntoVal, Map, Symbol, Val, Vec, U256,
+    contract, contractimpl, contractmeta, panic_with_error, symbol_short, Address, Bytes, BytesN,
+    Env, IntoVal, Map, Symbol, Val, Vec, U256,
 };
+use upgrade::allowlist::{require_wasm_allowed_by, WasmKind};
 use upgrade::events::Events as UpgradeEvents;
-use upgrade::{apply_upgrade, commit_upgrade, revert_upgrade};
+use upgrade::history::UpgradeRecord;
+use upgrade::interface::AdminStateContract;
+use upgrade::view::{get_admin_state, AdminState};
+use upgrade::{
+    apply_upgrade, commit_checked_upgrade, commit_upgrade_allowed_by, get_storage_version,
+    get_upgrade_history, migrate, require_migrated, revert_upgrade, rollback_upgrade,
+    set_storage_version,
+};
 
 contractmeta!(
     key = "Description",
@@ -157,23 +191,7 @@ impl LiquidityPoolTrait for LiquidityPool {
     //
     // * The amount of token `j` that will be received.
     fn get_dy(e: Env, i: u32, j: u32, dx: u128) -> u128 {
-        // dx and dy in c-units
-        let precision_mul = get_precision_mul(&e);
-        let xp = Self::_xp(&e, &get_reserves(&e));
-
-        let x = xp.get(i).unwrap() + dx * precision_mul.get(i).unwrap();
-        let y = Self::_get_y(&e, i, j, x, &xp);
-
-        if y == 0 {
-            // pool is empty
-            return 0;
-        }
-
-        let dy = (xp.get(j).unwrap() - y - 1) / precision_mul.get(j).unwrap();
-        // The `fixed_mul_ceil` function is used to perform the multiplication
-        //  to ensure user cannot exploit rounding errors.
-        let fee = (get_fee(&e) as u128).fixed_mul_ceil(&e, &dy, &(FEE_DENOMINATOR as u128));
-        dy - fee
+        Self::_get_dy(&e, i, j, dx).0
     }
 
     // Calculate the amount of token `i` that will be sent for swapping `dy` of token `j`.
@@ -188,36 +206,7 @@ impl LiquidityPoolTrait for LiquidityPool {
     //
     // * The amount of token `i` that will be swapped.
     fn get_dx(e: Env, i: u32, j: u32, dy: u128) -> u128 {
-        // dx and dy in c-units
-        let precision_mul = get_precision_mul(&e);
-        let xp = Self::_xp(&e, &get_reserves(&e));
-        let xp_buy = xp.get(j).unwrap();
-
-        // apply fee to dy to keep swap symmetrical
-        let dy_w_fee_scaled = dy.fixed_mul_ceil(
-            &e,
-            &(FEE_DENOMINATOR as u128),
-            &((FEE_DENOMINATOR - get_fee(&e)) as u128),
-        ) * precision_mul.get(j).unwrap();
-
-        // if total value including fee is more than the reserve, math can't be done properly
-        if dy_w_fee_scaled >= xp_buy {
-            panic_with_error!(e, LiquidityPoolValidationError::InsufficientBalance);
-        }
-
-        let y_w_fee = match xp_buy.checked_sub(dy_w_fee_scaled) {
-            Some(y) => y,
-            None => panic_with_error!(e, LiquidityPoolValidationError::InsufficientBalance),
-        };
-        let x = Self::_get_y(&e, j, i, y_w_fee, &xp);
-
-        if x == 0 {
-            // pool is empty
-            return 0;
-        }
-
-        let dx = (x - xp.get(i).unwrap() + 1) / precision_mul.get(i).unwrap();
-        dx
+        Self::_get_dx(&e, i, j, dy).0
     }
 
     // Withdraw coins from the pool in an imbalanced amount.
@@ -237,6 +226,7 @@ impl LiquidityPoolTrait for LiquidityPool {
         amounts: Vec<u128>,
         max_burn_amount: u128,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
         let tokens = get_tokens(&e);
@@ -271,6 +261,7 @@ impl LiquidityPoolTrait for LiquidityPool {
 
         let d1 = Self::_get_d(&e, &Self::_xp(&e, &new_balances), amp);
 
+        let mut admin_fees = Vec::new(&e);
         for i in 0..n_coins {
             let new_balance = new_balances.get(i).unwrap();
             let ideal_balance = d1
@@ -292,10 +283,14 @@ impl LiquidityPoolTrait for LiquidityPool {
                 &(4 * (n_coins as u128 - 1) * FEE_DENOMINATOR as u128),
             );
 
-            reserves.set(i, new_balance);
+            let admin_fee = Self::_admin_fee_share(&e, fee);
+            admin_fees.push_back(admin_fee);
+            reserves.set(i, new_balance - admin_fee);
             new_balances.set(i, new_balance - fee);
         }
         put_reserves(&e, &reserves);
+        Self::_accrue_admin_fees(&e, &admin_fees);
+        Self::_upkeep_oracle(&e, &reserves);
 
         let d2 = Self::_get_d(&e, &Self::_xp(&e, &new_balances), amp);
 
@@ -379,6 +374,7 @@ impl LiquidityPoolTrait for LiquidityPool {
         i: u32,
         min_amount: u128,
     ) -> Vec<u128> {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
         // Before actual changes were made to the pool, update total rewards data and refresh user reward
@@ -389,14 +385,21 @@ impl LiquidityPoolTrait for LiquidityPool {
             .manager()
             .checkpoint_user(&user, total_shares, user_shares);
 
-        let (dy, _) = Self::_calc_withdraw_one_coin(&e, share_amount, i);
+        let (dy, dy_fee) = Self::_calc_withdraw_one_coin(&e, share_amount, i);
         if dy < min_amount {
             panic_with_error!(&e, LiquidityPoolValidationError::InMinNotSatisfied);
         }
 
+        let admin_fee = Self::_admin_fee_share(&e, dy_fee);
         let mut reserves = get_reserves(&e);
-        reserves.set(i, reserves.get(i).unwrap() - dy);
+        reserves.set(i, reserves.get(i).unwrap() - dy - admin_fee);
         put_reserves(&e, &reserves);
+        Self::_upkeep_oracle(&e, &reserves);
+        let mut admin_fees = Vec::new(&e);
+        for token_idx in 0..reserves.len() {
+            admin_fees.push_back(if token_idx == i { admin_fee } else { 0 });
+        }
+        Self::_accrue_admin_fees(&e, &admin_fees);
 
         // Redeem shares
         burn_shares(&e, &user, share_amount);
@@ -429,10 +432,215 @@ impl LiquidityPoolTrait for LiquidityPool {
 }
 
 impl LiquidityPool {
+    // Calculates admin share of the fee.
+    //
+    // # Arguments
+    //
+    // * `fee` - The fee amount.
+    //
+    // # Returns
+    //
+    // * The part of the fee which goes to the admin.
+    fn _admin_fee_share(e: &Env, fee: u128) -> u128 {
+        fee.fixed_mul_floor(e, &(get_admin_fee(e) as u128), &(FEE_DENOMINATOR as u128))
+    }
+
+    // Accrues admin fees, which are excluded from the reserves.
+    //
+    // # Arguments
+    //
+    // * `admin_fees` - The admin fee amounts for each token.
+    fn _accrue_admin_fees(e: &Env, admin_fees: &Vec<u128>) {
+        let mut admin_balances = get_admin_balances(e);
+        for i in 0..admin_balances.len() {
+            admin_balances.set(
+                i,
+                admin_balances.get(i).unwrap() + admin_fees.get(i).unwrap(),
+            );
+        }
+        put_admin_balances(e, &admin_balances);
+    }
+
+    // Calculates the amount of token `j` received for swapping `dx` of token `i`.
+    //
+    // # Arguments
+    //
+    // * `i` - The index of the token being swapped.
+    // * `j` - The index of the token being received.
+    // * `dx` - The amount of token `i` being swapped.
+    //
+    // # Returns
+    //
+    // * The amount of token `j` received and the fee charged in token `j`.
+    fn _get_dy(e: &Env, i: u32, j: u32, dx: u128) -> (u128, u128) {
+        // dx and dy in c-units
+        let precision_mul = get_precision_mul(e);
+        let xp = Self::_xp(e, &get_reserves(e));
+
+        let x = xp.get(i).unwrap() + dx * precision_mul.get(i).unwrap();
+        let y = Self::_get_y(e, i, j, x, &xp);
+
+        if y == 0 {
+            // pool is empty
+            return (0, 0);
+        }
+
+        let dy = (xp.get(j).unwrap() - y - 1) / precision_mul.get(j).unwrap();
+        // The `fixed_mul_ceil` function is used to perform the multiplication
+        //  to ensure user cannot exploit rounding errors.
+        let fee = (get_fee(e) as u128).fixed_mul_ceil(e, &dy, &(FEE_DENOMINATOR as u128));
+        (dy - fee, fee)
+    }
+
+    // Calculates the amount of token `i` sent for receiving `dy` of token `j`.
+    //
+    // # Arguments
+    //
+    // * `i` - The index of the token being swapped.
+    // * `j` - The index of the token being received.
+    // * `dy` - The amount of token `j` being received.
+    //
+    // # Returns
+    //
+    // * The amount of token `i` sent and the fee charged in token `j`.
+    fn _get_dx(e: &Env, i: u32, j: u32, dy: u128) -> (u128, u128) {
+        // dx and dy in c-units
+        let precision_mul = get_precision_mul(e);
+        let xp = Self::_xp(e, &get_reserves(e));
+        let xp_buy = xp.get(j).unwrap();
+
+        // apply fee to dy to keep swap symmetrical
+        let dy_w_fee = dy.fixed_mul_ceil(
+            e,
+            &(FEE_DENOMINATOR as u128),
+            &((FEE_DENOMINATOR - get_fee(e)) as u128),
+        );
+        let dy_w_fee_scaled = dy_w_fee * precision_mul.get(j).unwrap();
+
+        // if total value including fee is more than the reserve, math can't be done properly
+        if dy_w_fee_scaled >= xp_buy {
+            panic_with_error!(e, LiquidityPoolValidationError::InsufficientBalance);
+        }
+
+        let y_w_fee = match xp_buy.checked_sub(dy_w_fee_scaled) {
+            Some(y) => y,
+            None => panic_with_error!(e, LiquidityPoolValidationError::InsufficientBalance),
+        };
+        let x = Self::_get_y(e, j, i, y_w_fee, &xp);
+
+        if x == 0 {
+            // pool is empty
+            return (0, 0);
+        }
+
+        let dx = (x - xp.get(i).unwrap() + 1) / precision_mul.get(i).unwrap();
+        (dx, dy_w_fee - dy)
+    }
+
     fn _xp(e: &Env, reserves: &Vec<u128>) -> Vec<u128> {
         xp(e, reserves)
     }
 
+    // Calculates spot prices of all coins in units of coin 0, scaled by PRICE_PRECISION.
+    // p_i = (Ann * x_0 + D_r * x_0 / x_i) / (Ann * x_0 + D_r), where D_r = D^(n+1) / (n^n * prod(x))
+    //
+    // # Arguments
+    //
+    // * `xp` - The balances of each token in the pool.
+    // * `amp` - The amplification coefficient in the form of A*N**(N-1).
+    //
+    // # Returns
+    //
+    // * The spot prices, starting with 1 for coin 0.
+    fn _get_p(e: &Env, xp: &Vec<u128>, amp: u128) -> Vec<u128> {
+        let n_coins = xp.len();
+        let d = Self::_get_d(e, xp, amp);
+        let mut d_r = d.clone();
+        for x in xp.iter() {
+            d_r = d_r.fixed_mul_floor(e, &d, &U256::from_u128(e, x * n_coins as u128));
+        }
+
+        let x0 = U256::from_u128(e, xp.get(0).unwrap());
+        let xp0_a = U256::from_u128(e, amp * n_coins as u128).mul(&x0);
+        let denominator = xp0_a.add(&d_r);
+
+        let mut prices = Vec::from_array(e, [PRICE_PRECISION]);
+        for i in 1..n_coins {
+            let numerator = xp0_a.add(&d_r.mul(&x0).div(&U256::from_u128(e, xp.get(i).unwrap())));
+            prices.push_back(
+                numerator
+                    .fixed_mul_floor(e, &U256::from_u128(e, PRICE_PRECISION), &denominator)
+                    .to_u128()
+                    .unwrap(),
+            );
+        }
+        prices
+    }
+
+    // Calculates moving average prices as of the current timestamp.
+    // Empty if the oracle hasn't seen any pool update yet.
+    fn _get_ma_prices(e: &Env) -> Vec<u128> {
+        let mut ma_prices = get_ma_prices(e);
+        let last_prices = get_last_prices(e);
+        let elapsed = e.ledger().timestamp() - get_ma_last_time(e);
+        if elapsed == 0 {
+            return ma_prices;
+        }
+
+        let half_life = get_ma_half_life(e);
+        for i in 0..ma_prices.len() {
+            ma_prices.set(
+                i,
+                get_moving_average(
+                    e,
+                    ma_prices.get(i).unwrap(),
+                    last_prices.get(i).unwrap(),
+                    elapsed,
+                    half_life,
+                ),
+            );
+        }
+        ma_prices
+    }
+
+    // Spot prices seen by the oracle. Calculated from the reserves if the oracle has no data yet.
+    fn _get_last_prices(e: &Env) -> Vec<u128> {
+        let last_prices = get_last_prices(e);
+        if !last_prices.is_empty() {
+            return last_prices;
+        }
+
+        let xp = Self::_xp(e, &get_reserves(e));
+        if xp.iter().any(|x| x == 0) {
+            panic_with_error!(e, LiquidityPoolValidationError::EmptyPool);
+        }
+        Self::_get_p(e, &xp, Self::a(e.clone()))
+    }
+
+    // Moves the moving average towards the spot price which was in effect since the last update
+    // and records the spot price for the new reserves. Should be called after every reserves change.
+    //
+    // # Arguments
+    //
+    // * `reserves` - The updated pool reserves.
+    fn _upkeep_oracle(e: &Env, reserves: &Vec<u128>) {
+        let xp = Self::_xp(e, reserves);
+        if xp.iter().any(|x| x == 0) {
+            // price is undefined for the empty pool, keep the last known values
+            return;
+        }
+
+        let last_prices = Self::_get_p(e, &xp, Self::a(e.clone()));
+        let ma_prices = if get_ma_prices(e).is_empty() {
+            last_prices.clone()
+        } else {
+            Self::_get_ma_prices(e)
+        };
+        put_ma_prices(e, &ma_prices);
+        set_ma_last_time(e, &e.ledger().timestamp());
+        put_last_prices(e, &last_prices);
+    }
+
     // Calculates the invariant `D` for the given token balances.
     //
     // # Arguments
@@ -778,10 +986,11 @@ impl AdminInterfaceTrait for LiquidityPool {
         admin.require_auth();
         require_operations_admin_or_owner(&e, &admin);
 
-        if e.ledger().timestamp() < get_initial_a_time(&e) + MIN_RAMP_TIME {
+        let min_ramp_time = AccessControl::new(&e).get_delay(&Delay::AdminActions);
+        if e.ledger().timestamp() < get_initial_a_time(&e) + min_ramp_time {
             panic_with_error!(&e, LiquidityPoolError::RampTooEarly);
         };
-        if future_time < e.ledger().timestamp() + MIN_RAMP_TIME {
+        if future_time < e.ledger().timestamp() + min_ramp_time {
             panic_with_error!(&e, LiquidityPoolError::RampTimeLessThanMinimum);
         };
 
@@ -845,7 +1054,8 @@ impl AdminInterfaceTrait for LiquidityPool {
             panic_with_error!(e, LiquidityPoolValidationError::FeeOutOfBounds);
         }
 
-        let deadline = e.ledger().timestamp() + ADMIN_ACTIONS_DELAY;
+        let deadline =
+            e.ledger().timestamp() + AccessControl::new(&e).get_delay(&Delay::AdminActions);
         put_admin_actions_deadline(&e, &deadline);
         put_future_fee(&e, &new_fee);
 
@@ -888,10 +1098,174 @@ impl AdminInterfaceTrait for LiquidityPool {
         require_operations_admin_or_owner(&e, &admin);
 
         put_admin_actions_deadline(&e, &0);
+        set_admin_fee_deadline(&e, &0);
+        set_ma_half_life_deadline(&e, &0);
 
         Events::new(&e).revert_new_parameters();
     }
 
+    // Sets a new admin fee to be applied in the future.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `new_admin_fee` - The new share of the fee which goes to the admin.
+    fn commit_new_admin_fee(e: Env, admin: Address, new_admin_fee: u32) {
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        if get_admin_fee_deadline(&e) != 0 {
+            panic_with_error!(&e, LiquidityPoolError::AnotherActionActive);
+        }
+        if new_admin_fee > FEE_DENOMINATOR {
+            panic_with_error!(e, LiquidityPoolValidationError::AdminFeeOutOfBounds);
+        }
+
+        let deadline =
+            e.ledger().timestamp() + AccessControl::new(&e).get_delay(&Delay::AdminActions);
+        set_admin_fee_deadline(&e, &deadline);
+        put_future_admin_fee(&e, &new_admin_fee);
+
+        Events::new(&e).commit_new_admin_fee(new_admin_fee);
+    }
+
+    // Applies the committed admin fee.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn apply_new_admin_fee(e: Env, admin: Address) {
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        if e.ledger().timestamp() < get_admin_fee_deadline(&e) {
+            panic_with_error!(&e, LiquidityPoolError::ActionNotReadyYet);
+        }
+        if get_admin_fee_deadline(&e) == 0 {
+            panic_with_error!(&e, LiquidityPoolError::NoActionActive);
+        }
+
+        set_admin_fee_deadline(&e, &0);
+        let admin_fee = get_future_admin_fee(&e);
+        put_admin_fee(&e, &admin_fee);
+
+        Events::new(&e).apply_new_admin_fee(admin_fee);
+    }
+
+    // Returns the share of the fee which goes to the admin.
+    fn get_admin_fee(e: Env) -> u32 {
+        get_admin_fee(&e)
+    }
+
+    // Returns the admin fee to be applied in the future.
+    fn get_future_admin_fee(e: Env) -> u32 {
+        get_future_admin_fee(&e)
+    }
+
+    // Sets a new half-life of the moving average price oracle to be applied in the future.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `new_half_life` - The new half-life in seconds.
+    fn commit_new_ma_half_life(e: Env, admin: Address, new_half_life: u64) {
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        if get_ma_half_life_deadline(&e) != 0 {
+            panic_with_error!(&e, LiquidityPoolError::AnotherActionActive);
+        }
+        if new_half_life < MIN_MA_HALF_LIFE || new_half_life > MAX_MA_HALF_LIFE {
+            panic_with_error!(&e, LiquidityPoolError::MaHalfLifeOutOfBounds);
+        }
+
+        let deadline =
+            e.ledger().timestamp() + AccessControl::new(&e).get_delay(&Delay::AdminActions);
+        set_ma_half_life_deadline(&e, &deadline);
+        set_future_ma_half_life(&e, &new_half_life);
+
+        Events::new(&e).commit_new_ma_half_life(new_half_life);
+    }
+
+    // Applies the committed half-life of the moving average price oracle.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn apply_new_ma_half_life(e: Env, admin: Address) {
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        if e.ledger().timestamp() < get_ma_half_life_deadline(&e) {
+            panic_with_error!(&e, LiquidityPoolError::ActionNotReadyYet);
+        }
+        if get_ma_half_life_deadline(&e) == 0 {
+            panic_with_error!(&e, LiquidityPoolError::NoActionActive);
+        }
+
+        set_ma_half_life_deadline(&e, &0);
+
+        // zero half-life would break the moving average, so the value is validated once again
+        let half_life = get_future_ma_half_life(&e);
+        if half_life < MIN_MA_HALF_LIFE || half_life > MAX_MA_HALF_LIFE {
+            panic_with_error!(&e, LiquidityPoolError::MaHalfLifeOutOfBounds);
+        }
+
+        // period before the change is averaged with the old half-life
+        if !get_ma_prices(&e).is_empty() {
+            put_ma_prices(&e, &Self::_get_ma_prices(&e));
+            set_ma_last_time(&e, &e.ledger().timestamp());
+        }
+
+        set_ma_half_life(&e, &half_life);
+
+        Events::new(&e).apply_new_ma_half_life(half_life);
+    }
+
+    // Returns the admin fees accrued for each token.
+    fn get_admin_balances(e: Env) -> Vec<u128> {
+        get_admin_balances(&e)
+    }
+
+    // Sends accrued admin fees to the destination address.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `destination` - The address to receive the fees.
+    //
+    // # Returns
+    //
+    // * The amounts of tokens withdrawn.
+    fn withdraw_admin_fees(e: Env, admin: Address, destination: Address) -> Vec<u128> {
+        require_migrated(&e, Self::version());
+        admin.require_auth();
+        require_operations_admin_or_owner(&e, &admin);
+
+        let tokens = get_tokens(&e);
+        let admin_balances = get_admin_balances(&e);
+        let mut zero_balances = Vec::new(&e);
+        for _ in 0..admin_balances.len() {
+            zero_balances.push_back(0);
+        }
+        put_admin_balances(&e, &zero_balances);
+
+        for i in 0..admin_balances.len() {
+            let amount = admin_balances.get(i).unwrap();
+            if amount > 0 {
+                SorobanTokenClient::new(&e, &tokens.get(i).unwrap()).transfer(
+                    &e.current_contract_address(),
+                    &destination,
+                    &(amount as i128),
+                );
+            }
+        }
+
+        Events::new(&e).withdraw_admin_fees(destination, admin_balances.clone());
+
+        admin_balances
+    }
+
     // Stops the pool deposits instantly.
     //
     // # Arguments
@@ -1087,6 +1461,7 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         }
 
         access_control.set_role_address(&Role::Admin, &admin);
+        set_storage_version(&e, Self::version());
         access_control.set_role_address(&Role::EmergencyAdmin, &privileged_addrs.0);
         access_control.set_role_address(&Role::RewardsAdmin, &privileged_addrs.1);
         access_control.set_role_address(&Role::OperationsAdmin, &privileged_addrs.2);
@@ -1197,6 +1572,7 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
     //
     // A tuple containing a vector of actual amounts of each token deposited and a u128 representing the amount of pool tokens minted.
     fn deposit(e: Env, user: Address, amounts: Vec<u128>, min_shares: u128) -> (Vec<u128>, u128) {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
         if get_is_killed_deposit(&e) {
@@ -1255,6 +1631,7 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         // We need to recalculate the invariant accounting for fees
         // to calculate fair user's share
         let mut d2 = d1.clone();
+        let mut admin_fees = Vec::new(&e);
         let balances = if token_supply > 0 {
             let mut result = new_balances.clone();
             // Only account for fees if we are not the first to deposit
@@ -1281,7 +1658,9 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
                     &(FEE_DENOMINATOR as u128 * 4 * (n_coins as u128 - 1)),
                 );
 
-                result.set(i, new_balance);
+                let admin_fee = Self::_admin_fee_share(&e, fee);
+                admin_fees.push_back(admin_fee);
+                result.set(i, new_balance - admin_fee);
                 new_balances.set(i, new_balances.get(i).unwrap() - fee);
             }
             d2 = Self::_get_d(&e, &Self::_xp(&e, &new_balances), amp);
@@ -1290,6 +1669,10 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
             new_balances
         };
         put_reserves(&e, &balances);
+        if !admin_fees.is_empty() {
+            Self::_accrue_admin_fees(&e, &admin_fees);
+        }
+        Self::_upkeep_oracle(&e, &balances);
 
         // Calculate, how much pool tokens to mint
         let mint_amount = if token_supply == 0 {
@@ -1344,6 +1727,7 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         in_amount: u128,
         out_min: u128,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
         if get_is_killed_swap(&e) {
             panic_with_error!(e, LiquidityPoolError::PoolSwapKilled);
@@ -1376,6 +1760,8 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         let dy_fee = dy.fixed_mul_ceil(&e, &(get_fee(&e) as u128), &(FEE_DENOMINATOR as u128));
 
         // Convert all to real units
+        let dy_admin_fee =
+            Self::_admin_fee_share(&e, dy_fee) / precision_mul.get(out_idx).unwrap();
         let dy = (dy - dy_fee) / precision_mul.get(out_idx).unwrap();
         if dy < out_min {
             panic_with_error!(e, LiquidityPoolValidationError::OutMinNotSatisfied);
@@ -1384,8 +1770,17 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         // Change balances exactly in same way as we change actual ERC20 coin amounts
         let mut reserves = get_reserves(&e);
         reserves.set(in_idx, old_balances.get(in_idx).unwrap() + in_amount);
-        reserves.set(out_idx, old_balances.get(out_idx).unwrap() - dy);
+        reserves.set(
+            out_idx,
+            old_balances.get(out_idx).unwrap() - dy - dy_admin_fee,
+        );
         put_reserves(&e, &reserves);
+        let mut admin_fees = Vec::new(&e);
+        for i in 0..reserves.len() {
+            admin_fees.push_back(if i == out_idx { dy_admin_fee } else { 0 });
+        }
+        Self::_accrue_admin_fees(&e, &admin_fees);
+        Self::_upkeep_oracle(&e, &reserves);
 
         let token_out = coins.get(out_idx).unwrap();
         let token_client = SorobanTokenClient::new(&e, &token_out);
@@ -1417,6 +1812,22 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         Self::get_dy(e, in_idx, out_idx, in_amount)
     }
 
+    // Estimates the result of a swap operation along with the fee charged by the pool.
+    //
+    // # Arguments
+    //
+    // * `in_idx` - The index of the input token to be swapped.
+    // * `out_idx` - The index of the output token to be received.
+    // * `in_amount` - The amount of the input token to be swapped.
+    //
+    // # Returns
+    //
+    // The estimated amount of the output token that would be received
+    //  and the fee charged in the output token.
+    fn estimate_swap_with_fee(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> (u128, u128) {
+        Self::_get_dy(&e, in_idx, out_idx, in_amount)
+    }
+
     // Swaps tokens in the pool, receiving fixed amount of out tokens.
     //
     // # Arguments
@@ -1438,6 +1849,7 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         out_amount: u128,
         in_max: u128,
     ) -> u128 {
+        require_migrated(&e, Self::version());
         user.require_auth();
         if get_is_killed_swap(&e) {
             panic_with_error!(e, LiquidityPoolError::PoolSwapKilled);
@@ -1503,10 +1915,20 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         }
 
         // Update reserves
+        let dy_admin_fee = Self::_admin_fee_share(&e, dy_w_fee - out_amount);
         let mut reserves = get_reserves(&e);
         reserves.set(in_idx, old_balances.get(in_idx).unwrap() + dx);
-        reserves.set(out_idx, old_balances.get(out_idx).unwrap() - out_amount);
+        reserves.set(
+            out_idx,
+            old_balances.get(out_idx).unwrap() - out_amount - dy_admin_fee,
+        );
         put_reserves(&e, &reserves);
+        let mut admin_fees = Vec::new(&e);
+        for i in 0..reserves.len() {
+            admin_fees.push_back(if i == out_idx { dy_admin_fee } else { 0 });
+        }
+        Self::_accrue_admin_fees(&e, &admin_fees);
+        Self::_upkeep_oracle(&e, &reserves);
 
         let token_out = coins.get(out_idx).unwrap();
         let token_client = SorobanTokenClient::new(&e, &token_out);
@@ -1542,6 +1964,28 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
         Self::get_dx(e, in_idx, out_idx, out_amount)
     }
 
+    // Estimate amount of coins to retrieve using swap_strict_receive function
+    //  along with the fee charged by the pool.
+    //
+    // # Arguments
+    //
+    // * `in_idx` - The index of the input token to be swapped.
+    // * `out_idx` - The index of the output token to be received.
+    // * `out_amount` - The amount of the output token to be received.
+    //
+    // # Returns
+    //
+    // The estimated amount of the input token that would be sent
+    //  and the fee charged in the output token.
+    fn estimate_swap_strict_receive_with_fee(
+        e: Env,
+        in_idx: u32,
+        out_idx: u32,
+        out_amount: u128,
+    ) -> (u128, u128) {
+        Self::_get_dx(&e, in_idx, out_idx, out_amount)
+    }
+
     // Withdraws tokens from the pool.
     //
     // # Arguments
@@ -1554,6 +1998,7 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
     //
     // A vector of actual amounts of each token withdrawn.
     fn withdraw(e: Env, user: Address, share_amount: u128, min_amounts: Vec<u128>) -> Vec<u128> {
+        require_migrated(&e, Self::version());
         user.require_auth();
 
         let tokens = Self::get_tokens(e.clone());
@@ -1591,6 +2036,7 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
             token_client.transfer(&e.current_contract_address(), &user, &(value as i128));
         }
         put_reserves(&e, &reserves);
+        Self::_upkeep_oracle(&e, &reserves);
 
         // Redeem shares
         burn_shares(&e, &user, share_amount);
@@ -1630,6 +2076,162 @@ impl LiquidityPoolInterfaceTrait for LiquidityPool {
     }
 }
 
+#[contractimpl]
+impl PriceOracleTrait for LiquidityPool {
+    // Returns the exponential moving average price of the coin.
+    //
+    // # Arguments
+    //
+    // * `i` - The index of the coin to be priced.
+    //
+    // # Returns
+    //
+    // * The moving average price in units of coin 0, scaled by 1e18.
+    fn price_oracle(e: Env, i: u32) -> u128 {
+        if i >= get_tokens(&e).len() {
+            panic_with_error!(&e, LiquidityPoolValidationError::InTokenOutOfBounds);
+        }
+
+        let ma_prices = Self::_get_ma_prices(&e);
+        if ma_prices.is_empty() {
+            return Self::_get_last_prices(&e).get(i).unwrap();
+        }
+        ma_prices.get(i).unwrap()
+    }
+
+    // Returns the latest spot price of the coin seen by the oracle.
+    //
+    // # Arguments
+    //
+    // * `i` - The index of the coin to be priced.
+    //
+    // # Returns
+    //
+    // * The spot price in units of coin 0, scaled by 1e18.
+    fn last_price(e: Env, i: u32) -> u128 {
+        if i >= get_tokens(&e).len() {
+            panic_with_error!(&e, LiquidityPoolValidationError::InTokenOutOfBounds);
+        }
+
+        Self::_get_last_prices(&e).get(i).unwrap()
+    }
+
+    // Returns the half-life of the moving average in seconds.
+    fn get_ma_half_life(e: Env) -> u64 {
+        get_ma_half_life(&e)
+    }
+
+    // Returns the half-life of the moving average to be applied in the future.
+    fn get_future_ma_half_life(e: Env) -> u64 {
+        get_future_ma_half_life(&e)
+    }
+}
+
+#[contractimpl]
+impl FlashLoanTrait for LiquidityPool {
+    // Lends pool reserves to the receiver contract within a single transaction.
+    //
+    // # Arguments
+    //
+    // * `initiator` - The address of the user initiating the loan. Passed to the receiver callback.
+    // * `receiver` - The address of the contract to receive the loan and the callback.
+    // * `amounts` - A vector of amounts of each token to borrow.
+    // * `data` - Arbitrary data passed to the receiver callback.
+    //
+    // # Returns
+    //
+    // A vector of fees paid for each token.
+    fn flash_loan(
+        e: Env,
+        initiator: Address,
+        receiver: Address,
+        amounts: Vec<u128>,
+        data: Bytes,
+    ) -> Vec<u128> {
+        require_migrated(&e, Self::version());
+        initiator.require_auth();
+
+        if get_is_killed_swap(&e) {
+            panic_with_error!(e, LiquidityPoolError::PoolSwapKilled);
+        }
+
+        let tokens = get_tokens(&e);
+        let n_coins = tokens.len();
+        if amounts.len() != n_coins {
+            panic_with_error!(&e, LiquidityPoolValidationError::WrongInputVecSize);
+        }
+        if amounts.iter().all(|amount| amount == 0) {
+            panic_with_error!(&e, LiquidityPoolValidationError::ZeroAmount);
+        }
+
+        let mut reserves = get_reserves(&e);
+        let mut fees = Vec::new(&e);
+        let mut balances_before = Vec::new(&e);
+        for i in 0..n_coins {
+            let amount = amounts.get(i).unwrap();
+            if amount > reserves.get(i).unwrap() {
+                panic_with_error!(&e, LiquidityPoolValidationError::InsufficientBalance);
+            }
+            fees.push_back(amount.fixed_mul_ceil(
+                &e,
+                &(get_fee(&e) as u128),
+                &(FEE_DENOMINATOR as u128),
+            ));
+
+            let token_client = SorobanTokenClient::new(&e, &tokens.get(i).unwrap());
+            balances_before.push_back(token_client.balance(&e.current_contract_address()) as u128);
+            if amount > 0 {
+                token_client.transfer(&e.current_contract_address(), &receiver, &(amount as i128));
+            }
+        }
+
+        e.invoke_contract::<()>(
+            &receiver,
+            &Symbol::new(&e, "on_flash_loan"),
+            Vec::from_array(
+                &e,
+                [
+                    initiator.into_val(&e),
+                    e.current_contract_address().into_val(&e),
+                    tokens.into_val(&e),
+                    amounts.into_val(&e),
+                    fees.into_val(&e),
+                    data.into_val(&e),
+                ],
+            ),
+        );
+
+        // everything returned above the borrowed amount is considered as a fee
+        let mut fees_paid = Vec::new(&e);
+        let mut admin_fees = Vec::new(&e);
+        for i in 0..n_coins {
+            let token_client = SorobanTokenClient::new(&e, &tokens.get(i).unwrap());
+            let balance_after = token_client.balance(&e.current_contract_address()) as u128;
+            let balance_before = balances_before.get(i).unwrap();
+            if balance_after < balance_before + fees.get(i).unwrap() {
+                panic_with_error!(&e, LiquidityPoolError::FlashLoanNotRepaid);
+            }
+
+            // fee goes to the LPs, except the admin share of it
+            let fee_paid = balance_after - balance_before;
+            let admin_fee = Self::_admin_fee_share(&e, fee_paid);
+            reserves.set(i, reserves.get(i).unwrap() + fee_paid - admin_fee);
+            fees_paid.push_back(fee_paid);
+            admin_fees.push_back(admin_fee);
+        }
+        put_reserves(&e, &reserves);
+        Self::_accrue_admin_fees(&e, &admin_fees);
+        Self::_upkeep_oracle(&e, &reserves);
+
+        // update plane data for every pool update
+        update_plane(&e);
+
+        Events::new(&e).flash_loan(initiator, receiver, amounts, fees_paid.clone());
+
+        fees_paid
+    }
+}
+
 // The `UpgradeableContract` trait provides the interface for upgrading the contract.
 #[contractimpl]
 impl UpgradeableContract for LiquidityPool {
@@ -1648,7 +2250,7 @@ impl UpgradeableContract for LiquidityPool {
     //
     // # Arguments
     //
-    // * `admin` - The address of the admin.
+    // * `admin` - The address of the admin or the router.
     // * `new_wasm_hash` - The new wasm hash to commit.
     // * `new_token_wasm_hash` - The new token wasm hash to commit.
     fn commit_upgrade(
@@ -1658,8 +2260,18 @@ impl UpgradeableContract for LiquidityPool {
         token_new_wasm_hash: BytesN<32>,
     ) {
         admin.require_auth();
-        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        commit_upgrade(&e, &new_wasm_hash);
+        let router = get_router(&e);
+        if admin == router {
+            // pool is not able to query the router allowlist here: the router is the caller
+            // and contract re-entry is not allowed. Router checks both hashes against its allowlist
+            // right before the call and once again before applying the upgrade
+            commit_checked_upgrade(&e, &new_wasm_hash);
+        } else {
+            AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+            // pools share the audited wasm hashes allowlist of the router
+            commit_upgrade_allowed_by(&e, &router, &WasmKind::StableSwapPool, &new_wasm_hash);
+            require_wasm_allowed_by(&e, &router, &WasmKind::Token, &token_new_wasm_hash);
+        }
         // handle token upgrade manually together with pool upgrade
         set_token_future_wasm(&e, &token_new_wasm_hash);
 
@@ -1677,7 +2289,7 @@ impl UpgradeableContract for LiquidityPool {
     fn apply_upgrade(e: Env, admin: Address) -> (BytesN<32>, BytesN<32>) {
         admin.require_auth();
         AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
-        let new_wasm_hash = apply_upgrade(&e);
+        let new_wasm_hash = apply_upgrade(&e, &admin);
         let token_new_wasm_hash = get_token_future_wasm(&e);
         token_share::Client::new(&e, &get_token_share(&e))
             .upgrade(&e.current_contract_address(), &token_new_wasm_hash);
@@ -1705,8 +2317,55 @@ impl UpgradeableContract for LiquidityPool {
         UpgradeEvents::new(&e).revert_upgrade();
     }
 
+    // Rolls back the contract to the previously running wasm.
+    // The rollback is applied immediately as it's intended to fix the faulty release.
+    // The previous wasm is known only for the upgrades applied through `apply_upgrade`.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    //
+    // # Returns
+    //
+    // The wasm hash the contract has been rolled back to.
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32> {
+        emergency_admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
+        let wasm_hash = rollback_upgrade(&e, &emergency_admin);
+        UpgradeEvents::new(&e).rollback_upgrade(wasm_hash.clone());
+        wasm_hash
+    }
+
+    // Returns the latest applied upgrades and rollbacks.
+    //
+    // # Returns
+    //
+    // The list of records with wasm hash, timestamp and the admin applied it.
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord> {
+        get_upgrade_history(&e)
+    }
+
+    // Migrates the storage to the current contract version.
+    // Should be called once after every upgrade, state-changing methods are not available until then.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    fn migrate(e: Env, admin: Address) {
+        admin.require_auth();
+        AccessControl::new(&e).assert_address_has_role(&admin, &Role::Admin);
+        let from_version = migrate(&e, Self::version());
+        UpgradeEvents::new(&e).migrate(from_version, Self::version());
+    }
+
+    // Returns the version of the storage schema.
+    fn get_storage_version(e: Env) -> u32 {
+        get_storage_version(&e)
+    }
+
     // Sets the emergency mode.
     // When the emergency mode is set to true, the contract will allow instant upgrades without the delay.
+    // Emergency mode enabled this way unlocks all emergency powers and expires after 7 days.
     // This is useful in case of critical issues that need to be fixed immediately.
     // When the emergency mode is set to false, the contract will require the standard upgrade delay.
     // The emergency mode can only be set by the emergency admin.
@@ -1716,10 +2375,7 @@ impl UpgradeableContract for LiquidityPool {
     // * `emergency_admin` - The address of the emergency admin.
     // * `value` - The value to set the emergency mode to.
     fn set_emergency_mode(e: Env, emergency_admin: Address, value: bool) {
-        emergency_admin.require_auth();
-        AccessControl::new(&e).assert_address_has_role(&emergency_admin, &Role::EmergencyAdmin);
-        set_emergency_mode(&e, &value);
-        AccessControlEvents::new(&e).set_emergency_mode(value);
+        toggle_emergency_mode(&e, &emergency_admin, value);
     }
 
     // Returns the emergency mode flag value.
@@ -1827,7 +2483,9 @@ impl RewardsTrait for LiquidityPool {
         match get_tokens(&e).first_index_of(reward_token) {
             Some(idx) => {
                 // since reward token is in the reserves, we need to keep also the reserves value
+                // and admin fees accrued but not yet withdrawn
                 reward_balance_to_keep += get_reserves(&e).get(idx).unwrap();
+                reward_balance_to_keep += get_admin_balances(&e).get(idx).unwrap();
             }
             None => {}
         };
@@ -2041,6 +2699,7 @@ impl RewardsTrait for LiquidityPool {
     //
     // The amount of tokens rewarded to the user as a u128.
     fn claim(e: Env, user: Address) -> u128 {
+        require_migrated(&e, Self::version());
         if get_is_killed_claim(&e) {
             panic_with_error!(e, LiquidityPoolError::PoolClaimKilled);
         }
@@ -2056,6 +2715,7 @@ impl RewardsTrait for LiquidityPool {
         let tokens = Self::get_tokens(e.clone());
         let reward_token = rewards_storage.get_reward_token();
         let reserves = Self::get_reserves(e.clone());
+        let admin_balances = get_admin_balances(&e);
 
         for i in 0..reserves.len() {
             let token = tokens.get(i).unwrap();
@@ -2065,7 +2725,7 @@ impl RewardsTrait for LiquidityPool {
 
             let balance = SorobanTokenClient::new(&e, &tokens.get(i).unwrap())
                 .balance(&e.current_contract_address()) as u128;
-            if reserves.get(i).unwrap() > balance {
+            if reserves.get(i).unwrap() + admin_balances.get(i).unwrap() > balance {
                 panic_with_error!(&e, LiquidityPoolValidationError::InsufficientBalance);
             }
         }
@@ -2202,3 +2862,241 @@ impl TransferableContract for LiquidityPool {
         }
     }
 }
+
+// The `ConfigurableDelaysContract` trait provides the interface for configuring admin actions delays.
+#[contractimpl]
+impl ConfigurableDelaysContract for LiquidityPool {
+    // Commits new value for the delay: `AdminActions`, `Upgrade` or `WasmAllowlist`.
+    // The value is applied after the current delay passes.
+    fn commit_delay(e: Env, admin: Address, delay_name: Symbol, value: u64) {
+        commit_delay(&e, &admin, delay_name, value)
+    }
+
+    // Applies the committed delay value.
+    fn apply_delay(e: Env, admin: Address, delay_name: Symbol) {
+        apply_delay(&e, &admin, delay_name)
+    }
+
+    // Reverts the committed delay value.
+    fn revert_delay(e: Env, admin: Address, delay_name: Symbol) {
+        revert_delay(&e, &admin, delay_name)
+    }
+
+    // Returns the current delay value in seconds.
+    fn get_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_delay(&e, delay_name)
+    }
+
+    // Returns the committed delay value in seconds. Defaults to the current one if not committed.
+    fn get_future_delay(e: Env, delay_name: Symbol) -> u64 {
+        get_future_delay(&e, delay_name)
+    }
+}
+
+// The `AdminSignersContract` trait provides M-of-N authorization for the admin role.
+#[contractimpl]
+impl AdminSignersContract for LiquidityPool {
+    // Commits the signers set backing the admin role. Empty set switches back to the single admin.
+    fn commit_admin_signers(e: Env, admin: Address, signers: Vec<Address>, threshold: u32) {
+        commit_admin_signers(&e, &admin, &signers, threshold)
+    }
+
+    // Applies the committed admin signers set once the admin transfer delay passes.
+    fn apply_admin_signers(e: Env, admin: Address) {
+        apply_admin_signers(&e, &admin)
+    }
+
+    // Reverts the committed admin signers set.
+    fn revert_admin_signers(e: Env, admin: Address) {
+        revert_admin_signers(&e, &admin)
+    }
+
+    // Returns the admin signers set and threshold. Empty set if not configured.
+    fn get_admin_signers(e: Env) -> (Vec<Address>, u32) {
+        get_admin_signers(&e)
+    }
+
+    // Returns the committed admin signers set and threshold if the change is pending.
+    fn get_future_admin_signers(e: Env) -> Option<(Vec<Address>, u32)> {
+        get_future_admin_signers(&e)
+    }
+
+    // Nominates the admin signers authorizing the admin actions of the signer together with it.
+    fn set_admin_cosigners(e: Env, signer: Address, cosigners: Vec<Address>) {
+        set_admin_cosigners(&e, &signer, &cosigners)
+    }
+
+    // Returns the admin signers nominated by the signer.
+    fn get_admin_cosigners(e: Env, signer: Address) -> Vec<Address> {
+        get_admin_cosigners(&e, &signer)
+    }
+}
+
+// The `RoleTimelockContract` trait provides delayed changes for the rest of the roles.
+#[contractimpl]
+impl RoleTimelockContract for LiquidityPool {
+    // Commits delay between commit and apply of the role transfer or members change.
+    // The value is applied after the current delay of the role passes.
+    // Once delay is set, role can be changed only through the commit-apply process.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    // * `delay` - Delay in seconds. Cannot be less than the minimum delay for the roles
+    //   transferred with mandatory delay like `Admin` and `EmergencyAdmin`.
+    fn commit_role_transfer_delay(e: Env, admin: Address, role_name: Symbol, delay: u64) {
+        commit_role_transfer_delay(&e, &admin, role_name, delay);
+    }
+
+    // Applies the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn apply_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        apply_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Reverts the committed role transfer delay.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the role.
+    fn revert_role_transfer_delay(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_transfer_delay(&e, &admin, role_name);
+    }
+
+    // Returns delay between commit and apply of the role transfer or members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_role_transfer_delay(&e, role_name)
+    }
+
+    // Returns the committed role transfer delay.
+    // The value will be defaulted to the current one if the change is not committed.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the role.
+    fn get_future_role_transfer_delay(e: Env, role_name: Symbol) -> u64 {
+        get_future_role_transfer_delay(&e, role_name)
+    }
+
+    // Commits address addition to the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be added.
+    fn commit_add_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, true);
+    }
+
+    // Commits address removal from the multiple addresses role.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    // * `address` - The address to be removed.
+    fn commit_remove_role_member(e: Env, admin: Address, role_name: Symbol, address: Address) {
+        commit_role_member_change(&e, &admin, role_name, &address, false);
+    }
+
+    // Applies the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The new list of the role members.
+    fn apply_role_members_change(e: Env, admin: Address, role_name: Symbol) -> Vec<Address> {
+        apply_role_members_change(&e, &admin, role_name)
+    }
+
+    // Reverts the committed members change.
+    //
+    // # Arguments
+    //
+    // * `admin` - The address of the admin.
+    // * `role_name` - The name of the multiple addresses role.
+    fn revert_role_members_change(e: Env, admin: Address, role_name: Symbol) {
+        revert_role_members_change(&e, &admin, role_name);
+    }
+
+    // Returns the committed members change.
+    //
+    // # Arguments
+    //
+    // * `role_name` - The name of the multiple addresses role.
+    //
+    // # Returns
+    //
+    // The (address, is_added) pair if change is committed.
+    fn get_pending_role_members_change(e: Env, role_name: Symbol) -> Option<(Address, bool)> {
+        get_pending_role_members_change(&e, role_name)
+    }
+}
+
+// The `AdminStateContract` trait provides the view of the roles and pending admin actions.
+#[contractimpl]
+impl AdminStateContract for LiquidityPool {
+    // Returns the roles state along with the delays, pending admin actions and emergency mode.
+    //
+    // # Returns
+    //
+    // The list of roles with current holders, pending transfers, signers and their deadlines,
+    // the configurable delays, the pending wasm hash with the upgrade deadline,
+    // the pending allowlist changes and contract actions and the emergency mode state.
+    fn get_admin_state(e: Env) -> AdminState {
+        get_admin_state(
+            &e,
+            &[
+                ("Fee", get_admin_actions_deadline(&e)),
+                ("AdminFee", get_admin_fee_deadline(&e)),
+                ("MaHalfLife", get_ma_half_life_deadline(&e)),
+            ],
+        )
+    }
+}
+
+// The `EmergencyModeContract` trait provides the scoped emergency mode with automatic expiration.
+#[contractimpl]
+impl EmergencyModeContract for LiquidityPool {
+    // Enables the emergency mode for the limited time.
+    // Scope defines what is unlocked, currently the instant upgrade only.
+    // The emergency mode is disabled automatically once expired.
+    //
+    // # Arguments
+    //
+    // * `emergency_admin` - The address of the emergency admin.
+    // * `scope` - The powers unlocked by the emergency mode.
+    // * `duration` - The emergency mode duration in seconds.
+    fn enable_emergency_mode(
+        e: Env,
+        emergency_admin: Address,
+        scope: EmergencyScope,
+        duration: u64,
+    ) {
+        enable_scoped_emergency_mode(&e, &emergency_admin, &scope, duration);
+    }
+
+    // Returns the emergency mode state.
+    //
+    // # Returns
+    //
+    // The emergency mode flag, its scope, expiration timestamp and the remaining time in seconds.
+    fn get_emergency_mode_state(e: Env) -> EmergencyModeState {
+        get_emergency_mode_state(&e)
+    }
+}
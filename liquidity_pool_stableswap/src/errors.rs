@@ -14,6 +14,7 @@ pub enum LiquidityPoolError {
     FutureShareIdNotSet = 208,
     // solution did not converge
     MaxIterationsReached = 209,
+    FlashLoanNotRepaid = 212,
 
     // pool specific validation errors
     RampTooEarly = 2902,
@@ -23,4 +24,5 @@ pub enum LiquidityPoolError {
     AnotherActionActive = 2906,
     NoActionActive = 2907,
     ActionNotReadyYet = 2908,
+    MaHalfLifeOutOfBounds = 2909,
 }
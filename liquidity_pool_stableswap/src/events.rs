@@ -1,4 +1,4 @@
-use soroban_sdk::{Env, Symbol};
+use soroban_sdk::{Address, Env, Symbol, Vec};
 
 #[derive(Clone)]
 pub struct Events(Env);
@@ -26,6 +26,41 @@ impl Events {
             .publish((Symbol::new(self.env(), "apply_new_fee"),), (new_fee,))
     }
 
+    pub fn commit_new_admin_fee(&self, new_admin_fee: u32) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "commit_new_admin_fee"),),
+            (new_admin_fee,),
+        )
+    }
+
+    pub fn apply_new_admin_fee(&self, new_admin_fee: u32) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "apply_new_admin_fee"),),
+            (new_admin_fee,),
+        )
+    }
+
+    pub fn commit_new_ma_half_life(&self, new_half_life: u64) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "commit_new_ma_half_life"),),
+            (new_half_life,),
+        )
+    }
+
+    pub fn apply_new_ma_half_life(&self, new_half_life: u64) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "apply_new_ma_half_life"),),
+            (new_half_life,),
+        )
+    }
+
+    pub fn withdraw_admin_fees(&self, destination: Address, amounts: Vec<u128>) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "withdraw_admin_fees"), destination),
+            (amounts,),
+        )
+    }
+
     pub fn revert_new_parameters(&self) {
         self.env()
             .events()
@@ -44,4 +79,17 @@ impl Events {
             .events()
             .publish((Symbol::new(self.env(), "stop_ramp_a"),), (current_a,))
     }
+
+    pub fn flash_loan(
+        &self,
+        initiator: Address,
+        receiver: Address,
+        amounts: Vec<u128>,
+        fees: Vec<u128>,
+    ) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "flash_loan"), initiator, receiver),
+            (amounts, fees),
+        )
+    }
 }
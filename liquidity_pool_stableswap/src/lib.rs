@@ -10,6 +10,7 @@ mod token;
 pub mod errors;
 mod events;
 mod normalize;
+mod oracle;
 mod plane;
 mod plane_interface;
 mod rewards;
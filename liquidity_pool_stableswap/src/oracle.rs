@@ -0,0 +1,56 @@
+use crate::pool_constants::PRICE_PRECISION;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::Env;
+
+// 2^(-1/2^k) for k = 1..16 scaled by PRICE_PRECISION
+const HALF_LIFE_ROOTS: [u128; 16] = [
+    707106781186547524,
+    840896415253714543,
+    917004043204671232,
+    957603280698573647,
+    978572062087700135,
+    989228013193975484,
+    994599423483633176,
+    997296056085470126,
+    998647112890970174,
+    999323327502650752,
+    999661606496243684,
+    999830788931929063,
+    999915390886613498,
+    999957694548431133,
+    999978847050491930,
+    999989423469314464,
+];
+
+// Weight of the old moving average after `elapsed` seconds: 0.5^(elapsed / half_life) scaled by PRICE_PRECISION
+pub fn get_decay(e: &Env, elapsed: u64, half_life: u64) -> u128 {
+    let halvings = elapsed / half_life;
+    if halvings >= 128 {
+        return 0;
+    }
+    let mut decay = PRICE_PRECISION >> halvings;
+
+    // fractional part of the exponent is applied bit by bit
+    let mut remainder = elapsed % half_life;
+    for root in HALF_LIFE_ROOTS.iter() {
+        remainder *= 2;
+        if remainder >= half_life {
+            remainder -= half_life;
+            decay = decay.fixed_mul_floor(e, root, &PRICE_PRECISION);
+        }
+    }
+    decay
+}
+
+// Moving average after the spot price `last_price` has been in effect for `elapsed` seconds
+pub fn get_moving_average(
+    e: &Env,
+    ma_price: u128,
+    last_price: u128,
+    elapsed: u64,
+    half_life: u64,
+) -> u128 {
+    let decay = get_decay(e, elapsed, half_life);
+    last_price.fixed_mul_floor(e, &(PRICE_PRECISION - decay), &PRICE_PRECISION)
+        + ma_price.fixed_mul_floor(e, &decay, &PRICE_PRECISION)
+}
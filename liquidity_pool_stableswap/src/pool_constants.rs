@@ -2,5 +2,7 @@ pub const FEE_DENOMINATOR: u32 = 10000; // 0.01% = 0.0001 = 1 / 10000
 pub const MAX_A: u128 = 1_000_000; // absolute maximum value for A
 pub const MAX_A_CHANGE: u128 = 10; // maximum multiplier allowed for a change in 'A'
 
-pub const MIN_RAMP_TIME: u64 = 86400; // minimum time for ramping. ensures that changes occur
-                                      //    over a minimum duration to prevent abrupt shifts.
+pub const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000; // oracle prices are scaled by 1e18
+pub const DEFAULT_MA_HALF_LIFE: u64 = 600; // moving average forgets half of the old price in 10 minutes
+pub const MIN_MA_HALF_LIFE: u64 = 60;
+pub const MAX_MA_HALF_LIFE: u64 = 7 * 86400;
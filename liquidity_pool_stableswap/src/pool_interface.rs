@@ -1,4 +1,5 @@
-use soroban_sdk::{Address, BytesN, Env, Map, Symbol, Val, Vec};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Map, Symbol, Val, Vec};
+use upgrade::history::UpgradeRecord;
 
 pub trait ManagedLiquidityPool {
     // Initialize pool completely to reduce calculations cost
@@ -78,6 +79,9 @@ pub trait LiquidityPoolInterfaceTrait {
     // Estimate amount of coins to retrieve using swap function
     fn estimate_swap(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> u128;
 
+    // Estimate amount of coins to retrieve using swap function and the fee charged in coin out_idx
+    fn estimate_swap_with_fee(e: Env, in_idx: u32, out_idx: u32, in_amount: u128) -> (u128, u128);
+
     // Perform an exchange between two coins with strict amount to receive.
     // in_idx: Index value for the coin to send
     // out_idx: Index value of the coin to receive
@@ -95,6 +99,14 @@ pub trait LiquidityPoolInterfaceTrait {
     // Estimate amount of coins to retrieve using swap_strict_receive function
     fn estimate_swap_strict_receive(e: Env, in_idx: u32, out_idx: u32, out_amount: u128) -> u128;
 
+    // Estimate amount of coins to send using swap_strict_receive function and the fee charged in coin out_idx
+    fn estimate_swap_strict_receive_with_fee(
+        e: Env,
+        in_idx: u32,
+        out_idx: u32,
+        out_amount: u128,
+    ) -> (u128, u128);
+
     // Withdraw coins from the pool.
     // share_amount: Quantity of LP tokens to burn in the withdrawal
     // min_amounts: Minimum amounts of underlying coins to receive
@@ -119,6 +131,16 @@ pub trait UpgradeableContract {
     fn apply_upgrade(e: Env, admin: Address) -> (BytesN<32>, BytesN<32>);
     fn revert_upgrade(e: Env, admin: Address);
 
+    // Rollback to the previously running wasm without delay
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32>;
+
+    // Get latest applied upgrades
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord>;
+
+    // Migrate storage to the current contract version after the upgrade
+    fn migrate(e: Env, admin: Address);
+    fn get_storage_version(e: Env) -> u32;
+
     // Emergency mode - bypass upgrade deadline
     fn set_emergency_mode(e: Env, admin: Address, value: bool);
     fn get_emergency_mode(e: Env) -> bool;
@@ -218,6 +240,30 @@ pub trait AdminInterfaceTrait {
     // Revert committed parameters to current values
     fn revert_new_parameters(e: Env, admin: Address);
 
+    // Set new admin fee to be applied in future. Share of the fee which goes to the admin
+    fn commit_new_admin_fee(e: Env, admin: Address, new_admin_fee: u32);
+
+    // Apply committed admin fee
+    fn apply_new_admin_fee(e: Env, admin: Address);
+
+    // Get admin fee. 1 = 0.01% of the fee
+    fn get_admin_fee(e: Env) -> u32;
+
+    // Get admin fee to be applied in future
+    fn get_future_admin_fee(e: Env) -> u32;
+
+    // Set new half-life in seconds of the moving average price oracle to be applied in future
+    fn commit_new_ma_half_life(e: Env, admin: Address, new_half_life: u64);
+
+    // Apply committed moving average half-life
+    fn apply_new_ma_half_life(e: Env, admin: Address);
+
+    // Get admin fees accrued for each token
+    fn get_admin_balances(e: Env) -> Vec<u128>;
+
+    // Send accrued admin fees to the destination
+    fn withdraw_admin_fees(e: Env, admin: Address, destination: Address) -> Vec<u128>;
+
     // Stop pool instantly
     fn kill_deposit(e: Env, admin: Address);
     fn kill_swap(e: Env, admin: Address);
@@ -234,6 +280,36 @@ pub trait AdminInterfaceTrait {
     fn get_is_killed_claim(e: Env) -> bool;
 }
 
+pub trait PriceOracleTrait {
+    // Exponential moving average price of coin i in units of coin 0, scaled by 1e18
+    fn price_oracle(e: Env, i: u32) -> u128;
+
+    // Latest spot price of coin i in units of coin 0 seen by the oracle, scaled by 1e18
+    fn last_price(e: Env, i: u32) -> u128;
+
+    // Half-life of the moving average in seconds
+    fn get_ma_half_life(e: Env) -> u64;
+
+    // Half-life of the moving average to be applied in future
+    fn get_future_ma_half_life(e: Env) -> u64;
+}
+
+pub trait FlashLoanTrait {
+    // Lend pool reserves within a single transaction.
+    // Receiver contract is called back with
+    // `on_flash_loan(initiator, pool, tokens, amounts, fees, data)` and has to transfer
+    // borrowed amounts plus fees back to the pool before the callback returns.
+    // Initiator authorizes the loan, so the receiver is able to reject loans started by others.
+    // Returns the fees paid for each token.
+    fn flash_loan(
+        e: Env,
+        initiator: Address,
+        receiver: Address,
+        amounts: Vec<u128>,
+        data: Bytes,
+    ) -> Vec<u128>;
+}
+
 pub trait LiquidityPoolTrait:
     LiquidityPoolInterfaceTrait
     + UpgradeableContract
@@ -2,6 +2,7 @@ use paste::paste;
 use soroban_sdk::{contracttype, panic_with_error, Address, BytesN, Env, Vec};
 
 use crate::normalize;
+use crate::pool_constants::DEFAULT_MA_HALF_LIFE;
 use rewards::utils::bump::bump_instance;
 use utils::storage_errors::StorageError;
 use utils::{
@@ -23,7 +24,9 @@ enum DataKey {
     FutureFee,
     AdminFee,
     FutureAdminFee,
+    AdminBalances, // admin fees accrued per token, excluded from reserves
     AdminActionsDeadline,
+    AdminFeeDeadline, // admin fee is committed independently of the trading fee
     IsKilledSwap,
     IsKilledDeposit,
     IsKilledClaim,
@@ -34,6 +37,14 @@ enum DataKey {
     // Tokens precision
     Precision, // target precision for internal calculations. It's the maximum precision of all tokens.
     PrecisionMul, // Scales raw token amounts to match `Precision`, accounting for decimal differences.
+
+    // Moving average price oracle. Prices of every coin in units of coin 0
+    LastPrices,
+    MaPrices,
+    MaLastTime,
+    MaHalfLife,
+    FutureMaHalfLife,
+    MaHalfLifeDeadline,
 }
 
 generate_instance_storage_getter_and_setter_with_default!(
@@ -55,6 +66,38 @@ generate_instance_storage_getter_and_setter_with_default!(
     false
 );
 
+generate_instance_storage_getter_and_setter_with_default!(
+    admin_fee_deadline,
+    DataKey::AdminFeeDeadline,
+    u64,
+    0
+);
+
+generate_instance_storage_getter_and_setter_with_default!(
+    ma_last_time,
+    DataKey::MaLastTime,
+    u64,
+    0
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    ma_half_life,
+    DataKey::MaHalfLife,
+    u64,
+    DEFAULT_MA_HALF_LIFE
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    future_ma_half_life,
+    DataKey::FutureMaHalfLife,
+    u64,
+    0
+);
+generate_instance_storage_getter_and_setter_with_default!(
+    ma_half_life_deadline,
+    DataKey::MaHalfLifeDeadline,
+    u64,
+    0
+);
+
 pub fn get_tokens(e: &Env) -> Vec<Address> {
     bump_instance(e);
     match e.storage().instance().get(&DataKey::Tokens) {
@@ -182,6 +225,51 @@ pub fn put_future_fee(e: &Env, value: &u32) {
     e.storage().instance().set(&DataKey::FutureFee, value);
 }
 
+// admin_fee
+pub fn get_admin_fee(e: &Env) -> u32 {
+    bump_instance(e);
+    e.storage().instance().get(&DataKey::AdminFee).unwrap_or(0)
+}
+
+pub fn put_admin_fee(e: &Env, value: &u32) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::AdminFee, value);
+}
+
+// future_admin_fee
+pub fn get_future_admin_fee(e: &Env) -> u32 {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::FutureAdminFee)
+        .unwrap_or(0)
+}
+
+pub fn put_future_admin_fee(e: &Env, value: &u32) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::FutureAdminFee, value);
+}
+
+// admin_balances
+pub fn get_admin_balances(e: &Env) -> Vec<u128> {
+    bump_instance(e);
+    match e.storage().instance().get(&DataKey::AdminBalances) {
+        Some(v) => v,
+        None => {
+            let mut balances = Vec::new(e);
+            for _ in 0..get_tokens(e).len() {
+                balances.push_back(0);
+            }
+            balances
+        }
+    }
+}
+
+pub fn put_admin_balances(e: &Env, amounts: &Vec<u128>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::AdminBalances, amounts);
+}
+
 // admin_actions_deadline
 pub fn get_admin_actions_deadline(e: &Env) -> u64 {
     bump_instance(e);
@@ -281,3 +369,30 @@ pub(crate) fn get_token_future_wasm(e: &Env) -> BytesN<32> {
         None => panic_with_error!(e, StorageError::ValueNotInitialized),
     }
 }
+
+// oracle prices. Empty until the first pool update
+pub fn get_last_prices(e: &Env) -> Vec<u128> {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::LastPrices)
+        .unwrap_or(Vec::new(e))
+}
+
+pub fn put_last_prices(e: &Env, prices: &Vec<u128>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::LastPrices, prices);
+}
+
+pub fn get_ma_prices(e: &Env) -> Vec<u128> {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::MaPrices)
+        .unwrap_or(Vec::new(e))
+}
+
+pub fn put_ma_prices(e: &Env, prices: &Vec<u128>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::MaPrices, prices);
+}
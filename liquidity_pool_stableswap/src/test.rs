@@ -1,19 +1,18 @@
 #![cfg(test)]
 extern crate std;
 
-use crate::pool_constants::MIN_RAMP_TIME;
 use core::cmp::min;
 use rewards::utils::test_utils::assert_approx_eq_abs;
-use soroban_sdk::testutils::{Address as _, Events};
-use soroban_sdk::{symbol_short, vec, Address, Env, Error, IntoVal, Symbol, Val, Vec};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events};
+use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, Error, IntoVal, Symbol, Val, Vec};
 use token_share::Client as ShareTokenClient;
 
 use crate::testutils::{
-    create_liqpool_contract, create_plane_contract, create_reward_boost_feed_contract,
-    create_token_contract, get_token_admin_client, install_token_wasm,
-    install_token_wasm_with_decimal, Setup, TestConfig,
+    create_flash_loan_receiver, create_liqpool_contract, create_plane_contract,
+    create_reward_boost_feed_contract, create_token_contract, get_token_admin_client,
+    install_token_wasm, install_token_wasm_with_decimal, Setup, TestConfig,
 };
-use access_control::constants::ADMIN_ACTIONS_DELAY;
+use access_control::constants::{ADMIN_ACTIONS_DELAY, MIN_DELAY};
 use soroban_sdk::token::{
     StellarAssetClient as SorobanTokenAdminClient, TokenClient as SorobanTokenClient,
 };
@@ -3006,12 +3005,14 @@ fn test_ramp_a_too_early() {
         &plane.address,
     );
 
-    jump(&e, MIN_RAMP_TIME - 1);
+    jump(&e, ADMIN_ACTIONS_DELAY - 1);
     assert_eq!(liqpool.a(), 10);
     liqpool.ramp_a(
         &pool_admin_original,
         &30,
-        &e.ledger().timestamp().saturating_add(MIN_RAMP_TIME + 1),
+        &e.ledger()
+            .timestamp()
+            .saturating_add(ADMIN_ACTIONS_DELAY + 1),
     );
 }
 
@@ -3052,12 +3053,14 @@ fn test_ramp_a_too_short() {
         &plane.address,
     );
 
-    jump(&e, MIN_RAMP_TIME + 1);
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
     assert_eq!(liqpool.a(), 10);
     liqpool.ramp_a(
         &pool_admin_original,
         &30,
-        &e.ledger().timestamp().saturating_add(MIN_RAMP_TIME - 1),
+        &e.ledger()
+            .timestamp()
+            .saturating_add(ADMIN_ACTIONS_DELAY - 1),
     );
 }
 
@@ -3098,12 +3101,14 @@ fn test_ramp_a_too_fast() {
         &plane.address,
     );
 
-    jump(&e, MIN_RAMP_TIME + 1);
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
     assert_eq!(liqpool.a(), 10);
     liqpool.ramp_a(
         &pool_admin_original,
         &101,
-        &e.ledger().timestamp().saturating_add(MIN_RAMP_TIME + 1),
+        &e.ledger()
+            .timestamp()
+            .saturating_add(ADMIN_ACTIONS_DELAY + 1),
     );
 }
 
@@ -3143,16 +3148,18 @@ fn test_ramp_a() {
         &plane.address,
     );
 
-    jump(&e, MIN_RAMP_TIME + 1);
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
     assert_eq!(liqpool.a(), 10);
     liqpool.ramp_a(
         &pool_admin_original,
         &99,
-        &e.ledger().timestamp().saturating_add(MIN_RAMP_TIME + 1),
+        &e.ledger()
+            .timestamp()
+            .saturating_add(ADMIN_ACTIONS_DELAY + 1),
     );
-    jump(&e, MIN_RAMP_TIME / 2 + 1);
+    jump(&e, ADMIN_ACTIONS_DELAY / 2 + 1);
     assert_eq!(liqpool.a(), 54);
-    jump(&e, MIN_RAMP_TIME);
+    jump(&e, ADMIN_ACTIONS_DELAY);
     assert_eq!(liqpool.a(), 99);
 }
 
@@ -4640,11 +4647,15 @@ fn test_ramp_a_events() {
     let setup = Setup::default();
     let pool = setup.liq_pool;
 
-    jump(&setup.env, MIN_RAMP_TIME);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY);
     pool.ramp_a(
         &setup.admin,
         &185,
-        &setup.env.ledger().timestamp().saturating_add(MIN_RAMP_TIME),
+        &setup
+            .env
+            .ledger()
+            .timestamp()
+            .saturating_add(ADMIN_ACTIONS_DELAY),
     );
     assert_eq!(
         vec![&setup.env, setup.env.events().all().last().unwrap()],
@@ -4655,14 +4666,18 @@ fn test_ramp_a_events() {
                 (Symbol::new(&setup.env, "ramp_a"),).into_val(&setup.env),
                 (
                     185_u128,
-                    setup.env.ledger().timestamp().saturating_add(MIN_RAMP_TIME)
+                    setup
+                        .env
+                        .ledger()
+                        .timestamp()
+                        .saturating_add(ADMIN_ACTIONS_DELAY)
                 )
                     .into_val(&setup.env),
             ),
         ]
     );
 
-    jump(&setup.env, MIN_RAMP_TIME / 2);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY / 2);
 
     pool.stop_ramp_a(&setup.admin);
     assert_eq!(
@@ -4926,3 +4941,402 @@ fn test_claim_event() {
         ]
     );
 }
+
+#[test]
+fn test_admin_fee() {
+    let setup = Setup::default();
+    let liq_pool = setup.liq_pool;
+    let user = Address::generate(&setup.env);
+    let treasury = Address::generate(&setup.env);
+    let token_1_admin_client = get_token_admin_client(&setup.env, &setup.token1.address);
+    let token_2_admin_client = get_token_admin_client(&setup.env, &setup.token2.address);
+    token_1_admin_client.mint(&user, &1_000_000_0000000);
+    token_2_admin_client.mint(&user, &1_000_000_0000000);
+
+    liq_pool.commit_new_admin_fee(&setup.admin, &5000);
+    assert_eq!(
+        liq_pool
+            .try_apply_new_admin_fee(&setup.admin)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    liq_pool.apply_new_admin_fee(&setup.admin);
+    assert_eq!(liq_pool.get_admin_fee(), 5000);
+
+    liq_pool.deposit(
+        &user,
+        &Vec::from_array(&setup.env, [100_0000000, 100_0000000]),
+        &0,
+    );
+    assert_eq!(liq_pool.get_admin_balances(), Vec::from_array(&setup.env, [0, 0]));
+
+    liq_pool.swap(&user, &0, &1, &10_0000000, &0);
+    liq_pool.swap_strict_receive(&user, &1, &0, &1_0000000, &10_0000000);
+    let admin_balances = liq_pool.get_admin_balances();
+    assert!(admin_balances.get(0).unwrap() > 0);
+    assert!(admin_balances.get(1).unwrap() > 0);
+
+    // admin balances are excluded from reserves
+    let reserves = liq_pool.get_reserves();
+    for (i, token) in [setup.token1.clone(), setup.token2.clone()]
+        .iter()
+        .enumerate()
+    {
+        assert_eq!(
+            token.balance(&liq_pool.address) as u128,
+            reserves.get(i as u32).unwrap() + admin_balances.get(i as u32).unwrap()
+        );
+    }
+
+    assert_eq!(
+        liq_pool.withdraw_admin_fees(&setup.admin, &treasury),
+        admin_balances
+    );
+    assert_eq!(
+        setup.token1.balance(&treasury) as u128,
+        admin_balances.get(0).unwrap()
+    );
+    assert_eq!(
+        setup.token2.balance(&treasury) as u128,
+        admin_balances.get(1).unwrap()
+    );
+    assert_eq!(liq_pool.get_admin_balances(), Vec::from_array(&setup.env, [0, 0]));
+    assert_eq!(liq_pool.get_reserves(), reserves);
+}
+
+#[test]
+fn test_admin_fee_and_fee_committed_independently() {
+    let setup = Setup::default();
+    let liq_pool = setup.liq_pool;
+
+    // applying the fee doesn't consume admin fee commit and vice versa
+    liq_pool.commit_new_admin_fee(&setup.admin, &5000);
+    assert_eq!(
+        liq_pool.try_apply_new_fee(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2907))
+    );
+    liq_pool.commit_new_fee(&setup.admin, &8);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    liq_pool.apply_new_fee(&setup.admin);
+    assert_eq!(liq_pool.get_fee_fraction(), 8);
+    assert_eq!(liq_pool.get_admin_fee(), 0);
+
+    liq_pool.apply_new_admin_fee(&setup.admin);
+    assert_eq!(liq_pool.get_admin_fee(), 5000);
+    assert_eq!(liq_pool.get_fee_fraction(), 8);
+
+    // revert drops every pending parameter
+    liq_pool.commit_new_fee(&setup.admin, &10);
+    liq_pool.commit_new_admin_fee(&setup.admin, &1000);
+    liq_pool.revert_new_parameters(&setup.admin);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    assert_eq!(
+        liq_pool.try_apply_new_fee(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2907))
+    );
+    assert_eq!(
+        liq_pool.try_apply_new_admin_fee(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2907))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2012)")]
+fn test_admin_fee_out_of_bounds() {
+    let setup = Setup::default();
+    setup.liq_pool.commit_new_admin_fee(&setup.admin, &10001);
+}
+
+#[test]
+fn test_configurable_admin_actions_delay() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let delay_name = Symbol::new(&setup.env, "AdminActions");
+    let new_delay = 7 * 86400;
+
+    assert_eq!(pool.get_delay(&delay_name), ADMIN_ACTIONS_DELAY);
+    pool.commit_delay(&setup.admin, &delay_name, &new_delay);
+    assert_eq!(pool.get_delay(&delay_name), ADMIN_ACTIONS_DELAY);
+    assert_eq!(pool.get_future_delay(&delay_name), new_delay);
+
+    // delay change is subject to the current delay
+    jump(&setup.env, ADMIN_ACTIONS_DELAY - 1);
+    assert!(pool.try_apply_delay(&setup.admin, &delay_name).is_err());
+    jump(&setup.env, 1);
+    pool.apply_delay(&setup.admin, &delay_name);
+    assert_eq!(pool.get_delay(&delay_name), new_delay);
+
+    pool.commit_new_fee(&setup.admin, &8);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    assert_eq!(
+        pool.try_apply_new_fee(&setup.admin).unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    jump(&setup.env, new_delay - ADMIN_ACTIONS_DELAY);
+    pool.apply_new_fee(&setup.admin);
+    assert_eq!(pool.get_fee_fraction(), 8);
+}
+
+#[test]
+fn test_configurable_delay_too_short() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+
+    assert_eq!(
+        pool.try_commit_delay(
+            &setup.admin,
+            &Symbol::new(&setup.env, "AdminActions"),
+            &(MIN_DELAY - 1)
+        )
+        .unwrap_err(),
+        Ok(Error::from_contract_error(107))
+    );
+    assert_eq!(
+        pool.try_commit_delay(&setup.admin, &Symbol::new(&setup.env, "Unknown"), &MIN_DELAY)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(108))
+    );
+}
+
+#[test]
+fn test_price_oracle() {
+    let setup = Setup::default();
+    let liq_pool = setup.liq_pool;
+    let user = Address::generate(&setup.env);
+    let token_1_admin_client = get_token_admin_client(&setup.env, &setup.token1.address);
+    let token_2_admin_client = get_token_admin_client(&setup.env, &setup.token2.address);
+    token_1_admin_client.mint(&user, &1_000_000_0000000);
+    token_2_admin_client.mint(&user, &1_000_000_0000000);
+    let precision = 1_000_000_000_000_000_000_u128;
+
+    assert_eq!(liq_pool.get_ma_half_life(), 600);
+    assert_eq!(
+        liq_pool.try_last_price(&1).unwrap_err(),
+        Ok(Error::from_contract_error(2010))
+    );
+    assert_eq!(
+        liq_pool.try_price_oracle(&2).unwrap_err(),
+        Ok(Error::from_contract_error(2008))
+    );
+
+    liq_pool.deposit(
+        &user,
+        &Vec::from_array(&setup.env, [100_0000000, 100_0000000]),
+        &0,
+    );
+    assert_eq!(liq_pool.last_price(&0), precision);
+    assert_eq!(liq_pool.last_price(&1), precision);
+    assert_eq!(liq_pool.price_oracle(&1), precision);
+
+    // coin 1 becomes scarce and more expensive, but the moving average doesn't react instantly
+    liq_pool.swap(&user, &0, &1, &50_0000000, &0);
+    let last_price = liq_pool.last_price(&1);
+    assert!(last_price > precision);
+    assert_eq!(liq_pool.price_oracle(&1), precision);
+
+    // half of the way after one half-life
+    jump(&setup.env, 600);
+    assert_approx_eq_abs(liq_pool.price_oracle(&1), (precision + last_price) / 2, 1);
+    jump(&setup.env, 6000);
+    assert_approx_eq_abs(
+        liq_pool.price_oracle(&1),
+        last_price,
+        (last_price - precision) / 1000,
+    );
+
+    // half-life is changed with delay
+    assert_eq!(
+        liq_pool
+            .try_commit_new_ma_half_life(&setup.admin, &59)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2909))
+    );
+    liq_pool.commit_new_ma_half_life(&setup.admin, &3600);
+    assert_eq!(liq_pool.get_future_ma_half_life(), 3600);
+    assert_eq!(
+        liq_pool
+            .try_apply_new_ma_half_life(&setup.admin)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    liq_pool.apply_new_ma_half_life(&setup.admin);
+    assert_eq!(liq_pool.get_ma_half_life(), 3600);
+
+    // half of the half-life passed: old average weight is 2^(-1/2)
+    let ma_price = liq_pool.price_oracle(&1);
+    liq_pool.swap(&user, &1, &0, &50_0000000, &0);
+    let new_last_price = liq_pool.last_price(&1);
+    assert!(new_last_price < last_price);
+    jump(&setup.env, 1800);
+    let decay = 707106781186547524_u128;
+    assert_approx_eq_abs(
+        liq_pool.price_oracle(&1),
+        new_last_price * (precision - decay) / precision + ma_price * decay / precision,
+        1,
+    );
+}
+
+#[test]
+fn test_flash_loan() {
+    let setup = Setup::default();
+    let e = setup.env;
+    let liq_pool = setup.liq_pool;
+    let user = Address::generate(&e);
+    let token_1_admin_client = get_token_admin_client(&e, &setup.token1.address);
+    let token_2_admin_client = get_token_admin_client(&e, &setup.token2.address);
+    token_1_admin_client.mint(&user, &1_000_000_0000000);
+    token_2_admin_client.mint(&user, &1_000_000_0000000);
+    liq_pool.deposit(&user, &Vec::from_array(&e, [100_0000000, 100_0000000]), &0);
+
+    let receiver = create_flash_loan_receiver(&e, &user);
+    token_1_admin_client.mint(&receiver.address, &1_0000000);
+    token_2_admin_client.mint(&receiver.address, &1_0000000);
+    let amounts = Vec::from_array(&e, [10_0000000, 5_0000000]);
+    let repay_with_fee = Bytes::from_array(&e, &[1]);
+
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user,
+                &receiver.address,
+                &amounts,
+                &Bytes::from_array(&e, &[0])
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(212))
+    );
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user,
+                &receiver.address,
+                &Vec::from_array(&e, [1]),
+                &repay_with_fee
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2001))
+    );
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user,
+                &receiver.address,
+                &Vec::from_array(&e, [0, 0]),
+                &repay_with_fee
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2018))
+    );
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(
+                &user,
+                &receiver.address,
+                &Vec::from_array(&e, [100_0000001, 0]),
+                &repay_with_fee
+            )
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2019))
+    );
+
+    // receiver accepts loans initiated by its owner only
+    let third_party = Address::generate(&e);
+    assert!(liq_pool
+        .try_flash_loan(&third_party, &receiver.address, &amounts, &repay_with_fee)
+        .is_err());
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [100_0000000, 100_0000000])
+    );
+
+    // fee is charged as for the swap and goes to the LPs
+    let fees = liq_pool.flash_loan(&user, &receiver.address, &amounts, &repay_with_fee);
+    assert_eq!(
+        e.auths()[0],
+        (
+            user.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    liq_pool.address.clone(),
+                    Symbol::new(&e, "flash_loan"),
+                    (
+                        &user,
+                        &receiver.address,
+                        amounts.clone(),
+                        repay_with_fee.clone()
+                    )
+                        .into_val(&e),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )
+    );
+    assert_eq!(fees, Vec::from_array(&e, [300000, 150000]));
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [100_0300000, 100_0150000])
+    );
+    assert_eq!(setup.token1.balance(&receiver.address), 9700000);
+    assert_eq!(setup.token2.balance(&receiver.address), 9850000);
+
+    // admin share of the fee is excluded from the reserves
+    liq_pool.commit_new_admin_fee(&setup.admin, &5000);
+    jump(&e, ADMIN_ACTIONS_DELAY + 1);
+    liq_pool.apply_new_admin_fee(&setup.admin);
+    liq_pool.flash_loan(&user, &receiver.address, &amounts, &repay_with_fee);
+    assert_eq!(
+        liq_pool.get_admin_balances(),
+        Vec::from_array(&e, [150000, 75000])
+    );
+    assert_eq!(
+        liq_pool.get_reserves(),
+        Vec::from_array(&e, [100_0450000, 100_0225000])
+    );
+
+    liq_pool.kill_swap(&setup.admin);
+    assert_eq!(
+        liq_pool
+            .try_flash_loan(&user, &receiver.address, &amounts, &repay_with_fee)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(206))
+    );
+}
+
+#[test]
+fn test_ma_half_life_committed_independently() {
+    let setup = Setup::default();
+    let liq_pool = setup.liq_pool;
+
+    // other pending parameters can't be applied as the half-life
+    liq_pool.commit_new_fee(&setup.admin, &8);
+    liq_pool.commit_new_admin_fee(&setup.admin, &5000);
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    assert_eq!(
+        liq_pool
+            .try_apply_new_ma_half_life(&setup.admin)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2907))
+    );
+    assert_eq!(liq_pool.get_ma_half_life(), 600);
+
+    liq_pool.commit_new_ma_half_life(&setup.admin, &3600);
+    liq_pool.apply_new_fee(&setup.admin);
+    liq_pool.apply_new_admin_fee(&setup.admin);
+    assert_eq!(
+        liq_pool
+            .try_apply_new_ma_half_life(&setup.admin)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2908))
+    );
+    jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+    liq_pool.apply_new_ma_half_life(&setup.admin);
+    assert_eq!(liq_pool.get_ma_half_life(), 3600);
+    assert_eq!(
+        liq_pool
+            .try_apply_new_ma_half_life(&setup.admin)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2907))
+    );
+}
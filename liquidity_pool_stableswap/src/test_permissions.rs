@@ -1,10 +1,9 @@
 #![cfg(test)]
 
-use crate::pool_constants::MIN_RAMP_TIME;
-use crate::testutils::Setup;
+use crate::testutils::{install_token_wasm, RouterStubClient, Setup};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{symbol_short, Address, Symbol, Vec};
+use soroban_sdk::{symbol_short, Address, Error, Symbol, Vec};
 use token_share::Client as ShareTokenClient;
 use utils::test_utils::{install_dummy_wasm, jump};
 
@@ -288,6 +287,30 @@ fn test_commit_upgrade() {
     }
 }
 
+#[test]
+fn test_commit_upgrade_router_allowlist() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let router = RouterStubClient::new(&setup.env, &setup.router);
+    let new_wasm = install_dummy_wasm(&setup.env);
+    let token_wasm = install_token_wasm(&setup.env);
+
+    router.deny_wasm(&new_wasm);
+    assert_eq!(
+        pool.try_commit_upgrade(&setup.admin, &new_wasm, &token_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2915))
+    );
+    assert_eq!(
+        pool.try_commit_upgrade(&setup.admin, &token_wasm, &new_wasm)
+            .unwrap_err(),
+        Ok(Error::from_contract_error(2915))
+    );
+
+    // router checks the hashes itself when upgrading the pools
+    pool.commit_upgrade(&setup.router, &new_wasm, &token_wasm);
+}
+
 #[test]
 fn test_apply_upgrade_third_party_user() {
     let setup = Setup::default();
@@ -561,12 +584,16 @@ fn test_ramp_a() {
             pool.try_ramp_a(
                 &addr,
                 &(pool.a() * 2),
-                &setup.env.ledger().timestamp().saturating_add(MIN_RAMP_TIME)
+                &setup
+                    .env
+                    .ledger()
+                    .timestamp()
+                    .saturating_add(ADMIN_ACTIONS_DELAY)
             )
             .is_ok(),
             is_ok
         );
-        jump(&setup.env, MIN_RAMP_TIME);
+        jump(&setup.env, ADMIN_ACTIONS_DELAY);
         assert_eq!(pool.try_stop_ramp_a(&addr).is_ok(), is_ok);
     }
 }
@@ -591,3 +618,70 @@ fn test_update_fee() {
         assert_eq!(pool.try_apply_new_fee(&addr).is_ok(), is_ok);
     }
 }
+
+#[test]
+fn test_update_admin_fee() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let user = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.admin, true),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, true),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+    ] {
+        assert_eq!(pool.try_revert_new_parameters(&addr).is_ok(), is_ok);
+        assert_eq!(pool.try_commit_new_admin_fee(&addr, &1).is_ok(), is_ok);
+        jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+        assert_eq!(pool.try_apply_new_admin_fee(&addr).is_ok(), is_ok);
+    }
+}
+
+#[test]
+fn test_update_ma_half_life() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let user = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.admin, true),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, true),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+    ] {
+        assert_eq!(pool.try_revert_new_parameters(&addr).is_ok(), is_ok);
+        assert_eq!(
+            pool.try_commit_new_ma_half_life(&addr, &3600).is_ok(),
+            is_ok
+        );
+        jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
+        assert_eq!(pool.try_apply_new_ma_half_life(&addr).is_ok(), is_ok);
+    }
+}
+
+#[test]
+fn test_withdraw_admin_fees() {
+    let setup = Setup::default();
+    let pool = setup.liq_pool;
+    let user = Address::generate(&setup.env);
+    let destination = Address::generate(&setup.env);
+
+    for (addr, is_ok) in [
+        (user, false),
+        (setup.admin, true),
+        (setup.rewards_admin, false),
+        (setup.operations_admin, true),
+        (setup.pause_admin, false),
+        (setup.emergency_pause_admin, false),
+    ] {
+        assert_eq!(
+            pool.try_withdraw_admin_fees(&addr, &destination).is_ok(),
+            is_ok
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::testutils::{create_contract, install_dummy_wasm, jump, Setup};
 use access_control::constants::ADMIN_ACTIONS_DELAY;
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{symbol_short, Address, Env, Symbol};
+use utils::test_utils::allow_wasm;
 
 // test admin transfer ownership
 #[test]
@@ -306,9 +307,15 @@ fn test_commit_upgrade_emergency_admin() {
 fn test_commit_upgrade_admin() {
     let setup = Setup::default();
     let contract = setup.contract;
-    assert!(contract
-        .try_commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env))
-        .is_ok());
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "LockerFeed",
+        &new_wasm,
+    );
+    assert!(contract.try_commit_upgrade(&setup.admin, &new_wasm).is_ok());
 }
 
 #[test]
@@ -316,7 +323,15 @@ fn test_apply_upgrade_third_party_user() {
     let setup = Setup::default();
     let contract = setup.contract;
     let user = Address::generate(&setup.env);
-    contract.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "LockerFeed",
+        &new_wasm,
+    );
+    contract.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(contract.try_apply_upgrade(&user).is_err());
 }
@@ -325,7 +340,15 @@ fn test_apply_upgrade_third_party_user() {
 fn test_apply_upgrade_emergency_admin() {
     let setup = Setup::default();
     let contract = setup.contract;
-    contract.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "LockerFeed",
+        &new_wasm,
+    );
+    contract.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(contract.try_apply_upgrade(&setup.emergency_admin).is_err());
 }
@@ -334,7 +357,15 @@ fn test_apply_upgrade_emergency_admin() {
 fn test_apply_upgrade_admin() {
     let setup = Setup::default();
     let contract = setup.contract;
-    contract.commit_upgrade(&setup.admin, &install_dummy_wasm(&setup.env));
+    let new_wasm = install_dummy_wasm(&setup.env);
+    allow_wasm(
+        &setup.env,
+        &contract.address,
+        &setup.admin,
+        "LockerFeed",
+        &new_wasm,
+    );
+    contract.commit_upgrade(&setup.admin, &new_wasm);
     jump(&setup.env, ADMIN_ACTIONS_DELAY + 1);
     assert!(contract.try_apply_upgrade(&setup.admin).is_ok());
 }
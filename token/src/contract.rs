@@ -8,10 +8,11 @@ use crate::pool::{checkpoint_user_rewards, checkpoint_user_working_balance};
 use access_control::access::{AccessControl, AccessControlTrait};
 use access_control::errors::AccessControlError;
 use access_control::events::Events as AccessControlEvents;
-use access_control::interface::TransferableContract;
+use access_control::interface::{AccessControlStateContract, TransferableContract};
 use access_control::management::SingleAddressManagementTrait;
 use access_control::role::{Role, SymbolRepresentation};
 use access_control::transfer::TransferOwnershipTrait;
+use access_control::view::{get_access_control_state, AccessControlState};
 use soroban_sdk::token::{self, Interface as _};
 use soroban_sdk::{contract, contractimpl, panic_with_error, Address, BytesN, Env, String, Symbol};
 use soroban_token_sdk::metadata::TokenMetadata;
@@ -262,3 +263,18 @@ impl TransferableContract for Token {
         }
     }
 }
+
+// The `AccessControlStateContract` trait provides the view of the roles and pending role changes.
+// Token is upgraded along with the pool, so it has no upgrade state of its own.
+#[contractimpl]
+impl AccessControlStateContract for Token {
+    // Returns the roles state along with the configurable delays.
+    //
+    // # Returns
+    //
+    // The list of roles with current holders, pending transfers, signers and their deadlines
+    // and the configurable delays.
+    fn get_admin_state(e: Env) -> AccessControlState {
+        get_access_control_state(&e)
+    }
+}
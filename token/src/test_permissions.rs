@@ -83,6 +83,25 @@ fn test_transfer_ownership() {
     collector.commit_transfer_ownership(&admin_new, &symbol_short!("Admin"), &admin_new);
 }
 
+#[test]
+fn test_admin_state() {
+    let setup = Setup::default();
+    let token = setup.token;
+    let admin_new = Address::generate(&setup.env);
+
+    token.commit_transfer_ownership(&setup.admin, &symbol_short!("Admin"), &admin_new);
+    let state = token.get_admin_state();
+    assert_eq!(state.delays.len(), 3);
+    let admin_state = state.roles.get(0).unwrap();
+    assert_eq!(admin_state.name, symbol_short!("Admin"));
+    assert_eq!(admin_state.addresses.get(0).unwrap(), setup.admin);
+    assert_eq!(admin_state.future_address, Some(admin_new));
+    assert_eq!(
+        admin_state.deadline,
+        setup.env.ledger().timestamp() + ADMIN_ACTIONS_DELAY
+    );
+}
+
 // upgrade
 #[test]
 fn test_upgrade_third_party_user() {
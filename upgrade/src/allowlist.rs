@@ -0,0 +1,253 @@
+// Wasm allowlist of the audited code hashes per contract kind.
+//
+// Allowlist is empty after the deployment and rejects every upgrade until bootstrapped:
+// 1. upgrade and migrate the router (or the standalone contract) to the allowlist-aware code
+// 2. commit allowlist changes for every kind and hash of the release at once,
+//    changes are tracked per (kind, hash) so they all share the same deadline
+// 3. wait for the `WasmAllowlist` delay and apply each of the changes
+// 4. commit the upgrades, now checked against the allowlist
+use crate::constants::MAX_PENDING_ALLOWLIST_CHANGES;
+use crate::errors::Error;
+use crate::events::Events;
+use crate::storage::{
+    get_future_allowlist_changes, put_allowed_wasms, put_future_allowlist_changes,
+};
+use access_control::access::{AccessControl, AccessControlTrait};
+use access_control::delays::{Delay, DelaysTrait};
+use access_control::role::{Role, SymbolRepresentation};
+use soroban_sdk::{contracttype, panic_with_error, Address, BytesN, Env, IntoVal, Symbol, Vec};
+
+// Kinds of the contracts code. Allowlist is maintained separately for each of them
+#[derive(Clone)]
+pub enum WasmKind {
+    Router,
+    StandardPool,
+    StableSwapPool,
+    Token,
+    Plane,
+    LiquidityCalculator,
+    FeesCollector,
+    LockerFeed,
+    ProviderSwapFeeFactory,
+    ProviderSwapFee,
+}
+
+impl WasmKind {
+    fn name(&self) -> &'static str {
+        match self {
+            WasmKind::Router => "Router",
+            WasmKind::StandardPool => "StandardPool",
+            WasmKind::StableSwapPool => "StableSwapPool",
+            WasmKind::Token => "Token",
+            WasmKind::Plane => "Plane",
+            WasmKind::LiquidityCalculator => "LiquidityCalculator",
+            WasmKind::FeesCollector => "FeesCollector",
+            WasmKind::LockerFeed => "LockerFeed",
+            WasmKind::ProviderSwapFeeFactory => "ProviderSwapFeeFactory",
+            WasmKind::ProviderSwapFee => "ProviderSwapFee",
+        }
+    }
+}
+
+impl SymbolRepresentation for WasmKind {
+    fn as_symbol(&self, e: &Env) -> Symbol {
+        Symbol::new(e, self.name())
+    }
+
+    fn from_symbol(e: &Env, value: Symbol) -> Self {
+        for kind in [
+            WasmKind::Router,
+            WasmKind::StandardPool,
+            WasmKind::StableSwapPool,
+            WasmKind::Token,
+            WasmKind::Plane,
+            WasmKind::LiquidityCalculator,
+            WasmKind::FeesCollector,
+            WasmKind::LockerFeed,
+            WasmKind::ProviderSwapFeeFactory,
+            WasmKind::ProviderSwapFee,
+        ] {
+            if value == kind.as_symbol(e) {
+                return kind;
+            }
+        }
+        panic_with_error!(e, Error::UnknownWasmKind)
+    }
+}
+
+// Pending allowlist change, applied after the allowlist delay
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct WasmAllowlistChange {
+    pub kind: Symbol,
+    pub wasm_hash: BytesN<32>,
+    pub is_allowed: bool,
+    pub deadline: u64,
+}
+
+pub fn get_allowed_wasms(e: &Env, kind: &WasmKind) -> Vec<BytesN<32>> {
+    crate::storage::get_allowed_wasms(e, &kind.as_symbol(e)).unwrap_or(Vec::new(e))
+}
+
+// Only explicitly allowed hashes are accepted, unconfigured allowlist rejects everything
+pub fn is_wasm_allowed(e: &Env, kind: &WasmKind, wasm_hash: &BytesN<32>) -> bool {
+    get_allowed_wasms(e, kind).contains(wasm_hash)
+}
+
+pub fn require_wasm_allowed(e: &Env, kind: &WasmKind, wasm_hash: &BytesN<32>) {
+    if !is_wasm_allowed(e, kind, wasm_hash) {
+        panic_with_error!(e, Error::WasmNotAllowed);
+    }
+}
+
+// Contracts deployed by the router share the router allowlist instead of keeping their own
+pub fn require_wasm_allowed_by(
+    e: &Env,
+    allowlist_contract: &Address,
+    kind: &WasmKind,
+    wasm_hash: &BytesN<32>,
+) {
+    let is_allowed: bool = e.invoke_contract(
+        allowlist_contract,
+        &Symbol::new(e, "is_wasm_allowed"),
+        Vec::from_array(e, [kind.as_symbol(e).into_val(e), wasm_hash.into_val(e)]),
+    );
+    if !is_allowed {
+        panic_with_error!(e, Error::WasmNotAllowed);
+    }
+}
+
+pub fn get_pending_allowlist_changes(e: &Env) -> Vec<WasmAllowlistChange> {
+    get_future_allowlist_changes(e)
+}
+
+fn find_pending_change(
+    changes: &Vec<WasmAllowlistChange>,
+    kind: &Symbol,
+    wasm_hash: &BytesN<32>,
+) -> Option<u32> {
+    for (idx, change) in changes.iter().enumerate() {
+        if &change.kind == kind && &change.wasm_hash == wasm_hash {
+            return Some(idx as u32);
+        }
+    }
+    None
+}
+
+// Allowlist has its own delay, so the admin is not able to push unaudited code
+// faster than the allowlist delay even if the upgrade delay is shorter.
+// Changes are tracked per (kind, hash), so the whole release is committed at once
+pub fn commit_allowlist_change(
+    e: &Env,
+    kind: &WasmKind,
+    wasm_hash: &BytesN<32>,
+    is_allowed: bool,
+) -> WasmAllowlistChange {
+    let mut changes = get_future_allowlist_changes(e);
+    if find_pending_change(&changes, &kind.as_symbol(e), wasm_hash).is_some() {
+        panic_with_error!(e, Error::AnotherActionActive);
+    }
+    if changes.len() >= MAX_PENDING_ALLOWLIST_CHANGES {
+        panic_with_error!(e, Error::TooManyPendingChanges);
+    }
+
+    let change = WasmAllowlistChange {
+        kind: kind.as_symbol(e),
+        wasm_hash: wasm_hash.clone(),
+        is_allowed,
+        deadline: e.ledger().timestamp() + AccessControl::new(e).get_delay(&Delay::WasmAllowlist),
+    };
+    changes.push_back(change.clone());
+    put_future_allowlist_changes(e, &changes);
+    change
+}
+
+pub fn apply_allowlist_change(
+    e: &Env,
+    kind: &WasmKind,
+    wasm_hash: &BytesN<32>,
+) -> WasmAllowlistChange {
+    let mut changes = get_future_allowlist_changes(e);
+    let idx = match find_pending_change(&changes, &kind.as_symbol(e), wasm_hash) {
+        Some(v) => v,
+        None => panic_with_error!(e, Error::NoActionActive),
+    };
+    let change = changes.get(idx).unwrap();
+    if e.ledger().timestamp() < change.deadline {
+        panic_with_error!(e, Error::ActionNotReadyYet);
+    }
+
+    let mut allowed = crate::storage::get_allowed_wasms(e, &change.kind).unwrap_or(Vec::new(e));
+    match (change.is_allowed, allowed.first_index_of(&change.wasm_hash)) {
+        (true, None) => allowed.push_back(change.wasm_hash.clone()),
+        (false, Some(idx)) => {
+            allowed.remove(idx);
+        }
+        _ => {}
+    }
+    put_allowed_wasms(e, &change.kind, &allowed);
+    changes.remove(idx);
+    put_future_allowlist_changes(e, &changes);
+    change
+}
+
+pub fn revert_allowlist_change(e: &Env, kind: &WasmKind, wasm_hash: &BytesN<32>) {
+    let mut changes = get_future_allowlist_changes(e);
+    let idx = match find_pending_change(&changes, &kind.as_symbol(e), wasm_hash) {
+        Some(v) => v,
+        None => panic_with_error!(e, Error::NoActionActive),
+    };
+    changes.remove(idx);
+    put_future_allowlist_changes(e, &changes);
+}
+
+// Commit the allowlist change on behalf of the admin
+pub fn commit_wasm_allowlist_change(
+    e: &Env,
+    admin: &Address,
+    kind_name: Symbol,
+    wasm_hash: &BytesN<32>,
+    is_allowed: bool,
+) {
+    admin.require_auth();
+    AccessControl::new(e).assert_address_has_role(admin, &Role::Admin);
+
+    let kind = WasmKind::from_symbol(e, kind_name);
+    let change = commit_allowlist_change(e, &kind, wasm_hash, is_allowed);
+    Events::new(e).commit_wasm_allowlist_change(
+        change.kind,
+        change.wasm_hash,
+        change.is_allowed,
+        change.deadline,
+    );
+}
+
+// Apply the allowlist change for the (kind, hash) pair on behalf of the admin
+pub fn apply_wasm_allowlist_change(
+    e: &Env,
+    admin: &Address,
+    kind_name: Symbol,
+    wasm_hash: &BytesN<32>,
+) {
+    admin.require_auth();
+    AccessControl::new(e).assert_address_has_role(admin, &Role::Admin);
+
+    let kind = WasmKind::from_symbol(e, kind_name);
+    let change = apply_allowlist_change(e, &kind, wasm_hash);
+    Events::new(e).apply_wasm_allowlist_change(change.kind, change.wasm_hash, change.is_allowed);
+}
+
+// Revert the allowlist change for the (kind, hash) pair on behalf of the admin
+pub fn revert_wasm_allowlist_change(
+    e: &Env,
+    admin: &Address,
+    kind_name: Symbol,
+    wasm_hash: &BytesN<32>,
+) {
+    admin.require_auth();
+    AccessControl::new(e).assert_address_has_role(admin, &Role::Admin);
+
+    let kind = WasmKind::from_symbol(e, kind_name);
+    revert_allowlist_change(e, &kind, wasm_hash);
+    Events::new(e).revert_wasm_allowlist_change(kind.as_symbol(e), wasm_hash.clone());
+}
@@ -1 +1,2 @@
-pub const UPGRADE_DELAY: u64 = 3 * 86400; // 3 days
+pub const UPGRADE_HISTORY_SIZE: u32 = 10; // number of the latest upgrades to keep
+pub const MAX_PENDING_ALLOWLIST_CHANGES: u32 = 20; // allowlist changes awaiting the delay
@@ -7,4 +7,15 @@ pub enum Error {
     AnotherActionActive = 2906,
     NoActionActive = 2907,
     ActionNotReadyYet = 2908,
+    NoPreviousWasm = 2911,
+
+    // migration errors
+    AlreadyMigrated = 2912,
+    MigrationOutOfOrder = 2913,
+    NotMigrated = 2914,
+
+    // wasm allowlist errors
+    WasmNotAllowed = 2915,
+    UnknownWasmKind = 2916,
+    TooManyPendingChanges = 2917,
 }
@@ -26,6 +26,57 @@ impl Events {
             .publish((Symbol::new(self.env(), "apply_upgrade"),), new_wasms)
     }
 
+    pub fn rollback_upgrade(&self, wasm_hash: BytesN<32>) {
+        self.env()
+            .events()
+            .publish((Symbol::new(self.env(), "rollback_upgrade"),), (wasm_hash,))
+    }
+
+    pub fn migrate(&self, from_version: u32, to_version: u32) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "migrate"),),
+            (from_version, to_version),
+        )
+    }
+
+    pub fn commit_wasm_allowlist_change(
+        &self,
+        kind: Symbol,
+        wasm_hash: BytesN<32>,
+        is_allowed: bool,
+        deadline: u64,
+    ) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "commit_wasm_allowlist_change"),
+                kind,
+            ),
+            (wasm_hash, is_allowed, deadline),
+        )
+    }
+
+    pub fn apply_wasm_allowlist_change(
+        &self,
+        kind: Symbol,
+        wasm_hash: BytesN<32>,
+        is_allowed: bool,
+    ) {
+        self.env().events().publish(
+            (Symbol::new(self.env(), "apply_wasm_allowlist_change"), kind),
+            (wasm_hash, is_allowed),
+        )
+    }
+
+    pub fn revert_wasm_allowlist_change(&self, kind: Symbol, wasm_hash: BytesN<32>) {
+        self.env().events().publish(
+            (
+                Symbol::new(self.env(), "revert_wasm_allowlist_change"),
+                kind,
+            ),
+            (wasm_hash,),
+        )
+    }
+
     pub fn revert_upgrade(&self) {
         self.env()
             .events()
@@ -0,0 +1,26 @@
+use crate::constants::UPGRADE_HISTORY_SIZE;
+use crate::storage::{get_upgrade_history, put_upgrade_history};
+use soroban_sdk::{contracttype, Address, BytesN, Env};
+
+// Wasm hash installed by the upgrade or rollback
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UpgradeRecord {
+    pub wasm_hash: BytesN<32>,
+    pub timestamp: u64,
+    pub admin: Address,
+}
+
+// Keep only the latest records to limit the instance storage size
+pub(crate) fn record_upgrade(e: &Env, wasm_hash: &BytesN<32>, admin: &Address) {
+    let mut history = get_upgrade_history(e);
+    history.push_back(UpgradeRecord {
+        wasm_hash: wasm_hash.clone(),
+        timestamp: e.ledger().timestamp(),
+        admin: admin.clone(),
+    });
+    while history.len() > UPGRADE_HISTORY_SIZE {
+        history.pop_front();
+    }
+    put_upgrade_history(e, &history);
+}
@@ -1,4 +1,7 @@
-use soroban_sdk::{Address, BytesN, Env};
+use crate::allowlist::WasmAllowlistChange;
+use crate::history::UpgradeRecord;
+use crate::view::AdminState;
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
 
 pub trait UpgradeableContract {
     // Get contract version
@@ -9,7 +12,50 @@ pub trait UpgradeableContract {
     fn apply_upgrade(e: Env, admin: Address) -> BytesN<32>;
     fn revert_upgrade(e: Env, admin: Address);
 
+    // Rollback to the previously running wasm without delay
+    fn rollback_upgrade(e: Env, emergency_admin: Address) -> BytesN<32>;
+
+    // Get latest applied upgrades
+    fn get_upgrade_history(e: Env) -> Vec<UpgradeRecord>;
+
+    // Migrate storage to the current contract version after the upgrade
+    fn migrate(e: Env, admin: Address);
+    fn get_storage_version(e: Env) -> u32;
+
     // Emergency mode - bypass upgrade deadline
     fn set_emergency_mode(e: Env, admin: Address, value: bool);
     fn get_emergency_mode(e: Env) -> bool;
 }
+
+pub trait AdminStateContract {
+    // Get roles, pending admin actions and emergency mode in a single call
+    fn get_admin_state(e: Env) -> AdminState;
+}
+
+pub trait WasmAllowlistContract {
+    // Allow or disallow wasm hash for the contract kind after the allowlist delay
+    fn commit_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+        is_allowed: bool,
+    );
+    fn apply_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    );
+    fn revert_wasm_allowlist_change(
+        e: Env,
+        admin: Address,
+        kind_name: Symbol,
+        wasm_hash: BytesN<32>,
+    );
+
+    // Get audited wasm hashes for the contract kind
+    fn get_allowed_wasms(e: Env, kind_name: Symbol) -> Vec<BytesN<32>>;
+    fn is_wasm_allowed(e: Env, kind_name: Symbol, wasm_hash: BytesN<32>) -> bool;
+    fn get_pending_wasm_allowlist_changes(e: Env) -> Vec<WasmAllowlistChange>;
+}
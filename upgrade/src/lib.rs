@@ -1,35 +1,82 @@
 #![no_std]
 
+pub mod allowlist;
 mod constants;
 mod errors;
 pub mod events;
+pub mod history;
 pub mod interface;
 #[cfg(feature = "certora")]
 pub mod storage;
 #[cfg(not(feature = "certora"))]
 mod storage;
+pub mod view;
 
-use crate::constants::UPGRADE_DELAY;
+use crate::allowlist::{require_wasm_allowed, require_wasm_allowed_by, WasmKind};
 use crate::errors::Error;
+use crate::history::{record_upgrade, UpgradeRecord};
 use crate::storage::{
-    get_future_wasm, get_upgrade_deadline, put_future_wasm, put_upgrade_deadline,
+    get_current_wasm, get_future_wasm, get_previous_wasm, get_upgrade_deadline, put_current_wasm,
+    put_future_wasm, put_previous_wasm, put_upgrade_deadline, remove_future_wasm,
+    remove_previous_wasm,
 };
-use access_control::emergency::get_emergency_mode;
-use soroban_sdk::{panic_with_error, BytesN, Env};
+use access_control::access::AccessControl;
+use access_control::delays::{Delay, DelaysTrait};
+use access_control::emergency::{is_emergency_mode_active, EmergencyScope};
+use soroban_sdk::{panic_with_error, Address, BytesN, Env, Vec};
 use utils::storage_errors::StorageError;
 
-pub fn commit_upgrade(e: &Env, new_wasm_hash: &BytesN<32>) {
+pub fn commit_upgrade(e: &Env, kind: &WasmKind, new_wasm_hash: &BytesN<32>) {
     if get_upgrade_deadline(e) != 0 {
         panic_with_error!(e, Error::AnotherActionActive);
     }
+    require_wasm_allowed(e, kind, new_wasm_hash);
+    put_upgrade(e, new_wasm_hash);
+}
+
+// Same as `commit_upgrade`, but the hash is checked against the allowlist of another contract
+pub fn commit_upgrade_allowed_by(
+    e: &Env,
+    allowlist_contract: &Address,
+    kind: &WasmKind,
+    new_wasm_hash: &BytesN<32>,
+) {
+    if get_upgrade_deadline(e) != 0 {
+        panic_with_error!(e, Error::AnotherActionActive);
+    }
+    require_wasm_allowed_by(e, allowlist_contract, kind, new_wasm_hash);
+    put_upgrade(e, new_wasm_hash);
+}
 
-    let deadline = e.ledger().timestamp() + UPGRADE_DELAY;
+// Same as `commit_upgrade`, for the hash already checked against the allowlist by the caller
+pub fn commit_checked_upgrade(e: &Env, new_wasm_hash: &BytesN<32>) {
+    if get_upgrade_deadline(e) != 0 {
+        panic_with_error!(e, Error::AnotherActionActive);
+    }
+    put_upgrade(e, new_wasm_hash);
+}
+
+fn put_upgrade(e: &Env, new_wasm_hash: &BytesN<32>) {
+    record_running_wasm(e);
+    let deadline = e.ledger().timestamp() + AccessControl::new(e).get_delay(&Delay::Upgrade);
     put_upgrade_deadline(e, &deadline);
     put_future_wasm(e, &new_wasm_hash);
 }
 
-pub fn apply_upgrade(e: &Env) -> BytesN<32> {
-    if !get_emergency_mode(e) {
+// Code deployed before the rollback was introduced doesn't track the running wasm.
+// It leaves the applied hash as the future wasm, while `revert_upgrade` drops the reverted one,
+// so the future wasm without the pending upgrade is the running one
+fn record_running_wasm(e: &Env) {
+    if get_current_wasm(e).is_some() || get_upgrade_deadline(e) != 0 {
+        return;
+    }
+    if let Some(wasm_hash) = get_future_wasm(e) {
+        put_current_wasm(e, &wasm_hash);
+    }
+}
+
+pub fn apply_upgrade(e: &Env, admin: &Address) -> BytesN<32> {
+    if !is_emergency_mode_active(e, &EmergencyScope::Upgrade) {
         if e.ledger().timestamp() < get_upgrade_deadline(e) {
             panic_with_error!(e, Error::ActionNotReadyYet);
         }
@@ -45,9 +92,70 @@ pub fn apply_upgrade(e: &Env) -> BytesN<32> {
     };
     e.deployer()
         .update_current_contract_wasm(new_wasm_hash.clone());
+
+    // remember currently running wasm to be able to rollback to it
+    if let Some(current_wasm_hash) = get_current_wasm(e) {
+        put_previous_wasm(e, &current_wasm_hash);
+    }
+    put_current_wasm(e, &new_wasm_hash);
+    record_upgrade(e, &new_wasm_hash, admin);
     new_wasm_hash
 }
 
+// Restore previously running wasm immediately. Rollback target is consumed to avoid
+// switching back to the faulty wasm with the second call
+pub fn rollback_upgrade(e: &Env, admin: &Address) -> BytesN<32> {
+    let previous_wasm_hash = match get_previous_wasm(e) {
+        Some(v) => v,
+        None => panic_with_error!(e, Error::NoPreviousWasm),
+    };
+
+    e.deployer()
+        .update_current_contract_wasm(previous_wasm_hash.clone());
+    remove_previous_wasm(e);
+    put_current_wasm(e, &previous_wasm_hash);
+    record_upgrade(e, &previous_wasm_hash, admin);
+    previous_wasm_hash
+}
+
+pub fn get_upgrade_history(e: &Env) -> Vec<UpgradeRecord> {
+    crate::storage::get_upgrade_history(e)
+}
+
 pub fn revert_upgrade(e: &Env) {
     put_upgrade_deadline(e, &0);
+    remove_future_wasm(e);
+}
+
+// Storage schema version should be set on the contract initialization
+pub fn set_storage_version(e: &Env, version: u32) {
+    crate::storage::put_storage_version(e, &version);
+}
+
+pub fn get_storage_version(e: &Env) -> u32 {
+    crate::storage::get_storage_version(e)
+}
+
+// Bump storage schema version to the code version. Returns the version storage is migrated from
+pub fn migrate(e: &Env, version: u32) -> u32 {
+    let storage_version = get_storage_version(e);
+    if storage_version == version {
+        panic_with_error!(e, Error::AlreadyMigrated);
+    }
+    if storage_version > version {
+        panic_with_error!(e, Error::MigrationOutOfOrder);
+    }
+
+    set_storage_version(e, version);
+    // rollback target of the first upgrade is known only if the running wasm is recorded
+    record_running_wasm(e);
+    storage_version
+}
+
+// Code newer than the storage schema should not modify the state until migrated.
+// Storage newer than the code is allowed to keep the contract operational after the rollback
+pub fn require_migrated(e: &Env, version: u32) {
+    if get_storage_version(e) < version {
+        panic_with_error!(e, Error::NotMigrated);
+    }
 }
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracttype, BytesN, Env};
+use crate::allowlist::WasmAllowlistChange;
+use crate::history::UpgradeRecord;
+use soroban_sdk::{contracttype, BytesN, Env, Symbol, Vec};
 use utils::bump::bump_instance;
 
 #[cfg(feature = "certora")]
@@ -9,6 +11,18 @@ use ghost_state::GhostState;
 enum DataKey {
     UpgradeDeadline,
     FutureWASM,
+
+    // rollback
+    CurrentWASM,
+    PreviousWASM,
+    UpgradeHistory,
+
+    // storage schema version, bumped by the migration
+    StorageVersion,
+
+    // audited wasm hashes per contract kind
+    AllowedWASM(Symbol),
+    FutureAllowlistChanges,
 }
 
 // upgrade deadline
@@ -68,3 +82,95 @@ pub fn put_future_wasm(e: &Env, value: &BytesN<32>) {
         });
     }
 }
+
+pub fn remove_future_wasm(e: &Env) {
+    bump_instance(e);
+    e.storage().instance().remove(&DataKey::FutureWASM);
+
+    #[cfg(feature = "certora")]
+    {
+        GhostState::update(|state| {
+            state.future_wasm = None;
+        });
+    }
+}
+
+pub fn get_current_wasm(e: &Env) -> Option<BytesN<32>> {
+    bump_instance(e);
+    e.storage().instance().get(&DataKey::CurrentWASM)
+}
+
+pub fn put_current_wasm(e: &Env, value: &BytesN<32>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::CurrentWASM, value);
+}
+
+pub fn get_previous_wasm(e: &Env) -> Option<BytesN<32>> {
+    bump_instance(e);
+    e.storage().instance().get(&DataKey::PreviousWASM)
+}
+
+pub fn put_previous_wasm(e: &Env, value: &BytesN<32>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::PreviousWASM, value);
+}
+
+pub fn remove_previous_wasm(e: &Env) {
+    bump_instance(e);
+    e.storage().instance().remove(&DataKey::PreviousWASM);
+}
+
+pub fn get_upgrade_history(e: &Env) -> Vec<UpgradeRecord> {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::UpgradeHistory)
+        .unwrap_or(Vec::new(e))
+}
+
+pub fn put_upgrade_history(e: &Env, value: &Vec<UpgradeRecord>) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::UpgradeHistory, value);
+}
+
+pub fn get_storage_version(e: &Env) -> u32 {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::StorageVersion)
+        .unwrap_or(0)
+}
+
+pub fn put_storage_version(e: &Env, value: &u32) {
+    bump_instance(e);
+    e.storage().instance().set(&DataKey::StorageVersion, value);
+}
+
+pub fn get_allowed_wasms(e: &Env, kind: &Symbol) -> Option<Vec<BytesN<32>>> {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::AllowedWASM(kind.clone()))
+}
+
+pub fn put_allowed_wasms(e: &Env, kind: &Symbol, value: &Vec<BytesN<32>>) {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .set(&DataKey::AllowedWASM(kind.clone()), value);
+}
+
+pub fn get_future_allowlist_changes(e: &Env) -> Vec<WasmAllowlistChange> {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .get(&DataKey::FutureAllowlistChanges)
+        .unwrap_or(Vec::new(e))
+}
+
+pub fn put_future_allowlist_changes(e: &Env, value: &Vec<WasmAllowlistChange>) {
+    bump_instance(e);
+    e.storage()
+        .instance()
+        .set(&DataKey::FutureAllowlistChanges, value);
+}
@@ -0,0 +1,59 @@
+use crate::allowlist::{get_pending_allowlist_changes, WasmAllowlistChange};
+use crate::storage::{get_future_wasm, get_upgrade_deadline};
+use access_control::access::AccessControl;
+use access_control::emergency::{get_emergency_mode, get_emergency_mode_state, EmergencyModeState};
+use access_control::view::{DelayState, RoleState, RolesViewTrait};
+use soroban_sdk::{contracttype, BytesN, Env, Symbol, Vec};
+
+// Contract specific admin action waiting for its deadline, like the protocol fee change
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PendingAction {
+    pub name: Symbol,
+    pub deadline: u64,
+}
+
+// Roles and pending admin actions of the contract
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AdminState {
+    pub roles: Vec<RoleState>,
+    pub delays: Vec<DelayState>,
+    pub future_wasm: Option<BytesN<32>>,
+    pub upgrade_deadline: u64,
+    pub pending_allowlist_changes: Vec<WasmAllowlistChange>,
+    pub pending_actions: Vec<PendingAction>,
+    pub emergency_mode: bool,
+    pub emergency_mode_state: EmergencyModeState,
+}
+
+// Contracts pass their own pending actions as (name, deadline) pairs, zero deadline is skipped
+pub fn get_admin_state(e: &Env, actions: &[(&str, u64)]) -> AdminState {
+    let access_control = AccessControl::new(e);
+    let upgrade_deadline = get_upgrade_deadline(e);
+
+    let mut pending_actions = Vec::new(e);
+    for (name, deadline) in actions {
+        if *deadline != 0 {
+            pending_actions.push_back(PendingAction {
+                name: Symbol::new(e, name),
+                deadline: *deadline,
+            });
+        }
+    }
+
+    AdminState {
+        roles: access_control.get_roles_state(),
+        delays: access_control.get_delays_state(),
+        // future wasm is kept in storage after upgrade, so expose it only while upgrade is pending
+        future_wasm: match upgrade_deadline {
+            0 => None,
+            _ => get_future_wasm(e),
+        },
+        upgrade_deadline,
+        pending_allowlist_changes: get_pending_allowlist_changes(e),
+        pending_actions,
+        emergency_mode: get_emergency_mode(e),
+        emergency_mode_state: get_emergency_mode_state(e),
+    }
+}
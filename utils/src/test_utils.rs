@@ -1,7 +1,7 @@
 #![cfg(any(test, feature = "testutils"))]
 
 use soroban_sdk::testutils::{Ledger, LedgerInfo};
-use soroban_sdk::{BytesN, Env, U256};
+use soroban_sdk::{vec, Address, BytesN, Env, IntoVal, Symbol, Val, U256};
 
 pub fn assert_approx_eq_abs(a: u128, b: u128, delta: u128) {
     assert!(
@@ -55,3 +55,43 @@ pub fn install_dummy_wasm<'a>(e: &Env) -> BytesN<32> {
     soroban_sdk::contractimport!(file = "../contracts/dummy_contract.wasm");
     e.deployer().upload_contract_wasm(WASM)
 }
+
+// Allows the wasm hash in the contract allowlist waiting out the allowlist delay.
+// Ledger time is restored afterwards, so the timing of the test is not affected.
+pub fn allow_wasm(
+    e: &Env,
+    contract: &Address,
+    admin: &Address,
+    kind_name: &str,
+    wasm_hash: &BytesN<32>,
+) {
+    e.invoke_contract::<Val>(
+        contract,
+        &Symbol::new(e, "commit_wasm_allowlist_change"),
+        vec![
+            e,
+            admin.into_val(e),
+            Symbol::new(e, kind_name).into_val(e),
+            wasm_hash.into_val(e),
+            true.into_val(e),
+        ],
+    );
+    let delay: u64 = e.invoke_contract(
+        contract,
+        &Symbol::new(e, "get_delay"),
+        vec![e, Symbol::new(e, "WasmAllowlist").into_val(e)],
+    );
+    let timestamp = e.ledger().timestamp();
+    jump(e, delay);
+    e.invoke_contract::<Val>(
+        contract,
+        &Symbol::new(e, "apply_wasm_allowlist_change"),
+        vec![
+            e,
+            admin.into_val(e),
+            Symbol::new(e, kind_name).into_val(e),
+            wasm_hash.into_val(e),
+        ],
+    );
+    e.ledger().set_timestamp(timestamp);
+}